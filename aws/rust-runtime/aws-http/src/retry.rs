@@ -27,14 +27,18 @@ const THROTTLING_ERRORS: &[&str] = &[
     "EC2ThrottledException",
 ];
 const TRANSIENT_ERRORS: &[&str] = &["RequestTimeout", "RequestTimeoutException"];
+/// Status codes for which the standard `Retry-After` header is honored
+const RETRY_AFTER_STATUS_CODES: &[u16] = &[429, 503];
 
 /// Implementation of [`ClassifyRetry`] that classifies AWS error codes.
 ///
 /// In order of priority:
 /// 1. The `x-amz-retry-after` header is checked
-/// 2. The modeled error retry mode is checked
-/// 3. The code is checked against a predetermined list of throttling errors & transient error codes
-/// 4. The status code is checked against a predetermined list of status codes
+/// 2. For `429 Too Many Requests` and `503 Service Unavailable` responses, the standard
+///    `Retry-After` header is checked. Only delays given in seconds are supported.
+/// 3. The modeled error retry mode is checked
+/// 4. The code is checked against a predetermined list of throttling errors & transient error codes
+/// 5. The status code is checked against a predetermined list of status codes
 #[non_exhaustive]
 #[derive(Clone, Debug)]
 pub struct AwsResponseRetryClassifier;
@@ -74,6 +78,17 @@ where
         {
             return RetryKind::Explicit(Duration::from_millis(retry_after_delay));
         }
+        if RETRY_AFTER_STATUS_CODES.contains(&response.http().status().as_u16()) {
+            if let Some(retry_after_delay) = response
+                .http()
+                .headers()
+                .get(http::header::RETRY_AFTER)
+                .and_then(|header| header.to_str().ok())
+                .and_then(|header| header.trim().parse::<u64>().ok())
+            {
+                return RetryKind::Explicit(Duration::from_secs(retry_after_delay));
+            }
+        }
         if let Some(kind) = err.retryable_error_kind() {
             return RetryKind::Error(kind);
         };
@@ -258,6 +273,42 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_standard_retry_after_header() {
+        let policy = AwsResponseRetryClassifier::new();
+        let test_response = http::Response::builder()
+            .status(429)
+            .header("retry-after", "3")
+            .body("retry later")
+            .unwrap();
+        assert_eq!(
+            policy.classify_retry(make_err(UnmodeledError, test_response).as_ref()),
+            RetryKind::Explicit(Duration::from_secs(3))
+        );
+
+        // Other errors don't dictate the delay, even if they include the header
+        let test_response = http::Response::builder()
+            .status(500)
+            .header("retry-after", "3")
+            .body("retry later")
+            .unwrap();
+        assert_eq!(
+            policy.classify_retry(make_err(UnmodeledError, test_response).as_ref()),
+            RetryKind::Error(ErrorKind::TransientError)
+        );
+
+        // HTTP dates aren't supported, so classification falls through to the status code
+        let test_response = http::Response::builder()
+            .status(503)
+            .header("retry-after", "Wed, 21 Oct 2015 07:28:00 GMT")
+            .body("retry later")
+            .unwrap();
+        assert_eq!(
+            policy.classify_retry(make_err(UnmodeledError, test_response).as_ref()),
+            RetryKind::Error(ErrorKind::TransientError)
+        );
+    }
+
     #[test]
     fn classify_response_error() {
         let policy = AwsResponseRetryClassifier::new();
@@ -3,10 +3,15 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use aws_credential_types::Credentials;
+use aws_sigv4::http_request::{sign, SignableBody, SignableRequest, SigningSettings};
+use aws_sigv4::SigningParams;
 use aws_smithy_http::body::SdkBody;
+use aws_smithy_runtime::interceptors::{RequestBodyHash, SignatureAlgorithm};
 use aws_smithy_runtime::{AuthOrchestrator, BoxError};
 use aws_smithy_runtime_api::config_bag::ConfigBag;
 use aws_smithy_runtime_api::runtime_plugin::RuntimePlugin;
+use std::time::SystemTime;
 
 #[derive(Debug)]
 pub struct GetObjectAuthOrc {}
@@ -57,3 +62,59 @@ impl AuthOrchestrator<http::Request<SdkBody>> for GetObjectAuthOrc {
         // Ok(())
     }
 }
+
+/// SigV4 header signing for use with a `HeaderSigningInterceptor`
+///
+/// Interceptor hooks are synchronous, so this takes already-resolved credentials rather than a
+/// credentials provider.
+#[derive(Debug)]
+pub struct SigV4HeaderSigning {
+    credentials: Credentials,
+    region: String,
+    service: String,
+}
+
+impl SigV4HeaderSigning {
+    pub fn new(
+        credentials: Credentials,
+        region: impl Into<String>,
+        service: impl Into<String>,
+    ) -> Self {
+        Self {
+            credentials,
+            region: region.into(),
+            service: service.into(),
+        }
+    }
+}
+
+impl SignatureAlgorithm<http::Request<SdkBody>> for SigV4HeaderSigning {
+    fn sign_headers(
+        &self,
+        request: &mut http::Request<SdkBody>,
+        cfg: &ConfigBag,
+    ) -> Result<(), BoxError> {
+        let mut params = SigningParams::builder()
+            .access_key(self.credentials.access_key_id())
+            .secret_key(self.credentials.secret_access_key())
+            .region(&self.region)
+            .service_name(&self.service)
+            .time(SystemTime::now())
+            .settings(SigningSettings::default());
+        params.set_security_token(self.credentials.session_token());
+        let params = params.build()?;
+
+        // Reuse the body hash from `RequestBodyHashInterceptor` rather than hashing the body again
+        let body = match (cfg.get::<RequestBodyHash>(), request.body().bytes()) {
+            (Some(hash), _) => SignableBody::Precomputed(hash.as_str().to_owned()),
+            (None, Some(bytes)) => SignableBody::Bytes(bytes),
+            (None, None) => SignableBody::UnsignedPayload,
+        };
+        let signable =
+            SignableRequest::new(request.method(), request.uri(), request.headers(), body);
+        let (instructions, _signature) = sign(signable, &params)?.into_parts();
+        // Adds the `Authorization` and `x-amz-date` headers
+        instructions.apply_to_request(request);
+        Ok(())
+    }
+}
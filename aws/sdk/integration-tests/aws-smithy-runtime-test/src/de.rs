@@ -7,6 +7,7 @@ use aws_sdk_s3::operation::get_object::GetObjectOutput;
 use aws_smithy_http::body::SdkBody;
 use aws_smithy_runtime::{BoxError, ResponseDeserializer};
 use aws_smithy_runtime_api::config_bag::ConfigBag;
+use aws_smithy_runtime_api::interceptors::ErrorDetails;
 use aws_smithy_runtime_api::runtime_plugin::RuntimePlugin;
 
 #[derive(Debug)]
@@ -372,4 +373,35 @@ impl ResponseDeserializer<http::Response<SdkBody>, GetObjectOutput>
         //     output.build()
         // })
     }
+
+    fn is_streaming_response(&self, _res: &http::Response<SdkBody>) -> bool {
+        // The object is returned as a streaming `ByteStream`
+        true
+    }
+
+    fn error_details(&self, res: &http::Response<SdkBody>) -> Option<ErrorDetails> {
+        // S3 error bodies look like `<Error><Code>..</Code><Message>..</Message></Error>`
+        let body = std::str::from_utf8(res.body().bytes()?).ok()?;
+        let code = xml_element(body, "Code")?;
+        let message = xml_element(body, "Message").unwrap_or_default();
+        let details = ErrorDetails::new(code, message);
+        Some(match res.headers().get("x-amz-request-id") {
+            Some(request_id) => details.with_request_id(request_id.to_str().ok()?),
+            None => details,
+        })
+    }
+
+    fn response_payload_size(&self, res: &http::Response<SdkBody>) -> Option<u64> {
+        res.headers()
+            .get(http::header::CONTENT_LENGTH)
+            .and_then(|len| len.to_str().ok()?.parse().ok())
+            .or_else(|| res.body().bytes().map(|bytes| bytes.len() as u64))
+    }
+}
+
+/// Extract the text of the first `<name>` element in `body`
+fn xml_element<'a>(body: &'a str, name: &str) -> Option<&'a str> {
+    let start = body.find(&format!("<{name}>"))? + name.len() + 2;
+    let len = body[start..].find(&format!("</{name}>"))?;
+    Some(&body[start..start + len])
 }
@@ -63,6 +63,10 @@ impl EndpointOrchestrator<http::Request<SdkBody>> for GetObjectEndpointOrc {
         // }
     }
 
+    fn service_endpoint(&self, req: &http::Request<SdkBody>) -> Option<http::Uri> {
+        Some(req.uri().clone())
+    }
+
     fn resolve_auth_schemes(&self) -> Result<Vec<String>, BoxError> {
         todo!()
 
@@ -4,9 +4,97 @@
  */
 
 use aws_sdk_s3::operation::get_object::{GetObjectError, GetObjectOutput};
-use aws_smithy_runtime::{BoxError, RetryStrategy};
+use aws_smithy_runtime::{BoxError, RetryClassification, RetryStrategy};
 use aws_smithy_runtime_api::config_bag::ConfigBag;
 use aws_smithy_runtime_api::runtime_plugin::RuntimePlugin;
+use aws_smithy_types::retry::{ErrorKind, ProvideErrorKind, RetryKind};
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+/// The number of tokens a [`StandardRetryTokenBucket`] starts with
+const DEFAULT_RETRY_TOKENS: u32 = 500;
+/// Error codes returned by S3 that indicate a request is safe to retry, and the kind of error
+/// each one represents
+///
+/// `NoSuchKey`, `AccessDenied`, and other client errors are deliberately absent.
+const RETRYABLE_S3_ERRORS: &[(&str, ErrorKind)] = &[
+    ("SlowDown", ErrorKind::ThrottlingError),
+    ("InternalError", ErrorKind::ServerError),
+    ("RequestTimeout", ErrorKind::TransientError),
+];
+/// How long to wait before the first retry; the delay doubles with every further retry
+const RETRY_DELAY: Duration = Duration::from_millis(100);
+/// The longest time to wait before a retry
+const MAX_BACKOFF: Duration = Duration::from_secs(20);
+
+fn classify(err: &GetObjectError) -> RetryKind {
+    // The raw response isn't available here, so instead of calling
+    // `AwsResponseRetryClassifier` directly, this mirrors its modeled-error and error-code
+    // checks using S3's own error codes.
+    if let Some(kind) = err.retryable_error_kind() {
+        return RetryKind::Error(kind);
+    }
+    ProvideErrorKind::code(err)
+        .and_then(|code| {
+            RETRYABLE_S3_ERRORS
+                .iter()
+                .find(|(retryable, _)| *retryable == code)
+        })
+        .map_or(RetryKind::UnretryableFailure, |(_, kind)| {
+            RetryKind::Error(*kind)
+        })
+}
+
+/// A bucket of tokens that must be acquired before a request may be sent.
+pub trait RetryTokenBucket: Send + Sync + Debug {
+    /// Try to take a token from the bucket, returning `false` if the bucket is empty.
+    fn try_acquire(&self) -> bool;
+
+    /// Return a token to the bucket.
+    fn release(&self);
+}
+
+/// A [`RetryTokenBucket`] that holds up to a fixed number of tokens.
+#[derive(Debug)]
+pub struct StandardRetryTokenBucket {
+    tokens: AtomicU32,
+    max_tokens: u32,
+}
+
+impl StandardRetryTokenBucket {
+    pub fn new(max_tokens: u32) -> Self {
+        Self {
+            tokens: AtomicU32::new(max_tokens),
+            max_tokens,
+        }
+    }
+}
+
+impl Default for StandardRetryTokenBucket {
+    fn default() -> Self {
+        Self::new(DEFAULT_RETRY_TOKENS)
+    }
+}
+
+impl RetryTokenBucket for StandardRetryTokenBucket {
+    fn try_acquire(&self) -> bool {
+        self.tokens
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |tokens| {
+                tokens.checked_sub(1)
+            })
+            .is_ok()
+    }
+
+    fn release(&self) {
+        // A full bucket is left as-is
+        let _ = self
+            .tokens
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |tokens| {
+                (tokens < self.max_tokens).then(|| tokens + 1)
+            });
+    }
+}
 
 #[derive(Debug)]
 pub struct GetObjectRetryStrategy {}
@@ -18,24 +106,356 @@ impl GetObjectRetryStrategy {
 }
 
 impl RuntimePlugin for GetObjectRetryStrategy {
-    fn configure(&self, _cfg: &mut ConfigBag) -> Result<(), BoxError> {
-        todo!()
+    fn configure(&self, cfg: &mut ConfigBag) -> Result<(), BoxError> {
+        let token_bucket: Box<dyn RetryTokenBucket> = Box::new(StandardRetryTokenBucket::default());
+        cfg.put(token_bucket);
+        Ok(())
     }
 }
 
 impl RetryStrategy<Result<GetObjectOutput, GetObjectError>> for GetObjectRetryStrategy {
-    fn should_retry(
+    fn should_attempt_initial_request(&self, cfg: &ConfigBag) -> Result<(), BoxError> {
+        match cfg.get::<Box<dyn RetryTokenBucket>>() {
+            // Without a token bucket, the initial request is always allowed
+            None => Ok(()),
+            Some(token_bucket) if token_bucket.try_acquire() => Ok(()),
+            Some(_) => Err("the retry token bucket is empty".into()),
+        }
+    }
+
+    fn classify_response(
         &self,
-        _res: &Result<GetObjectOutput, GetObjectError>,
-        _cfg: &ConfigBag,
+        res: &Result<GetObjectOutput, GetObjectError>,
+    ) -> RetryClassification {
+        // S3 errors don't carry a retry hint, so there's never a retry delay
+        match res {
+            Ok(_) => RetryClassification::new(RetryKind::Unnecessary),
+            Err(err) => RetryClassification::new(classify(err)),
+        }
+    }
+
+    fn should_attempt_retry(
+        &self,
+        res: &Result<GetObjectOutput, GetObjectError>,
+        cfg: &ConfigBag,
     ) -> Result<bool, BoxError> {
-        todo!()
+        if !self.classify_response(res).is_retryable() {
+            return Ok(false);
+        }
+
+        Ok(cfg
+            .get::<Box<dyn RetryTokenBucket>>()
+            .map(|token_bucket| token_bucket.try_acquire())
+            .unwrap_or(true))
+    }
+
+    fn backoff_duration(&self, attempt: u32, _cfg: &ConfigBag) -> Duration {
+        // S3 errors don't carry a retry hint, so back off exponentially
+        let factor = 2_u32.saturating_pow(attempt.saturating_sub(1));
+        RETRY_DELAY
+            .checked_mul(factor)
+            .map_or(MAX_BACKOFF, |delay| delay.min(MAX_BACKOFF))
+    }
+
+    fn on_attempt_success(&self, _attempt_number: u32, cfg: &ConfigBag) {
+        // Return the token taken for the initial request, so that an execution that succeeds
+        // without retrying leaves the bucket as it found it
+        if let Some(token_bucket) = cfg.get::<Box<dyn RetryTokenBucket>>() {
+            token_bucket.release();
+        }
     }
 }
 
-//     retry_classifier: Arc::new(
-//         |res: Result<&SdkSuccess<GetObjectOutput>, &SdkError<GetObjectError>>| -> RetryKind {
-//             let classifier = AwsResponseRetryClassifier::new();
-//             classifier.classify_retry(res)
-//         },
-//     ),
+#[cfg(test)]
+mod tests {
+    use super::{
+        GetObjectRetryStrategy, RetryTokenBucket, DEFAULT_RETRY_TOKENS, MAX_BACKOFF, RETRY_DELAY,
+    };
+    use aws_sdk_s3::operation::get_object::{GetObjectError, GetObjectInput, GetObjectOutput};
+    use aws_smithy_async::rt::sleep::{AsyncSleep, Sleep};
+    use aws_smithy_http::body::SdkBody;
+    use aws_smithy_runtime::RetryStrategy;
+    use aws_smithy_runtime_api::config_bag::ConfigBag;
+    use aws_smithy_runtime_api::interceptors::{
+        Interceptor, InterceptorContext, InterceptorError, Interceptors,
+    };
+    use aws_smithy_runtime_api::runtime_plugin::RuntimePlugin;
+    use aws_smithy_types::error::ErrorMetadata;
+    use aws_smithy_types::retry::{ErrorKind, RetryKind};
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    type Ctx = InterceptorContext<
+        GetObjectInput,
+        http::Request<SdkBody>,
+        http::Response<SdkBody>,
+        Result<GetObjectOutput, GetObjectError>,
+    >;
+
+    /// A transport that returns canned responses in order
+    struct MockTransport(VecDeque<http::Response<SdkBody>>);
+
+    impl MockTransport {
+        fn call(&mut self, _req: &http::Request<SdkBody>) -> http::Response<SdkBody> {
+            self.0.pop_front().expect("no more canned responses")
+        }
+    }
+
+    fn error_response(status: u16, code: &str) -> http::Response<SdkBody> {
+        http::Response::builder()
+            .status(status)
+            .body(SdkBody::from(format!(
+                "<Error><Code>{code}</Code><Message>Please reduce your request rate.</Message></Error>"
+            )))
+            .unwrap()
+    }
+
+    /// A minimal stand-in for the generated deserializer, which isn't wired up yet
+    fn deserialize(res: &http::Response<SdkBody>) -> Result<GetObjectOutput, GetObjectError> {
+        if res.status().is_success() {
+            let e_tag = res.headers()["etag"].to_str().unwrap();
+            return Ok(GetObjectOutput::builder().e_tag(e_tag).build());
+        }
+        let body = std::str::from_utf8(res.body().bytes().unwrap()).unwrap();
+        let code = body
+            .split("<Code>")
+            .nth(1)
+            .and_then(|rest| rest.split("</Code>").next())
+            .unwrap();
+        Err(GetObjectError::generic(
+            ErrorMetadata::builder().code(code).build(),
+        ))
+    }
+
+    /// Records the requested delays instead of sleeping
+    #[derive(Clone, Debug, Default)]
+    struct RecordingSleep(Arc<Mutex<Vec<Duration>>>);
+
+    impl AsyncSleep for RecordingSleep {
+        fn sleep(&self, duration: Duration) -> Sleep {
+            self.0.lock().unwrap().push(duration);
+            Sleep::new(async {})
+        }
+    }
+
+    /// Records the status code of every attempt
+    #[derive(Clone, Default)]
+    struct AttemptRecorder(Arc<Mutex<Vec<(u32, u16)>>>);
+
+    impl
+        Interceptor<
+            GetObjectInput,
+            http::Request<SdkBody>,
+            http::Response<SdkBody>,
+            Result<GetObjectOutput, GetObjectError>,
+        > for AttemptRecorder
+    {
+        fn read_after_attempt(
+            &mut self,
+            context: &Ctx,
+            _cfg: &mut ConfigBag,
+        ) -> Result<(), InterceptorError> {
+            let status = context.tx_response()?.status().as_u16();
+            self.0
+                .lock()
+                .unwrap()
+                .push((context.attempt_number(), status));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn get_object_retries_until_success() {
+        let mut transport = MockTransport(VecDeque::from(vec![
+            error_response(503, "SlowDown"),
+            error_response(429, "SlowDown"),
+            http::Response::builder()
+                .status(200)
+                .header("etag", "\"3858f62230ac3c915f300c664312c11f\"")
+                .body(SdkBody::from("hello"))
+                .unwrap(),
+        ]));
+        let strategy = GetObjectRetryStrategy::new();
+        let sleep = RecordingSleep::default();
+        let attempts = AttemptRecorder::default();
+
+        let mut cfg = ConfigBag::base();
+        strategy.configure(&mut cfg).unwrap();
+        let sleep_impl: Box<dyn AsyncSleep> = Box::new(sleep.clone());
+        cfg.put(sleep_impl);
+        let mut interceptors = Interceptors::new();
+        interceptors.with_client_interceptor(attempts.clone());
+
+        let input = GetObjectInput::builder()
+            .bucket("test-bucket")
+            .key("test-key")
+            .build()
+            .unwrap();
+
+        // Drive every attempt through the same context and interceptors, the same way `invoke`
+        // does. The transport request is kept across attempts.
+        let mut ctx: Ctx = InterceptorContext::new(input).with_operation_name("GetObject");
+        ctx.set_tx_request(http::Request::new(SdkBody::empty()));
+        strategy.should_attempt_initial_request(&cfg).unwrap();
+        loop {
+            interceptors
+                .orchestrate_attempt(
+                    &mut ctx,
+                    &mut cfg,
+                    |req, _cfg| Ok(transport.call(req)),
+                    |res, _cfg| Ok(deserialize(res)),
+                )
+                .unwrap();
+
+            let attempt = ctx.attempt_number();
+            let result = ctx.modeled_response().unwrap();
+            if strategy.should_attempt_retry(result, &cfg).unwrap() {
+                let delay = strategy
+                    .retry_after_hint(result)
+                    .unwrap_or_else(|| strategy.backoff_duration(attempt, &cfg));
+                cfg.get::<Box<dyn AsyncSleep>>().unwrap().sleep(delay).await;
+                continue;
+            }
+            if result.is_ok() {
+                strategy.on_attempt_success(attempt, &cfg);
+            }
+            break;
+        }
+        let output = ctx.into_modeled_response().unwrap();
+
+        let output = output.expect("the third attempt succeeds");
+        assert_eq!(output.e_tag(), Some("\"3858f62230ac3c915f300c664312c11f\""));
+        assert_eq!(
+            *attempts.0.lock().unwrap(),
+            vec![(1, 503), (2, 429), (3, 200)]
+        );
+        assert_eq!(*sleep.0.lock().unwrap(), vec![RETRY_DELAY, RETRY_DELAY * 2]);
+
+        // One token for the initial request and one for each retry, less the one released by
+        // the successful attempt
+        assert_eq!(remaining_tokens(&cfg), DEFAULT_RETRY_TOKENS - 2);
+    }
+
+    /// Drain the token bucket, returning how many tokens it held
+    fn remaining_tokens(cfg: &ConfigBag) -> u32 {
+        let token_bucket = cfg.get::<Box<dyn RetryTokenBucket>>().unwrap();
+        let mut remaining = 0;
+        while token_bucket.try_acquire() {
+            remaining += 1;
+        }
+        remaining
+    }
+
+    #[test]
+    fn token_bucket_does_not_leak_tokens() {
+        let strategy = GetObjectRetryStrategy::new();
+        let throttled = || {
+            Err(GetObjectError::generic(
+                ErrorMetadata::builder().code("SlowDown").build(),
+            ))
+        };
+
+        // Successful executions never drain the bucket
+        let mut cfg = ConfigBag::base();
+        strategy.configure(&mut cfg).unwrap();
+        for _ in 0..DEFAULT_RETRY_TOKENS * 2 {
+            strategy.should_attempt_initial_request(&cfg).unwrap();
+            strategy.on_attempt_success(1, &cfg);
+        }
+        assert_eq!(remaining_tokens(&cfg), DEFAULT_RETRY_TOKENS);
+
+        // An execution that fails keeps every token it took, and one that succeeds after
+        // retrying keeps the tokens taken by its retries
+        let mut cfg = ConfigBag::base();
+        strategy.configure(&mut cfg).unwrap();
+        strategy.should_attempt_initial_request(&cfg).unwrap();
+        for _ in 0..2 {
+            assert!(strategy.should_attempt_retry(&throttled(), &cfg).unwrap());
+        }
+        strategy.should_attempt_initial_request(&cfg).unwrap();
+        assert!(strategy.should_attempt_retry(&throttled(), &cfg).unwrap());
+        strategy.on_attempt_success(2, &cfg);
+        assert_eq!(remaining_tokens(&cfg), DEFAULT_RETRY_TOKENS - 4);
+    }
+
+    #[test]
+    fn initial_request_fails_when_the_token_bucket_is_empty() {
+        let strategy = GetObjectRetryStrategy::new();
+
+        // Without a token bucket, the initial request is always allowed
+        let mut cfg = ConfigBag::base();
+        strategy.should_attempt_initial_request(&cfg).unwrap();
+
+        strategy.configure(&mut cfg).unwrap();
+        assert_eq!(remaining_tokens(&cfg), DEFAULT_RETRY_TOKENS);
+        assert!(strategy.should_attempt_initial_request(&cfg).is_err());
+    }
+
+    #[test]
+    fn non_retryable_errors_are_not_retried() {
+        let strategy = GetObjectRetryStrategy::new();
+        let cfg = ConfigBag::base();
+        let err = GetObjectError::generic(ErrorMetadata::builder().code("AccessDenied").build());
+        let result = Err(err);
+        assert!(!strategy.should_attempt_retry(&result, &cfg).unwrap());
+        assert_eq!(strategy.retry_after_hint(&result), None);
+    }
+
+    #[test]
+    fn classify_response() {
+        let strategy = GetObjectRetryStrategy::new();
+        let classify =
+            |result: Result<GetObjectOutput, GetObjectError>| strategy.classify_response(&result);
+        let error = |code: &str| {
+            Err(GetObjectError::generic(
+                ErrorMetadata::builder().code(code).build(),
+            ))
+        };
+
+        let success = classify(Ok(GetObjectOutput::builder().build()));
+        assert_eq!(success.kind(), &RetryKind::Unnecessary);
+        assert!(!success.is_retryable());
+
+        let throttled = classify(error("SlowDown"));
+        assert_eq!(
+            throttled.kind(),
+            &RetryKind::Error(ErrorKind::ThrottlingError)
+        );
+        assert!(throttled.is_retryable());
+        assert_eq!(throttled.retry_after(), None);
+
+        assert_eq!(
+            classify(error("InternalError")).kind(),
+            &RetryKind::Error(ErrorKind::ServerError)
+        );
+        assert_eq!(
+            classify(error("RequestTimeout")).kind(),
+            &RetryKind::Error(ErrorKind::TransientError)
+        );
+
+        let fatal = classify(error("NoSuchKey"));
+        assert_eq!(fatal.kind(), &RetryKind::UnretryableFailure);
+        assert!(!fatal.is_retryable());
+    }
+
+    #[test]
+    fn backoff_is_exponential_and_capped() {
+        let strategy = GetObjectRetryStrategy::new();
+        let cfg = ConfigBag::base();
+        let backoff: Vec<_> = (1..=4)
+            .map(|attempt| strategy.backoff_duration(attempt, &cfg))
+            .collect();
+        assert_eq!(
+            backoff,
+            vec![
+                RETRY_DELAY,
+                RETRY_DELAY * 2,
+                RETRY_DELAY * 4,
+                RETRY_DELAY * 8
+            ]
+        );
+        assert_eq!(strategy.backoff_duration(20, &cfg), MAX_BACKOFF);
+        assert_eq!(strategy.backoff_duration(u32::MAX, &cfg), MAX_BACKOFF);
+    }
+}
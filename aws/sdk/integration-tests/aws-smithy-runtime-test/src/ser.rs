@@ -141,4 +141,16 @@ impl RequestSerializer<GetObjectInput, http::Request<SdkBody>> for GetObjectInpu
         // let body = aws_smithy_http::body::SdkBody::from("");
         // Ok(request.body(body).expect("should be valid request"))
     }
+
+    fn body_size_hint(&self, req: &http::Request<SdkBody>) -> Option<u64> {
+        req.headers()
+            .get(http::header::CONTENT_LENGTH)
+            .and_then(|len| len.to_str().ok()?.parse().ok())
+            .or_else(|| req.body().content_length())
+    }
+
+    fn request_payload_size(&self, req: &http::Request<SdkBody>) -> Option<u64> {
+        // Streaming bodies can't be measured without consuming them
+        req.body().bytes().map(|bytes| bytes.len() as u64)
+    }
 }
@@ -9,9 +9,15 @@
 //! with the following properties:
 //! 1. A new layer of configuration may be applied onto an existing configuration structure without modifying it or taking ownership.
 //! 2. No lifetime shenanigans to deal with
-use aws_smithy_http::property_bag::PropertyBag;
-use std::any::type_name;
-use std::fmt::Debug;
+//!
+//! The bag stores arbitrary types and so cannot itself be serialized. Configuration that needs to
+//! cross a process boundary (e.g. a distributed tracing context) should be gathered into a
+//! dedicated type with [`config_bag::Load`], serialized by the caller, and restored on the other
+//! side with [`config_bag::Persist`].
+use crate::interceptors::InterceptorError;
+use std::any::{type_name, Any, TypeId};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Debug};
 use std::ops::Deref;
 use std::sync::Arc;
 
@@ -39,6 +45,123 @@ impl Deref for FrozenConfigBag {
     }
 }
 
+/// A read-only view of a [`ConfigBag`]
+///
+/// Created with [`ConfigBag::as_read_only`]. Code that should only inspect configuration, such as
+/// an interceptor's read hooks, can accept this type instead of `&mut ConfigBag` so that it can't
+/// accidentally modify the bag.
+#[derive(Clone, Copy)]
+pub struct ReadOnlyConfigBag<'a>(&'a ConfigBag);
+
+impl<'a> ReadOnlyConfigBag<'a> {
+    /// Retrieve the value of type `T` from the bag if exists
+    pub fn get<T: Send + Sync + Debug + 'static>(&self) -> Option<&'a T> {
+        self.0.get()
+    }
+
+    /// Returns `true` if the bag contains a value of type `T`
+    pub fn contains<T: Send + Sync + Debug + 'static>(&self) -> bool {
+        self.0.contains::<T>()
+    }
+}
+
+/// A temporary layer of a [`ConfigBag`] used by a single interceptor
+///
+/// Created by [`ConfigBag::with_interceptor_scope`]. Values read from the scope fall back to the
+/// enclosing bag, while values written to it are only merged back into the bag when the scope
+/// ends, according to its [`ScopeMergeStrategy`].
+pub struct ScopedBag<'a> {
+    outer: &'a ConfigBag,
+    writes: ConfigBag,
+    merge_strategy: ScopeMergeStrategy,
+}
+
+impl<'a> ScopedBag<'a> {
+    /// The name of the interceptor that owns this scope
+    pub fn interceptor_name(&self) -> &'static str {
+        self.writes.head.name
+    }
+
+    /// Retrieve the value of type `T` from the scope, or from the enclosing bag if the scope
+    /// hasn't written it
+    pub fn get<T: Send + Sync + Debug + 'static>(&self) -> Option<&T> {
+        match self.writes.head.props.get(&TypeId::of::<T>()) {
+            Some(Value::Set { value, .. }) => Some(
+                value
+                    .downcast_ref()
+                    .expect("values are stored under their own TypeId"),
+            ),
+            Some(Value::ExplicitlyUnset) => None,
+            None => self.outer.get(),
+        }
+    }
+
+    /// Insert `value` into the scope
+    pub fn put<T: Send + Sync + Debug + 'static>(&mut self, value: T) -> &mut Self {
+        self.writes.put(value);
+        self
+    }
+
+    /// Remove `T` from the scope, hiding any value of the enclosing bag
+    pub fn unset<T: Send + Sync + 'static>(&mut self) -> &mut Self {
+        self.writes.unset::<T>();
+        self
+    }
+
+    /// Set how the values written to this scope are merged back into the enclosing bag
+    ///
+    /// Defaults to [`ScopeMergeStrategy::Overwrite`].
+    pub fn set_merge_strategy(&mut self, merge_strategy: ScopeMergeStrategy) -> &mut Self {
+        self.merge_strategy = merge_strategy;
+        self
+    }
+}
+
+/// How the values written to a [`ScopedBag`] are merged back into the enclosing [`ConfigBag`]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ScopeMergeStrategy {
+    /// Values written to the scope replace existing values.
+    #[default]
+    Overwrite,
+    /// Existing values are kept, and only values that weren't in the bag are merged in.
+    MergeLeft,
+    /// Nothing is merged if the scope wrote a value that's already in the bag, and
+    /// [`ConfigBag::with_interceptor_scope`] returns a [`ScopeConflictError`].
+    Error,
+}
+
+/// A [`ScopedBag`] using [`ScopeMergeStrategy::Error`] wrote a value that was already in the
+/// enclosing bag
+#[derive(Debug)]
+pub struct ScopeConflictError {
+    interceptor_name: &'static str,
+    type_name: &'static str,
+}
+
+impl ScopeConflictError {
+    /// The name of the interceptor whose scope caused the conflict
+    pub fn interceptor_name(&self) -> &'static str {
+        self.interceptor_name
+    }
+
+    /// The name of the conflicting value's type
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+impl fmt::Display for ScopeConflictError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the scope of interceptor `{}` wrote a `{}` that was already in the config bag",
+            self.interceptor_name, self.type_name
+        )
+    }
+}
+
+impl std::error::Error for ScopeConflictError {}
+
 pub trait Persist {
     fn layer_name(&self) -> &'static str;
     fn persist(&self, layer: &mut ConfigBag);
@@ -50,18 +173,147 @@ pub trait Load: Sized {
 
 pub trait ConfigLayer: Persist + Load {}
 
-enum Value<T> {
-    Set(T),
+#[derive(Clone)]
+enum Value {
+    Set {
+        /// The name of the value's type, kept for diagnostics since it can't be recovered from
+        /// its `TypeId`
+        type_name: &'static str,
+        value: Arc<dyn Any + Send + Sync>,
+        /// Formats the value with its `Debug` implementation, for `ConfigBag::debug_dump`
+        debug: fn(&(dyn Any + Send + Sync)) -> String,
+    },
     ExplicitlyUnset,
 }
 
+fn debug_value<T: Debug + 'static>(value: &(dyn Any + Send + Sync)) -> String {
+    format!(
+        "{:?}",
+        value
+            .downcast_ref::<T>()
+            .expect("values are stored under their own TypeId")
+    )
+}
+
+/// Returned by [`ConfigBag::debug_dump`] in place of the bag's values when debug assertions are
+/// disabled
+const REDACTED_DEBUG_DUMP: &str =
+    "<config bag values are only dumped with debug assertions enabled>\n";
+
 struct Layer {
     name: &'static str,
-    props: PropertyBag,
+    props: HashMap<TypeId, Value>,
+    /// Multi-valued entries added with [`ConfigBag::push`], in the order they were pushed
+    items: HashMap<TypeId, Vec<Item>>,
+}
+
+type Item = Arc<dyn Any + Send + Sync>;
+
+/// Which value wins when [merging](ConfigBag::merge_with_priority) two bags that both contain a
+/// value of the same type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MergePriority {
+    /// Values already in the bag being merged into are kept, including [pushed](ConfigBag::push)
+    /// values: values of a type are only merged in if the bag has none of that type.
+    Self_,
+    /// Values from the bag being merged in overwrite existing values. Pushed values are appended
+    /// to the existing ones, unless the bag being merged in replaced them with a single value.
+    Other,
+}
+
+/// A typed value to be inserted into a bag with [`ConfigBag::with_defaults`]
+///
+/// Usually created with the [`config_bag!`](crate::config_bag!) macro.
+pub struct ConfigBagEntry {
+    id: TypeId,
+    value: Value,
+}
+
+impl ConfigBagEntry {
+    /// Create an entry that stores `value` under its type `T`
+    pub fn new<T: Send + Sync + Debug + 'static>(value: T) -> Self {
+        Self {
+            id: TypeId::of::<T>(),
+            value: Value::Set {
+                type_name: type_name::<T>(),
+                value: Arc::new(value),
+                debug: debug_value::<T>,
+            },
+        }
+    }
+
+    /// The name of the entry's type
+    pub fn type_name(&self) -> &'static str {
+        match &self.value {
+            Value::Set { type_name, .. } => type_name,
+            Value::ExplicitlyUnset => unreachable!("entries are always set"),
+        }
+    }
+}
+
+impl Debug for ConfigBagEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Value::Set {
+                type_name,
+                value,
+                debug,
+            } => f
+                .debug_struct("ConfigBagEntry")
+                .field("type_name", type_name)
+                .field("value", &format_args!("{}", debug(value.as_ref())))
+                .finish(),
+            Value::ExplicitlyUnset => unreachable!("entries are always set"),
+        }
+    }
+}
+
+/// Construct a [`ConfigBag`] from a list of `Type: value` pairs
+///
+/// Each value is stored under the type it's paired with, so literals and other expressions are
+/// inferred as that type. The values are put into the base layer with
+/// [`ConfigBag::with_defaults`].
+///
+/// ```
+/// use aws_smithy_runtime_api::config_bag;
+/// use std::time::Duration;
+///
+/// let bag = config_bag! {
+///     u32: 3,
+///     Duration: Duration::from_secs(5),
+///     &'static str: "us-east-1",
+/// };
+/// assert_eq!(bag.get::<u32>(), Some(&3));
+/// assert_eq!(bag.get::<Duration>(), Some(&Duration::from_secs(5)));
+/// assert_eq!(bag.get::<&'static str>(), Some(&"us-east-1"));
+/// ```
+#[macro_export]
+macro_rules! config_bag {
+    ($($key:ty: $value:expr),* $(,)?) => {
+        $crate::config_bag::ConfigBag::base().with_defaults([
+            $($crate::config_bag::ConfigBagEntry::new::<$key>($value)),*
+        ])
+    };
 }
 
 fn no_op(_: &mut ConfigBag) {}
 
+/// Restores a bag to a snapshot when dropped, for [`ConfigBag::scoped_override`]
+struct OverrideGuard<'a> {
+    bag: &'a mut ConfigBag,
+    snapshot: Option<ConfigBag>,
+}
+
+impl Drop for OverrideGuard<'_> {
+    fn drop(&mut self) {
+        // The whole bag is replaced since the override may have been frozen into a lower layer,
+        // e.g. by `fork` or `add_layer`
+        if let Some(snapshot) = self.snapshot.take() {
+            *self.bag = snapshot;
+        }
+    }
+}
+
 impl FrozenConfigBag {
     /// Attempts to convert this bag directly into a [`ConfigBag`] if no other references exist
     ///
@@ -96,7 +348,8 @@ impl FrozenConfigBag {
     pub fn with_fn(&self, name: &'static str, next: impl Fn(&mut ConfigBag)) -> ConfigBag {
         let new_layer = Layer {
             name,
-            props: PropertyBag::new(),
+            props: HashMap::new(),
+            items: HashMap::new(),
         };
         let mut bag = ConfigBag {
             head: new_layer,
@@ -113,6 +366,7 @@ impl ConfigBag {
             head: Layer {
                 name: "base",
                 props: Default::default(),
+                items: Default::default(),
             },
             tail: None,
         }
@@ -126,15 +380,253 @@ impl ConfigBag {
         out
     }
 
+    /// Retrieve the value of type `T` from the bag, or fail with an
+    /// [internal](InterceptorError::internal) error described by `msg` if it's missing
+    ///
+    /// This is a shorthand for required configuration, e.g.
+    /// `cfg.get_or_error::<Box<dyn AsyncSleep>>("missing sleep implementation")?`.
+    pub fn get_or_error<T: Send + Sync + Debug + 'static>(
+        &self,
+        msg: &str,
+    ) -> Result<&T, InterceptorError> {
+        self.get_or_error_with(|| InterceptorError::internal(msg.to_owned()))
+    }
+
+    /// Retrieve the value of type `T` from the bag, or fail with the error returned by `f` if it's
+    /// missing
+    ///
+    /// Unlike [`Self::get_or_error`], the error is only constructed when the value is missing.
+    pub fn get_or_error_with<T: Send + Sync + Debug + 'static>(
+        &self,
+        f: impl FnOnce() -> InterceptorError,
+    ) -> Result<&T, InterceptorError> {
+        self.get().ok_or_else(f)
+    }
+
+    /// Apply `f` to the value of type `T` in the bag, if it exists, and return the result
+    ///
+    /// Unlike [`Self::get`], the bag isn't borrowed once this returns, so the result can be used
+    /// while modifying the bag:
+    ///
+    /// ```
+    /// use aws_smithy_runtime_api::config_bag::ConfigBag;
+    /// let mut bag = ConfigBag::base();
+    /// bag.put(1_u32);
+    /// if let Some(next) = bag.observe(|count: &u32| count + 1) {
+    ///     bag.put(next);
+    /// }
+    /// assert_eq!(bag.get::<u32>(), Some(&2));
+    /// ```
+    pub fn observe<T: Send + Sync + Debug + 'static, R>(
+        &self,
+        f: impl FnOnce(&T) -> R,
+    ) -> Option<R> {
+        self.get().map(f)
+    }
+
+    /// Retrieve a shared reference to the value of type `T` from the bag if exists
+    ///
+    /// Unlike [`Self::get`], the returned value doesn't borrow the bag, so the bag can be modified
+    /// while the value is in use.
+    pub fn get_shared<T: Send + Sync + Debug + 'static>(&self) -> Option<Arc<T>> {
+        match self.lookup(TypeId::of::<T>())? {
+            Value::Set { value, .. } => Some(
+                value
+                    .clone()
+                    .downcast()
+                    .expect("values are stored under their own TypeId"),
+            ),
+            Value::ExplicitlyUnset => None,
+        }
+    }
+
+    /// Returns `true` if the bag contains a value of type `T`
+    ///
+    /// This is cheaper than checking the result of [`Self::get`] since the value isn't retrieved.
+    pub fn contains<T: 'static>(&self) -> bool {
+        self.contains_key(TypeId::of::<T>())
+    }
+
+    /// Returns `true` if the bag contains a value with the given `TypeId`
+    ///
+    /// Values that have been [unset](Self::unset) are not contained in the bag.
+    pub fn contains_key(&self, id: TypeId) -> bool {
+        matches!(self.lookup(id), Some(Value::Set { .. }))
+    }
+
+    /// Borrow this bag as a [`ReadOnlyConfigBag`]
+    pub fn as_read_only(&self) -> ReadOnlyConfigBag<'_> {
+        ReadOnlyConfigBag(self)
+    }
+
     /// Insert `value` into the bag
+    ///
+    /// This replaces all earlier values of type `T`, including values added with [`Self::push`],
+    /// so [`Self::get_all`] returns just `value` until more values are pushed.
     pub fn put<T: Send + Sync + Debug + 'static>(&mut self, value: T) -> &mut Self {
-        self.head.props.insert(Value::Set(value));
+        self.head.items.remove(&TypeId::of::<T>());
+        self.head.props.insert(
+            TypeId::of::<T>(),
+            Value::Set {
+                type_name: type_name::<T>(),
+                value: Arc::new(value),
+                debug: debug_value::<T>,
+            },
+        );
         self
     }
 
+    /// Append `value` to the values of type `T` in the bag
+    ///
+    /// Unlike [`Self::put`], this doesn't replace earlier values, which is useful for
+    /// configuration that's naturally multi-valued, e.g. a list of auth schemes to try in order.
+    /// Pushed values are read with [`Self::get_all`] or [`Self::typed_iter`]; [`Self::get`] only
+    /// returns the value stored with `put`.
+    ///
+    /// ```
+    /// use aws_smithy_runtime_api::config_bag::ConfigBag;
+    /// let mut bag = ConfigBag::base();
+    /// bag.push("sigv4");
+    /// let mut bag = bag.add_layer("operation");
+    /// bag.push("anonymous");
+    /// let schemes: Vec<_> = bag.typed_iter::<&'static str>().collect();
+    /// assert_eq!(schemes, vec![&"sigv4", &"anonymous"]);
+    /// ```
+    pub fn push<T: Send + Sync + Debug + 'static>(&mut self, value: T) -> &mut Self {
+        self.head
+            .items
+            .entry(TypeId::of::<T>())
+            .or_default()
+            .push(Arc::new(value));
+        self
+    }
+
+    /// Insert every entry of `defaults` into the top layer of the bag
+    ///
+    /// This is equivalent to calling [`Self::put`] for each entry, which is convenient for plugins
+    /// that register many default values at once. See also [`config_bag!`](crate::config_bag!).
+    ///
+    /// ```
+    /// use aws_smithy_runtime_api::config_bag::{ConfigBag, ConfigBagEntry};
+    /// let bag = ConfigBag::base()
+    ///     .with_defaults([ConfigBagEntry::new(3_u32), ConfigBagEntry::new("us-east-1")]);
+    /// assert_eq!(bag.get::<u32>(), Some(&3));
+    /// assert_eq!(bag.get::<&'static str>(), Some(&"us-east-1"));
+    /// ```
+    pub fn with_defaults(mut self, defaults: impl IntoIterator<Item = ConfigBagEntry>) -> Self {
+        for entry in defaults {
+            self.head.items.remove(&entry.id);
+            self.head.props.insert(entry.id, entry.value);
+        }
+        self
+    }
+
+    /// Retrieve all values of type `T` in the bag, in the order they were inserted
+    ///
+    /// Starting with the bottom layer, this returns the value stored with the latest
+    /// [`put`](Self::put), if any, followed by the values [pushed](Self::push) after it. Insertion
+    /// order is preserved so that priorities such as the order of auth schemes are respected.
+    ///
+    /// ```
+    /// use aws_smithy_runtime_api::config_bag::ConfigBag;
+    /// let mut bag = ConfigBag::base();
+    /// bag.push("sigv4").push("sigv4a");
+    /// assert_eq!(bag.get_all::<&'static str>(), vec![&"sigv4", &"sigv4a"]);
+    /// bag.put("anonymous");
+    /// assert_eq!(bag.get_all::<&'static str>(), vec![&"anonymous"]);
+    /// ```
+    pub fn get_all<T: Send + Sync + Debug + 'static>(&self) -> Vec<&T> {
+        self.typed_iter().collect()
+    }
+
+    /// Iterate over all values of type `T` in the bag, in the order they were inserted
+    ///
+    /// See [`Self::get_all`] for the order of the values.
+    pub fn typed_iter<T: Send + Sync + Debug + 'static>(&self) -> impl Iterator<Item = &T> {
+        let mut items = Vec::new();
+        self.collect_items(TypeId::of::<T>(), &mut items);
+        items.into_iter().map(|item| {
+            item.downcast_ref::<T>()
+                .expect("values are stored under their own TypeId")
+        })
+    }
+
+    /// Insert `value` into the bag while `f` runs, then restore the bag to how it was before
+    ///
+    /// Values of `T` pushed with [`Self::push`] are hidden by the override and restored along with
+    /// the previous value. Any other changes `f` makes to the bag, including layers it adds, are
+    /// discarded as well. The bag is restored even if `f` panics, so an override made by one test
+    /// can't leak into another.
+    ///
+    /// ```
+    /// use aws_smithy_runtime_api::config_bag::ConfigBag;
+    /// let mut bag = ConfigBag::base();
+    /// bag.put("us-east-1");
+    /// let region = bag.scoped_override("us-west-2", |bag| *bag.get::<&'static str>().unwrap());
+    /// assert_eq!(region, "us-west-2");
+    /// assert_eq!(bag.get::<&'static str>(), Some(&"us-east-1"));
+    /// ```
+    pub fn scoped_override<T: Send + Sync + Debug + 'static, R>(
+        &mut self,
+        value: T,
+        f: impl FnOnce(&mut ConfigBag) -> R,
+    ) -> R {
+        let snapshot = Some(self.snapshot());
+        let guard = OverrideGuard {
+            bag: self,
+            snapshot,
+        };
+        guard.bag.put(value);
+        f(guard.bag)
+    }
+
+    /// Run `f` on a copy of this bag, keeping its changes only if it returns `Ok`
+    ///
+    /// This lets several related values be updated atomically: if `f` fails or panics, the bag is
+    /// left exactly as it was. Copying the bag is cheap since values are reference-counted.
+    ///
+    /// ```
+    /// use aws_smithy_runtime_api::config_bag::ConfigBag;
+    /// let mut bag = ConfigBag::base();
+    /// bag.put(1_u8);
+    /// let result: Result<(), &str> = bag.transaction(|bag| {
+    ///     bag.put(2_u8).put("partially updated");
+    ///     Err("failed halfway through")
+    /// });
+    /// assert!(result.is_err());
+    /// assert_eq!(bag.get::<u8>(), Some(&1));
+    /// assert_eq!(bag.get::<&'static str>(), None);
+    /// ```
+    pub fn transaction<R, E>(
+        &mut self,
+        f: impl FnOnce(&mut ConfigBag) -> Result<R, E>,
+    ) -> Result<R, E> {
+        let mut overlay = self.snapshot();
+        let result = f(&mut overlay)?;
+        *self = overlay;
+        Ok(result)
+    }
+
+    /// A copy of this bag that can be modified without affecting it
+    fn snapshot(&self) -> ConfigBag {
+        ConfigBag {
+            head: Layer {
+                name: self.head.name,
+                props: self.head.props.clone(),
+                items: self.head.items.clone(),
+            },
+            tail: self.tail.clone(),
+        }
+    }
+
     /// Remove `T` from this bag
+    ///
+    /// This also removes all values of type `T` added with [`Self::push`].
     pub fn unset<T: Send + Sync + 'static>(&mut self) -> &mut Self {
-        self.head.props.insert(Value::<T>::ExplicitlyUnset);
+        self.head.items.remove(&TypeId::of::<T>());
+        self.head
+            .props
+            .insert(TypeId::of::<T>(), Value::ExplicitlyUnset);
         self
     }
 
@@ -184,20 +676,358 @@ impl ConfigBag {
             .tail
             .as_ref()
             .and_then(|bag| bag.sourced_get(source_trail));
-        let (item, source) = match bag.props.get::<Value<T>>() {
+        let (item, source) = match bag.props.get(&TypeId::of::<T>()) {
             Some(Value::ExplicitlyUnset) => (None, SourceInfo::Unset { layer: bag.name }),
-            Some(Value::Set(v)) => (
-                Some(v),
-                SourceInfo::Set {
-                    layer: bag.name,
-                    value: format!("{:?}", v),
-                },
-            ),
+            Some(Value::Set { value: v, .. }) => {
+                let v = v
+                    .downcast_ref::<T>()
+                    .expect("values are stored under their own TypeId");
+                (
+                    Some(v),
+                    SourceInfo::Set {
+                        layer: bag.name,
+                        value: format!("{:?}", v),
+                    },
+                )
+            }
             None => (inner_item, SourceInfo::Inherit { layer: bag.name }),
         };
         source_trail.push(source);
         item
     }
+
+    /// Merge all values from `other` into the top layer of this bag
+    ///
+    /// Values in `other` replace values of the same type in this bag. This is equivalent to
+    /// calling [`Self::merge_with_priority`] with [`MergePriority::Other`].
+    pub fn merge(&mut self, other: ConfigBag) {
+        self.merge_with_priority(other, MergePriority::Other)
+    }
+
+    /// Merge all values from `other` into the top layer of this bag
+    ///
+    /// `priority` decides which value is kept when both bags contain a value of the same type.
+    /// Values explicitly [unset](Self::unset) in `other` are treated like any other value.
+    /// [Pushed](Self::push) values follow the same priority; see [`MergePriority`].
+    pub fn merge_with_priority(&mut self, other: ConfigBag, priority: MergePriority) {
+        self.merge_values(other.flatten(), other.flatten_items(), priority);
+    }
+
+    /// Split this bag into two bags that start with the same values but can diverge, e.g. for
+    /// the parts of a multipart upload that are sent in parallel
+    ///
+    /// Both this bag and the returned fork get a new, empty top layer on top of the values they
+    /// share, so neither sees the other's changes. The returned [`ConfigBagMergeHandle`] merges
+    /// the fork's changes back into this bag once it's done.
+    ///
+    /// ```
+    /// use aws_smithy_runtime_api::config_bag::{ConfigBag, MergePriority};
+    /// let mut bag = ConfigBag::base();
+    /// bag.put(1_u32);
+    /// let (mut fork, handle) = bag.fork();
+    /// fork.put("part 1");
+    /// assert_eq!(bag.get::<&'static str>(), None);
+    ///
+    /// handle.merge(&mut bag, fork, MergePriority::Other);
+    /// assert_eq!(bag.get::<&'static str>(), Some(&"part 1"));
+    /// assert_eq!(bag.get::<u32>(), Some(&1));
+    /// ```
+    pub fn fork(&mut self) -> (ConfigBag, ConfigBagMergeHandle) {
+        let name = self.head.name;
+        let base = std::mem::replace(self, ConfigBag::base()).freeze();
+        *self = base.add_layer(name);
+        let fork = base.add_layer("fork");
+        (fork, ConfigBagMergeHandle { base })
+    }
+
+    fn merge_values(
+        &mut self,
+        mut props: HashMap<TypeId, Value>,
+        mut items: HashMap<TypeId, Vec<Item>>,
+        priority: MergePriority,
+    ) {
+        let ids: HashSet<TypeId> = props.keys().chain(items.keys()).copied().collect();
+        for id in ids {
+            if priority == MergePriority::Self_ && self.has_values(id) {
+                continue;
+            }
+            if let Some(value) = props.remove(&id) {
+                // As with `put` and `unset`, the merged value replaces any pushed values
+                self.head.items.remove(&id);
+                self.head.props.insert(id, value);
+            }
+            if let Some(values) = items.remove(&id) {
+                self.head.items.entry(id).or_default().extend(values);
+            }
+        }
+    }
+
+    /// Returns `true` if the bag contains a value of the type with the given `id`, either a
+    /// single value or a pushed one
+    fn has_values(&self, id: TypeId) -> bool {
+        let mut items = Vec::new();
+        self.collect_items(id, &mut items);
+        !items.is_empty()
+    }
+
+    /// Run `f` with a fresh scope for the interceptor named `interceptor_name`
+    ///
+    /// Values written to the scope don't affect the bag until `f` returns, and are then merged
+    /// back according to the scope's [`ScopeMergeStrategy`]. This keeps the temporary state of
+    /// one interceptor from shadowing another's by accident.
+    ///
+    /// ```
+    /// use aws_smithy_runtime_api::config_bag::{ConfigBag, ScopeMergeStrategy};
+    /// let mut bag = ConfigBag::base();
+    /// bag.put("original");
+    /// bag.with_interceptor_scope("my-interceptor", |scope| {
+    ///     scope
+    ///         .set_merge_strategy(ScopeMergeStrategy::MergeLeft)
+    ///         .put("scoped")
+    ///         .put(1_u32);
+    ///     assert_eq!(scope.get::<&'static str>(), Some(&"scoped"));
+    /// })
+    /// .unwrap();
+    /// assert_eq!(bag.get::<&'static str>(), Some(&"original"));
+    /// assert_eq!(bag.get::<u32>(), Some(&1));
+    /// ```
+    pub fn with_interceptor_scope(
+        &mut self,
+        interceptor_name: &'static str,
+        f: impl FnOnce(&mut ScopedBag<'_>),
+    ) -> Result<(), ScopeConflictError> {
+        let mut scope = ScopedBag {
+            outer: self,
+            writes: ConfigBag {
+                head: Layer {
+                    name: interceptor_name,
+                    props: HashMap::new(),
+                    items: HashMap::new(),
+                },
+                tail: None,
+            },
+            merge_strategy: ScopeMergeStrategy::default(),
+        };
+        f(&mut scope);
+        let ScopedBag {
+            writes,
+            merge_strategy,
+            ..
+        } = scope;
+
+        let priority = match merge_strategy {
+            ScopeMergeStrategy::Overwrite => MergePriority::Other,
+            ScopeMergeStrategy::MergeLeft => MergePriority::Self_,
+            ScopeMergeStrategy::Error => {
+                if let Some(id) = writes.head.props.keys().find(|id| self.contains_key(**id)) {
+                    return Err(ScopeConflictError {
+                        interceptor_name,
+                        type_name: self.type_name_of(*id).expect("the bag contains this value"),
+                    });
+                }
+                MergePriority::Other
+            }
+        };
+        self.merge_with_priority(writes, priority);
+        Ok(())
+    }
+
+    /// The `TypeId`s of all values currently in the bag
+    ///
+    /// Values that have been [unset](Self::unset) are not included. The order is unspecified.
+    pub fn keys(&self) -> impl Iterator<Item = TypeId> {
+        self.flatten()
+            .into_iter()
+            .filter_map(|(id, value)| match value {
+                Value::Set { .. } => Some(id),
+                Value::ExplicitlyUnset => None,
+            })
+    }
+
+    /// The type names of all values currently in the bag, e.g. for printing diagnostics
+    ///
+    /// Values that have been [unset](Self::unset) are not included. The order is unspecified.
+    pub fn key_names(&self) -> impl Iterator<Item = &'static str> {
+        self.flatten()
+            .into_values()
+            .filter_map(|value| match value {
+                Value::Set { type_name, .. } => Some(type_name),
+                Value::ExplicitlyUnset => None,
+            })
+    }
+
+    /// Iterate over every value currently in the bag, along with the `TypeId` it's stored under
+    ///
+    /// Only the topmost value of each type is returned, and values that have been
+    /// [unset](Self::unset) are not included. The order is unspecified. Use
+    /// [`Self::type_name_of`] to find the name of a value's type.
+    pub fn iter(&self) -> impl Iterator<Item = (TypeId, &dyn Any)> {
+        let mut props = HashMap::new();
+        self.collect_refs(&mut props);
+        props.into_iter().filter_map(|(id, value)| match value {
+            Value::Set { value, .. } => Some((id, value.as_ref() as &dyn Any)),
+            Value::ExplicitlyUnset => None,
+        })
+    }
+
+    /// The name of the type stored under `id`, recorded when the value was inserted
+    ///
+    /// Returns `None` if the bag doesn't contain a value with the given `TypeId`.
+    pub fn type_name_of(&self, id: TypeId) -> Option<&'static str> {
+        match self.lookup(id)? {
+            Value::Set { type_name, .. } => Some(type_name),
+            Value::ExplicitlyUnset => None,
+        }
+    }
+
+    /// A human-readable listing of every value currently in the bag, for debugging configuration
+    ///
+    /// Each line contains a value's `TypeId`, its type name, and the value formatted with its
+    /// `Debug` implementation. Lines are sorted by type name. Values that have been
+    /// [unset](Self::unset) are not included.
+    ///
+    /// Since the dump includes every value in the bag, which may include credentials, values are
+    /// only listed in builds with debug assertions enabled. Other builds return a placeholder.
+    pub fn debug_dump(&self) -> String {
+        if !cfg!(debug_assertions) {
+            return REDACTED_DEBUG_DUMP.to_string();
+        }
+        let mut lines: Vec<_> = self
+            .flatten()
+            .into_iter()
+            .filter_map(|(id, value)| match value {
+                Value::Set {
+                    type_name,
+                    value,
+                    debug,
+                } => Some((type_name, id, debug(value.as_ref()))),
+                Value::ExplicitlyUnset => None,
+            })
+            .collect();
+        lines.sort_by_key(|(type_name, ..)| *type_name);
+        lines
+            .into_iter()
+            .map(|(type_name, id, value)| format!("{:?} {}: {}\n", id, type_name, value))
+            .collect()
+    }
+
+    /// Collapse all layers of this bag into a single map of the values visible from the top layer
+    fn flatten(&self) -> HashMap<TypeId, Value> {
+        let mut props = self
+            .tail
+            .as_ref()
+            .map(|tail| tail.flatten())
+            .unwrap_or_default();
+        props.extend(
+            self.head
+                .props
+                .iter()
+                .map(|(id, value)| (*id, value.clone())),
+        );
+        props
+    }
+
+    /// Like [`Self::flatten`], but borrowing the values instead of cloning them
+    fn collect_refs<'a>(&'a self, props: &mut HashMap<TypeId, &'a Value>) {
+        if let Some(tail) = &self.tail {
+            tail.collect_refs(props);
+        }
+        props.extend(self.head.props.iter().map(|(id, value)| (*id, value)));
+    }
+
+    /// Collect the values of the type with the given `id` from all layers, bottom first
+    fn collect_items<'a>(&'a self, id: TypeId, items: &mut Vec<&'a (dyn Any + Send + Sync)>) {
+        if let Some(tail) = &self.tail {
+            tail.collect_items(id, items);
+        }
+        // A single value replaces everything below it, and comes before this layer's pushed values
+        match self.head.props.get(&id) {
+            Some(Value::Set { value, .. }) => {
+                items.clear();
+                items.push(value.as_ref());
+            }
+            Some(Value::ExplicitlyUnset) => items.clear(),
+            None => {}
+        }
+        if let Some(values) = self.head.items.get(&id) {
+            items.extend(values.iter().map(|value| value.as_ref()));
+        }
+    }
+
+    /// Collapse the pushed values of all layers into a single map, bottom layer first
+    fn flatten_items(&self) -> HashMap<TypeId, Vec<Item>> {
+        let mut items = self
+            .tail
+            .as_ref()
+            .map(|tail| tail.flatten_items())
+            .unwrap_or_default();
+        // As in `collect_items`, a single value replaces the values pushed below it
+        for id in self.head.props.keys() {
+            items.remove(id);
+        }
+        for (id, values) in &self.head.items {
+            items.entry(*id).or_default().extend(values.iter().cloned());
+        }
+        items
+    }
+
+    /// Like [`Self::flatten`] and [`Self::flatten_items`], but only for the layers above `base`
+    fn flatten_above(
+        &self,
+        base: &FrozenConfigBag,
+    ) -> (HashMap<TypeId, Value>, HashMap<TypeId, Vec<Item>>) {
+        let mut layers = vec![&self.head];
+        let mut bag = self;
+        while let Some(tail) = &bag.tail {
+            if Arc::ptr_eq(&tail.0, &base.0) {
+                break;
+            }
+            layers.push(&tail.head);
+            bag = tail;
+        }
+
+        let mut props = HashMap::new();
+        let mut items: HashMap<TypeId, Vec<Item>> = HashMap::new();
+        for layer in layers.into_iter().rev() {
+            props.extend(layer.props.iter().map(|(id, value)| (*id, value.clone())));
+            for id in layer.props.keys() {
+                items.remove(id);
+            }
+            for (id, values) in &layer.items {
+                items.entry(*id).or_default().extend(values.iter().cloned());
+            }
+        }
+        (props, items)
+    }
+
+    /// Find the topmost entry for the type with the given `id`
+    fn lookup(&self, id: TypeId) -> Option<&Value> {
+        self.head
+            .props
+            .get(&id)
+            .or_else(|| self.tail.as_ref().and_then(|tail| tail.lookup(id)))
+    }
+}
+
+/// Merges a bag created with [`ConfigBag::fork`] back into the bag it was forked from
+#[must_use]
+pub struct ConfigBagMergeHandle {
+    /// The values shared by the fork and its parent when it was forked
+    base: FrozenConfigBag,
+}
+
+impl ConfigBagMergeHandle {
+    /// Merge the changes made to `fork` since it was forked into the top layer of `parent`
+    ///
+    /// Only values added after the fork are merged, so values the two bags shared aren't
+    /// duplicated, e.g. values [pushed](ConfigBag::push) before the fork. `priority` decides which
+    /// value is kept when both bags changed a value of the same type, as with
+    /// [`ConfigBag::merge_with_priority`]. `fork` should be the bag returned along with this
+    /// handle; for any other bag, all of its values are merged.
+    pub fn merge(self, parent: &mut ConfigBag, fork: ConfigBag, priority: MergePriority) {
+        let (props, items) = fork.flatten_above(&self.base);
+        parent.merge_values(props, items, priority);
+    }
 }
 
 impl From<ConfigBag> for FrozenConfigBag {
@@ -216,7 +1046,7 @@ pub enum SourceInfo {
 #[cfg(test)]
 mod test {
     use super::ConfigBag;
-    use crate::config_bag::{Load, Persist};
+    use crate::config_bag::{ConfigBagEntry, Load, MergePriority, Persist};
 
     #[test]
     fn layered_property_bag() {
@@ -326,4 +1156,474 @@ mod test {
 
         assert_eq!(MyConfig::load(&bag), Some(conf));
     }
+
+    #[test]
+    fn merge() {
+        #[derive(Debug, Eq, PartialEq)]
+        struct Region(&'static str);
+        #[derive(Debug, Eq, PartialEq)]
+        struct TraceId(u64);
+        #[derive(Debug, Eq, PartialEq)]
+        struct SpanId(u64);
+
+        let mut parent = ConfigBag::base();
+        parent.put(Region("us-east-1")).put(TraceId(1));
+
+        let mut child = ConfigBag::base().with_fn("defaults", |bag: &mut ConfigBag| {
+            bag.put(Region("us-west-2"));
+        });
+        child.put(TraceId(2)).put(SpanId(3));
+        parent.merge(child);
+
+        assert_eq!(parent.get::<Region>(), Some(&Region("us-west-2")));
+        assert_eq!(parent.get::<TraceId>(), Some(&TraceId(2)));
+        assert_eq!(parent.get::<SpanId>(), Some(&SpanId(3)));
+
+        let mut child = ConfigBag::base();
+        child.put(Region("eu-west-1")).unset::<SpanId>();
+        let mut other = ConfigBag::base();
+        other.put(TraceId(4));
+        parent.merge_with_priority(child, MergePriority::Self_);
+        parent.merge_with_priority(other, MergePriority::Self_);
+
+        assert_eq!(parent.get::<Region>(), Some(&Region("us-west-2")));
+        assert_eq!(parent.get::<TraceId>(), Some(&TraceId(2)));
+        assert_eq!(parent.get::<SpanId>(), Some(&SpanId(3)));
+    }
+
+    #[test]
+    fn merged_values_replace_pushed_values() {
+        // A value put in a higher layer of `other` hides the values pushed below it
+        let mut other = ConfigBag::base();
+        other.push("a");
+        let mut other = other.add_layer("next");
+        other.put("b");
+        let mut bag = ConfigBag::base();
+        bag.merge(other);
+        assert_eq!(bag.get_all::<&'static str>(), vec![&"b"]);
+
+        // A value merged from `other` replaces the values pushed to this bag
+        let mut bag = ConfigBag::base();
+        bag.push("a");
+        let mut other = ConfigBag::base();
+        other.put("b");
+        bag.merge(other);
+        assert_eq!(bag.get_all::<&'static str>(), vec![&"b"]);
+
+        let mut bag = ConfigBag::base();
+        bag.push("a");
+        let mut other = ConfigBag::base();
+        other.unset::<&'static str>();
+        bag.merge(other);
+        assert!(bag.get_all::<&'static str>().is_empty());
+    }
+
+    #[test]
+    fn merge_priority_applies_to_pushed_values() {
+        let merged = |existing: fn(&mut ConfigBag), merged: fn(&mut ConfigBag), priority| {
+            let mut bag = ConfigBag::base();
+            existing(&mut bag);
+            let mut other = ConfigBag::base();
+            merged(&mut other);
+            bag.merge_with_priority(other, priority);
+            bag.get_all::<&'static str>()
+                .into_iter()
+                .copied()
+                .collect::<Vec<_>>()
+        };
+        let push_a = |bag: &mut ConfigBag| {
+            bag.push("a");
+        };
+        let push_b = |bag: &mut ConfigBag| {
+            bag.push("b");
+        };
+        let put_a = |bag: &mut ConfigBag| {
+            bag.put("a");
+        };
+        let put_b = |bag: &mut ConfigBag| {
+            bag.put("b");
+        };
+        let nothing = |_: &mut ConfigBag| {};
+
+        // The other bag's pushed values are appended, and its single values replace everything
+        assert_eq!(merged(push_a, push_b, MergePriority::Other), vec!["a", "b"]);
+        assert_eq!(merged(put_a, push_b, MergePriority::Other), vec!["a", "b"]);
+        assert_eq!(merged(push_a, put_b, MergePriority::Other), vec!["b"]);
+
+        // Existing values of a type are kept, whether they were pushed or not
+        assert_eq!(merged(push_a, push_b, MergePriority::Self_), vec!["a"]);
+        assert_eq!(merged(put_a, push_b, MergePriority::Self_), vec!["a"]);
+        assert_eq!(merged(push_a, put_b, MergePriority::Self_), vec!["a"]);
+        assert_eq!(merged(nothing, push_b, MergePriority::Self_), vec!["b"]);
+    }
+
+    #[test]
+    fn keys() {
+        #[derive(Debug)]
+        struct Region;
+        #[derive(Debug)]
+        struct Retries;
+
+        let mut bag = ConfigBag::base().with_fn("a", |bag: &mut ConfigBag| {
+            bag.put(Region).put(Retries);
+        });
+        bag.unset::<Retries>().put(1_u8);
+
+        let mut names: Vec<_> = bag.key_names().collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "aws_smithy_runtime_api::config_bag::test::keys::Region",
+                "u8"
+            ]
+        );
+        assert!(bag.keys().any(|id| id == std::any::TypeId::of::<Region>()));
+        assert!(!bag.keys().any(|id| id == std::any::TypeId::of::<Retries>()));
+    }
+
+    #[test]
+    fn read_only() {
+        let mut bag = ConfigBag::base();
+        bag.put(1_u8);
+        let read_only = bag.as_read_only();
+        assert_eq!(read_only.get::<u8>(), Some(&1));
+        assert!(read_only.contains::<u8>());
+        assert!(!read_only.contains::<u16>());
+    }
+
+    #[test]
+    fn get_shared() {
+        #[derive(Debug, Eq, PartialEq)]
+        struct Region(&'static str);
+
+        let mut bag = ConfigBag::base();
+        bag.put(Region("us-east-1"));
+        let region = bag.get_shared::<Region>().unwrap();
+        // The returned value doesn't borrow the bag, so the bag can still be modified
+        bag.put(Region("us-west-2"));
+        assert_eq!(*region, Region("us-east-1"));
+        assert_eq!(bag.get::<Region>(), Some(&Region("us-west-2")));
+
+        bag.unset::<Region>();
+        assert!(bag.get_shared::<Region>().is_none());
+    }
+
+    #[test]
+    fn contains() {
+        #[derive(Debug)]
+        struct Region;
+
+        let mut bag = ConfigBag::base();
+        assert!(!bag.contains::<Region>());
+        bag.put(Region);
+        let mut bag = bag.add_layer("next");
+        assert!(bag.contains::<Region>());
+        assert!(bag.contains_key(std::any::TypeId::of::<Region>()));
+
+        bag.unset::<Region>();
+        assert!(!bag.contains::<Region>());
+        assert!(!bag.contains_key(std::any::TypeId::of::<Region>()));
+    }
+
+    #[test]
+    fn debug_dump() {
+        let mut bag = ConfigBag::base();
+        bag.put("us-east-1").put(3_u32);
+        let mut bag = bag.add_layer("next");
+        bag.put(5_u32);
+
+        let dump = bag.debug_dump();
+        if !cfg!(debug_assertions) {
+            assert_eq!(dump, super::REDACTED_DEBUG_DUMP);
+            return;
+        }
+        let lines: Vec<_> = dump.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with(" &str: \"us-east-1\""), "{}", lines[0]);
+        assert!(lines[1].ends_with(" u32: 5"), "{}", lines[1]);
+        assert!(lines[0].starts_with("TypeId"));
+    }
+
+    #[test]
+    fn iter() {
+        use std::any::TypeId;
+
+        let mut bag = ConfigBag::base();
+        bag.put(1_u8).put("unset");
+        let mut bag = bag.add_layer("next");
+        bag.put(2_u8).unset::<&'static str>();
+
+        let values: Vec<_> = bag.iter().collect();
+        assert_eq!(values.len(), 1);
+        let (id, value) = values[0];
+        assert_eq!(id, TypeId::of::<u8>());
+        assert_eq!(value.downcast_ref::<u8>(), Some(&2));
+
+        assert_eq!(bag.type_name_of(TypeId::of::<u8>()), Some("u8"));
+        assert_eq!(bag.type_name_of(TypeId::of::<&'static str>()), None);
+    }
+
+    #[test]
+    fn interceptor_scope_merge_strategies() {
+        use super::ScopeMergeStrategy;
+
+        let mut bag = ConfigBag::base();
+        bag.put("outer").put(1_u8);
+
+        bag.with_interceptor_scope("overwrite", |scope| {
+            assert_eq!(scope.interceptor_name(), "overwrite");
+            assert_eq!(scope.get::<&'static str>(), Some(&"outer"));
+            scope.put("scoped").unset::<u8>();
+            assert_eq!(scope.get::<u8>(), None);
+        })
+        .unwrap();
+        assert_eq!(bag.get::<&'static str>(), Some(&"scoped"));
+        assert_eq!(bag.get::<u8>(), None);
+
+        bag.with_interceptor_scope("merge-left", |scope| {
+            scope
+                .set_merge_strategy(ScopeMergeStrategy::MergeLeft)
+                .put("ignored")
+                .put(2_u8);
+        })
+        .unwrap();
+        assert_eq!(bag.get::<&'static str>(), Some(&"scoped"));
+        assert_eq!(bag.get::<u8>(), Some(&2));
+
+        let err = bag
+            .with_interceptor_scope("error", |scope| {
+                scope
+                    .set_merge_strategy(ScopeMergeStrategy::Error)
+                    .put(3_u8)
+                    .put(4_u16);
+            })
+            .unwrap_err();
+        assert_eq!(err.interceptor_name(), "error");
+        assert_eq!(err.type_name(), "u8");
+        assert_eq!(bag.get::<u8>(), Some(&2));
+        assert_eq!(bag.get::<u16>(), None);
+    }
+
+    #[test]
+    fn scoped_override_restores_the_previous_value() {
+        let mut bag = ConfigBag::base();
+        bag.put(1_u8);
+        let mut bag = bag.add_layer("next");
+
+        // The inherited value is visible again once the override ends
+        let value = bag.scoped_override(2_u8, |bag| *bag.get::<u8>().unwrap());
+        assert_eq!(value, 2);
+        assert_eq!(bag.get::<u8>(), Some(&1));
+
+        bag.put(3_u8);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            bag.scoped_override(4_u8, |_| panic!("the override must still be undone"))
+        }));
+        assert!(result.is_err());
+        assert_eq!(bag.get::<u8>(), Some(&3));
+    }
+
+    #[test]
+    fn scoped_override_is_undone_when_layers_are_added() {
+        let mut bag = ConfigBag::base();
+        bag.put(1_u32);
+        bag.scoped_override(5_u32, |bag| {
+            let _ = bag.fork();
+        });
+        assert_eq!(bag.get::<u32>(), Some(&1));
+
+        let mut bag = ConfigBag::base();
+        bag.scoped_override(5_u32, |bag| {
+            *bag = std::mem::replace(bag, ConfigBag::base()).add_layer("next");
+        });
+        assert_eq!(bag.get::<u32>(), None);
+    }
+
+    #[test]
+    fn scoped_override_restores_pushed_values() {
+        let mut bag = ConfigBag::base();
+        bag.push(1_u8).push(2_u8);
+
+        // The override replaces the pushed values while it's in place
+        let values: Vec<_> =
+            bag.scoped_override(3_u8, |bag| bag.typed_iter::<u8>().copied().collect());
+        assert_eq!(values, vec![3]);
+        assert_eq!(
+            bag.typed_iter::<u8>().copied().collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+    }
+
+    #[test]
+    fn get_or_error() {
+        use crate::interceptors::{ErrorKind, InterceptorError};
+        use std::error::Error;
+
+        let mut bag = ConfigBag::base();
+        bag.put(1_u8);
+        assert_eq!(bag.get_or_error::<u8>("missing u8").unwrap(), &1);
+
+        let err = bag.get_or_error::<u16>("missing u16").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Internal);
+        assert_eq!(err.source().unwrap().to_string(), "missing u16");
+
+        let err = bag
+            .get_or_error_with::<u16>(|| InterceptorError::signing("no credentials"))
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Signing);
+    }
+
+    #[test]
+    fn pushed_values_accumulate_across_layers() {
+        #[derive(Debug, PartialEq)]
+        struct AuthScheme(&'static str);
+
+        let mut bag = ConfigBag::base();
+        assert_eq!(bag.typed_iter::<AuthScheme>().count(), 0);
+        bag.push(AuthScheme("sigv4")).push(AuthScheme("sigv4a"));
+        let base = bag.freeze();
+
+        let mut bag = base.add_layer("operation");
+        bag.push(AuthScheme("anonymous"));
+        let names: Vec<_> = bag.typed_iter::<AuthScheme>().map(|s| s.0).collect();
+        assert_eq!(names, vec!["sigv4", "sigv4a", "anonymous"]);
+        assert_eq!(base.typed_iter::<AuthScheme>().count(), 2);
+
+        let mut other = ConfigBag::base();
+        other.push(AuthScheme("bearer"));
+        bag.merge(other);
+        let names: Vec<_> = bag.typed_iter::<AuthScheme>().map(|s| s.0).collect();
+        assert_eq!(names, vec!["sigv4", "sigv4a", "anonymous", "bearer"]);
+    }
+
+    #[test]
+    fn get_all_preserves_insertion_order() {
+        #[derive(Debug, PartialEq)]
+        struct AuthScheme(&'static str);
+
+        let mut bag = ConfigBag::base();
+        assert!(bag.get_all::<AuthScheme>().is_empty());
+        bag.push(AuthScheme("sigv4"))
+            .push(AuthScheme("sigv4a"))
+            .push(AuthScheme("anonymous"));
+        assert_eq!(
+            bag.get_all::<AuthScheme>(),
+            vec![
+                &AuthScheme("sigv4"),
+                &AuthScheme("sigv4a"),
+                &AuthScheme("anonymous")
+            ]
+        );
+        // Pushed values aren't visible through `get`
+        assert_eq!(bag.get::<AuthScheme>(), None);
+
+        // `put` replaces every value, including those pushed into lower layers
+        let mut bag = bag.add_layer("operation");
+        bag.put(AuthScheme("bearer"));
+        assert_eq!(bag.get_all::<AuthScheme>(), vec![&AuthScheme("bearer")]);
+        bag.push(AuthScheme("sigv4"));
+        assert_eq!(
+            bag.get_all::<AuthScheme>(),
+            vec![&AuthScheme("bearer"), &AuthScheme("sigv4")]
+        );
+
+        bag.unset::<AuthScheme>();
+        assert!(bag.get_all::<AuthScheme>().is_empty());
+    }
+
+    #[test]
+    fn with_defaults() {
+        #[derive(Debug, PartialEq)]
+        struct Region(&'static str);
+
+        let bag = crate::config_bag! {
+            Region: Region("us-east-1"),
+            u32: 3,
+        };
+        assert_eq!(bag.get::<Region>(), Some(&Region("us-east-1")));
+        assert_eq!(bag.get::<u32>(), Some(&3));
+
+        // Later layers still override the defaults
+        let bag = bag
+            .add_layer("operation")
+            .with_defaults([ConfigBagEntry::new(Region("eu-west-1"))]);
+        assert_eq!(bag.get::<Region>(), Some(&Region("eu-west-1")));
+        assert_eq!(bag.get::<u32>(), Some(&3));
+
+        let entry = ConfigBagEntry::new(Region("us-west-2"));
+        assert!(entry.type_name().ends_with("Region"));
+        assert!(format!("{:?}", entry).contains("us-west-2"));
+    }
+
+    #[test]
+    fn forks_diverge_and_merge_back() {
+        #[derive(Debug, PartialEq)]
+        struct PartNumber(u32);
+        #[derive(Debug, PartialEq)]
+        struct Checksum(&'static str);
+
+        let mut bag = ConfigBag::base();
+        bag.put(PartNumber(0)).put("shared");
+        bag.push(Checksum("crc32"));
+
+        let (mut fork_1, handle_1) = bag.fork();
+        let (mut fork_2, handle_2) = bag.fork();
+        fork_1.put(PartNumber(1));
+        fork_1.push(Checksum("part 1"));
+        fork_2.put(PartNumber(2)).put(2_u8);
+        fork_2.push(Checksum("part 2"));
+        bag.put(3_u16);
+
+        // Forks see the values from before the fork, but not each other's changes
+        assert_eq!(fork_1.get::<&'static str>(), Some(&"shared"));
+        assert_eq!(fork_1.get::<u8>(), None);
+        assert_eq!(fork_1.get::<u16>(), None);
+        assert_eq!(bag.get::<PartNumber>(), Some(&PartNumber(0)));
+
+        handle_1.merge(&mut bag, fork_1, MergePriority::Other);
+        handle_2.merge(&mut bag, fork_2, MergePriority::Self_);
+        assert_eq!(bag.get::<PartNumber>(), Some(&PartNumber(1)));
+        assert_eq!(bag.get::<u8>(), Some(&2));
+        assert_eq!(bag.get::<u16>(), Some(&3));
+        // Values shared before the fork aren't duplicated, and the second fork's checksum isn't
+        // merged since the bag already has checksums
+        assert_eq!(
+            bag.get_all::<Checksum>(),
+            vec![&Checksum("crc32"), &Checksum("part 1")]
+        );
+    }
+
+    #[test]
+    fn transaction() {
+        let mut bag = ConfigBag::base();
+        bag.put(1_u8).put("kept");
+        bag.push(1_u16);
+
+        let result = bag.transaction(|bag| {
+            bag.put(2_u8).unset::<&'static str>();
+            bag.push(2_u16);
+            Ok::<_, ()>(bag.get::<u8>().copied())
+        });
+        assert_eq!(result, Ok(Some(2)));
+        assert_eq!(bag.get::<u8>(), Some(&2));
+        assert_eq!(bag.get::<&'static str>(), None);
+        assert_eq!(bag.typed_iter::<u16>().count(), 2);
+
+        let result: Result<(), _> = bag.transaction(|bag| {
+            bag.put(3_u8);
+            Err("rolled back")
+        });
+        assert_eq!(result, Err("rolled back"));
+        assert_eq!(bag.get::<u8>(), Some(&2));
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            bag.transaction::<(), ()>(|bag| {
+                bag.put(4_u8);
+                panic!("the transaction must not be applied")
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(bag.get::<u8>(), Some(&2));
+    }
 }
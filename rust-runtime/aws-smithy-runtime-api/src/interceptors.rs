@@ -3,12 +3,30 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+pub mod composite;
+pub mod conditional;
 pub mod context;
 pub mod error;
+pub mod hooks;
+pub mod plan;
 
 use crate::config_bag::ConfigBag;
-pub use context::InterceptorContext;
-pub use error::InterceptorError;
+use crate::protocol::ProtocolId;
+pub use composite::CompositeInterceptor;
+pub use conditional::{ConditionalInterceptor, InterceptorPredicate};
+pub use context::{
+    AttemptOutcome, AttemptResult, AuthScheme, CancellationReason, ErrorDetails,
+    InterceptorContext, InterceptorHookTimeout, ModeledResponse, OperationTimeout, RetryDecision,
+    RetryReason, ServiceName, Signer, TraceContext,
+};
+pub use error::{CancelledError, ErrorKind, InterceptorError};
+pub use hooks::{HookPhase, InterceptorHookSet};
+pub use plan::{ExecutionPlan, HookStep, InterceptorInfo, InterceptorKind};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// An interceptor allows injecting code into the SDK ’s request execution pipeline.
 ///
@@ -21,6 +39,47 @@ pub use error::InterceptorError;
 ///   to read in-flight request or response messages, or "read/write" hooks, which make it possible
 ///   to modify in-flight request or output messages.
 pub trait Interceptor<ModReq, TxReq, TxRes, ModRes> {
+    /// The name of this interceptor, used to identify it when inspecting or filtering
+    /// an [`Interceptors`] collection.
+    ///
+    /// Defaults to the type name of the interceptor.
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
+    /// The set of hooks this interceptor implements.
+    ///
+    /// [`Interceptors`] will skip this interceptor when dispatching any hook that isn't a
+    /// member of the returned set. Defaults to [`InterceptorHookSet::ALL`]. Interceptors that only
+    /// override a few hooks should return just those hooks, which documents their intent and
+    /// avoids the cost of dispatching to the default no-op implementations.
+    ///
+    /// The set can't be checked against the implementation, since Rust can't tell which hooks an
+    /// implementation overrides. A hook that's overridden but missing from the set is silently
+    /// never called, so a narrowed set must name every overridden hook.
+    fn hooks(&self) -> InterceptorHookSet {
+        InterceptorHookSet::ALL
+    }
+
+    /// Returns `true` if this interceptor should run for operations using `protocol`.
+    ///
+    /// [`Interceptors`] will skip this interceptor entirely when this returns `false` for the
+    /// [protocol of the current operation](InterceptorContext::protocol). Defaults to `true`.
+    fn supports_protocol(&self, protocol: &ProtocolId) -> bool {
+        let _protocol = protocol;
+        true
+    }
+
+    /// The longest each of this interceptor's hooks may run before it fails with
+    /// [`InterceptorError::hook_timeout`], enforced as described on
+    /// [`Interceptors::run_hook_with_timeout`].
+    ///
+    /// This takes precedence over the default set with [`Interceptors::with_interceptor_timeout`].
+    /// Defaults to `None`, which uses that default.
+    fn hook_timeout(&self) -> Option<Duration> {
+        None
+    }
+
     /// A hook called at the start of an execution, before the SDK
     /// does anything else.
     ///
@@ -371,6 +430,35 @@ pub trait Interceptor<ModReq, TxReq, TxRes, ModRes> {
         Ok(())
     }
 
+    /// A hook called when the transport fails to send the request or to receive a response,
+    /// e.g. because the connection was refused or the TLS handshake failed.
+    ///
+    /// This hook is an extension to the Smithy interceptor specification.
+    ///
+    /// **When:** This will be called instead of `read_after_transmit` when sending the transport
+    /// request fails, including when the operation timeout elapses. It is called before the
+    /// runtime decides whether to retry. This method may be called multiple times in the event of
+    /// retries.
+    ///
+    /// **Available Information:** The [InterceptorContext::modeled_request()] and
+    /// [InterceptorContext::tx_request()] are **ALWAYS** available, along with the `error`
+    /// returned by the transport. The [InterceptorContext::tx_response()] **WILL NOT** be
+    /// available.
+    ///
+    /// **Error Behavior:** If errors are raised by this hook, the attempt fails with the raised
+    /// error instead of the transport error.
+    fn read_on_transport_error(
+        &mut self,
+        context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+        error: &(dyn std::error::Error + Send + Sync + 'static),
+    ) -> Result<(), InterceptorError> {
+        let _ctx = context;
+        let _cfg = cfg;
+        let _error = error;
+        Ok(())
+    }
+
     /// A hook called before the transport response message is unmarshalled.
     /// This method has the ability to modify and return a new transport
     /// response message of the same type.
@@ -530,6 +618,63 @@ pub trait Interceptor<ModReq, TxReq, TxRes, ModRes> {
         Ok(())
     }
 
+    /// A hook called between attempts, when the retry strategy has decided to retry.
+    ///
+    /// This hook is an extension to the Smithy interceptor specification.
+    ///
+    /// **When:** This will be called once before each retry, after `read_after_attempt` for the
+    /// failed attempt and before the runtime waits to make the next attempt. It is not called
+    /// before the first attempt.
+    ///
+    /// **Available Information:** The [InterceptorContext::modeled_request()] is **ALWAYS**
+    /// available, along with the [InterceptorContext::last_attempt_error()] if the failed attempt
+    /// raised an interceptor error. `delay` is the time the runtime will wait before the next
+    /// attempt, as hinted by the retry strategy or decided by its backoff.
+    ///
+    /// **Error Behavior:** If errors are raised by this hook, the execution is not retried and
+    /// the error is returned to the customer.
+    ///
+    /// **Return Constraints:** `delay` may be modified to override the time waited before the
+    /// next attempt, e.g. to add jitter or to skip the wait entirely.
+    fn modify_before_retry(
+        &mut self,
+        context: &mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+        delay: &mut Duration,
+    ) -> Result<(), InterceptorError> {
+        let _ctx = context;
+        let _cfg = cfg;
+        let _delay = delay;
+        Ok(())
+    }
+
+    /// A hook called between attempts, once the runtime has waited to make the next attempt.
+    ///
+    /// This hook is an extension to the Smithy interceptor specification.
+    ///
+    /// **When:** This will be called once before each retry, after the runtime has waited for the
+    /// delay decided in `modify_before_retry` and before `read_before_attempt` for the next
+    /// attempt. It is not called before the first attempt.
+    ///
+    /// **Available Information:** The [InterceptorContext::modeled_request()] is **ALWAYS**
+    /// available, along with the [InterceptorContext::last_attempt_error()] if the failed attempt
+    /// raised an interceptor error. `actual_sleep` is the time the runtime actually spent waiting,
+    /// which may differ from the requested delay, e.g. due to timer resolution.
+    ///
+    /// **Error Behavior:** If errors are raised by this hook, the execution is not retried and
+    /// the error is returned to the customer.
+    fn read_between_attempts(
+        &mut self,
+        context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+        actual_sleep: Duration,
+    ) -> Result<(), InterceptorError> {
+        let _ctx = context;
+        let _cfg = cfg;
+        let _actual_sleep = actual_sleep;
+        Ok(())
+    }
+
     /// A hook called when an execution is completed.
     /// This method has the ability to modify and return a new
     /// output message or error matching the currently - executing
@@ -591,23 +736,138 @@ pub trait Interceptor<ModReq, TxReq, TxRes, ModRes> {
 
 pub struct Interceptors<ModReq, TxReq, TxRes, ModRes> {
     client_interceptors: Vec<Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>>,
+    client_interceptor_factories: Vec<InterceptorFactory<ModReq, TxReq, TxRes, ModRes>>,
+    /// The interceptors produced by `client_interceptor_factories` for the current execution
+    factory_interceptors: Vec<Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>>,
     operation_interceptors: Vec<Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>>,
+    /// Client interceptors that are still being initialized
+    deferred_client_interceptors: Vec<DeferredInterceptor<ModReq, TxReq, TxRes, ModRes>>,
+    /// The timeout for interceptors that don't override [`Interceptor::hook_timeout`]
+    interceptor_timeout: Option<Duration>,
 }
 
+type InterceptorFactory<ModReq, TxReq, TxRes, ModRes> =
+    Box<dyn Fn(&ConfigBag) -> Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>> + Send + Sync>;
+
+type DeferredInterceptor<ModReq, TxReq, TxRes, ModRes> =
+    Pin<Box<dyn Future<Output = Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>> + Send>>;
+
 impl<ModReq, TxReq, TxRes, ModRes> Default for Interceptors<ModReq, TxReq, TxRes, ModRes> {
     fn default() -> Self {
         Self {
             client_interceptors: Vec::new(),
+            client_interceptor_factories: Vec::new(),
+            factory_interceptors: Vec::new(),
             operation_interceptors: Vec::new(),
+            deferred_client_interceptors: Vec::new(),
+            interceptor_timeout: None,
         }
     }
 }
 
+impl<ModReq, TxReq, TxRes, ModRes> fmt::Debug for Interceptors<ModReq, TxReq, TxRes, ModRes> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let client: Vec<_> = self.client_interceptors().map(|i| i.name()).collect();
+        let operation: Vec<_> = self.operation_interceptors().map(|i| i.name()).collect();
+        f.debug_struct("Interceptors")
+            .field("client_interceptors", &client)
+            .field("operation_interceptors", &operation)
+            .finish()
+    }
+}
+
+impl<ModReq, TxReq, TxRes, ModRes> FromIterator<Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>>
+    for Interceptors<ModReq, TxReq, TxRes, ModRes>
+{
+    /// Collect boxed interceptors into an `Interceptors` as client interceptors.
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>>,
+    {
+        Self::from_client_interceptors(iter)
+    }
+}
+
 impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Create a new `Interceptors` with the given client interceptors, e.g. from a plugin registry.
+    pub fn from_client_interceptors(
+        interceptors: impl IntoIterator<Item = Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>>,
+    ) -> Self {
+        let mut this = Self::new();
+        this.extend_with_client_interceptors(interceptors);
+        this
+    }
+
+    /// Create a new `Interceptors` with the given client and operation interceptors.
+    ///
+    /// This is the inverse of [`Self::into_parts`].
+    pub fn from_parts(
+        client_interceptors: Vec<Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>>,
+        operation_interceptors: Vec<Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>>,
+    ) -> Self {
+        Self {
+            client_interceptors,
+            operation_interceptors,
+            ..Self::default()
+        }
+    }
+
+    /// Consume this `Interceptors`, returning its client and operation interceptors, e.g. to move
+    /// them to another client.
+    ///
+    /// Only interceptors registered directly are returned. Interceptor
+    /// [factories](Self::with_client_interceptor_factory), the interceptors they produced for the
+    /// current execution, and [deferred](Self::with_deferred_client_interceptor) interceptors
+    /// that haven't been resolved yet are dropped.
+    #[allow(clippy::type_complexity)]
+    pub fn into_parts(
+        self,
+    ) -> (
+        Vec<Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>>,
+        Vec<Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>>,
+    ) {
+        (self.client_interceptors, self.operation_interceptors)
+    }
+
+    /// Append all of `interceptors` to the client interceptors.
+    pub fn extend_with_client_interceptors(
+        &mut self,
+        interceptors: impl IntoIterator<Item = Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>>,
+    ) -> &mut Self {
+        self.client_interceptors.extend(interceptors);
+        self
+    }
+
+    /// Move all client interceptors to the end of `target`'s client interceptors, e.g. when a
+    /// wrapper client delegates to an inner client.
+    ///
+    /// The interceptors keep their relative order. Interceptor
+    /// [factories](Self::with_client_interceptor_factory) and
+    /// [deferred](Self::with_deferred_client_interceptor) interceptors aren't moved.
+    pub fn drain_client_interceptors_into(&mut self, target: &mut Self) {
+        target
+            .client_interceptors
+            .append(&mut self.client_interceptors);
+    }
+
+    /// Append all of `interceptors` to the operation interceptors.
+    pub fn extend_with_operation_interceptors(
+        &mut self,
+        interceptors: impl IntoIterator<Item = Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>>,
+    ) -> &mut Self {
+        self.operation_interceptors.extend(interceptors);
+        self
+    }
+
+    /// Register an interceptor that runs for every operation invoked by the client.
+    ///
+    /// The interceptor is registered as soon as this is called. The returned reference is only
+    /// provided for chaining, so it's fine to ignore it. This is deliberately not `#[must_use]`,
+    /// since that would warn on the last call of every chain.
     pub fn with_client_interceptor(
         &mut self,
         interceptor: impl Interceptor<ModReq, TxReq, TxRes, ModRes> + 'static,
@@ -616,6 +876,9 @@ impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
         self
     }
 
+    /// Register an interceptor that runs only for the operation being invoked.
+    ///
+    /// As with [`Self::with_client_interceptor`], the returned reference may be ignored.
     pub fn with_operation_interceptor(
         &mut self,
         interceptor: impl Interceptor<ModReq, TxReq, TxRes, ModRes> + 'static,
@@ -624,12 +887,338 @@ impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
         self
     }
 
+    /// Register a client interceptor that only runs while `condition` holds.
+    ///
+    /// `condition` is evaluated against the current [`ConfigBag`] before each hook is dispatched,
+    /// so the interceptor can be toggled by feature flags stored in the bag. See
+    /// [`ConditionalInterceptor`].
+    pub fn with_conditional_client_interceptor(
+        &mut self,
+        condition: impl Fn(&ConfigBag) -> bool + Send + Sync + 'static,
+        interceptor: impl Interceptor<ModReq, TxReq, TxRes, ModRes> + 'static,
+    ) -> &mut Self
+    where
+        ModReq: 'static,
+        TxReq: 'static,
+        TxRes: 'static,
+        ModRes: 'static,
+    {
+        self.with_client_interceptor(ConditionalInterceptor::new(condition, interceptor))
+    }
+
+    /// Register an operation interceptor that only runs while `condition` holds.
+    ///
+    /// See [`Self::with_conditional_client_interceptor`].
+    pub fn with_conditional_operation_interceptor(
+        &mut self,
+        condition: impl Fn(&ConfigBag) -> bool + Send + Sync + 'static,
+        interceptor: impl Interceptor<ModReq, TxReq, TxRes, ModRes> + 'static,
+    ) -> &mut Self
+    where
+        ModReq: 'static,
+        TxReq: 'static,
+        TxRes: 'static,
+        ModRes: 'static,
+    {
+        self.with_operation_interceptor(ConditionalInterceptor::new(condition, interceptor))
+    }
+
+    /// Skip every hook of the client and operation interceptors [named](Interceptor::name) `name`
+    /// unless `predicate` returns `true` for the current context and [`ConfigBag`].
+    ///
+    /// This gates interceptors that are already registered, e.g. behind a feature flag, without
+    /// changing their code or how they're registered. Each matching interceptor is wrapped in a
+    /// [`ConditionalInterceptor`] that evaluates `predicate` before each hook is dispatched.
+    /// Interceptors produced by [factories](Self::with_client_interceptor_factory) or registered
+    /// later aren't affected.
+    pub fn with_interceptor_predicate(
+        &mut self,
+        name: &str,
+        predicate: impl Fn(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>, &ConfigBag) -> bool
+            + Send
+            + Sync
+            + 'static,
+    ) -> &mut Self
+    where
+        ModReq: 'static,
+        TxReq: 'static,
+        TxRes: 'static,
+        ModRes: 'static,
+    {
+        let predicate: InterceptorPredicate<ModReq, TxReq, TxRes, ModRes> = Arc::new(predicate);
+        for interceptors in [
+            &mut self.client_interceptors,
+            &mut self.operation_interceptors,
+        ] {
+            *interceptors = std::mem::take(interceptors)
+                .into_iter()
+                .map(|interceptor| {
+                    if interceptor.name() == name {
+                        Box::new(ConditionalInterceptor::from_parts(
+                            predicate.clone(),
+                            interceptor,
+                        )) as Box<dyn Interceptor<_, _, _, _>>
+                    } else {
+                        interceptor
+                    }
+                })
+                .collect();
+        }
+        self
+    }
+
+    /// Register a factory that produces a client interceptor for each execution.
+    ///
+    /// This is useful for interceptors that need values from the [`ConfigBag`] that aren't
+    /// available when the client is built, e.g. credentials for a signing interceptor. The factory
+    /// is kept for the lifetime of the client, and is called once at the start of every execution
+    /// by [`Self::instantiate_interceptor_factories`]. The interceptors it produces run after all
+    /// other client interceptors.
+    pub fn with_client_interceptor_factory(
+        &mut self,
+        factory: impl Fn(&ConfigBag) -> Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>
+            + Send
+            + Sync
+            + 'static,
+    ) -> &mut Self {
+        self.client_interceptor_factories.push(Box::new(factory));
+        self
+    }
+
+    /// Produce this execution's interceptors from the registered
+    /// [factories](Self::with_client_interceptor_factory), replacing those produced for any
+    /// previous execution.
+    ///
+    /// This is called by the runtime once the client's configuration has been applied to `cfg`.
+    pub fn instantiate_interceptor_factories(&mut self, cfg: &ConfigBag) {
+        self.factory_interceptors = self
+            .client_interceptor_factories
+            .iter()
+            .map(|factory| factory(cfg))
+            .collect();
+    }
+
+    /// Register a client interceptor that needs to be initialized asynchronously, e.g. by
+    /// fetching an initial token from a remote service.
+    ///
+    /// `interceptor` is awaited by [`Self::resolve_deferred_interceptors`], which the runtime
+    /// calls before any hooks are run. Once resolved, the interceptor is appended to the client
+    /// interceptors and kept for the lifetime of the client. Until then, it isn't counted by
+    /// [`Self::len`] and none of its hooks are run.
+    pub fn with_deferred_client_interceptor(
+        &mut self,
+        interceptor: impl Future<Output = Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>>
+            + Send
+            + 'static,
+    ) -> &mut Self {
+        self.deferred_client_interceptors
+            .push(Box::pin(interceptor));
+        self
+    }
+
+    /// Await all [deferred](Self::with_deferred_client_interceptor) client interceptors, in the
+    /// order they were registered, and append them to the client interceptors.
+    ///
+    /// This does nothing if there are no deferred interceptors left to resolve.
+    pub async fn resolve_deferred_interceptors(&mut self) {
+        for deferred in std::mem::take(&mut self.deferred_client_interceptors) {
+            let interceptor = deferred.await;
+            self.client_interceptors.push(interceptor);
+        }
+    }
+
+    /// Insert a client interceptor at position `index`, shifting all interceptors after it.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than the number of client interceptors.
+    pub fn insert_client_interceptor_at(
+        &mut self,
+        index: usize,
+        interceptor: impl Interceptor<ModReq, TxReq, TxRes, ModRes> + 'static,
+    ) -> &mut Self {
+        self.client_interceptors
+            .insert(index, Box::new(interceptor));
+        self
+    }
+
+    /// Insert a client interceptor so that it runs before all other client interceptors.
+    pub fn push_front_client_interceptor(
+        &mut self,
+        interceptor: impl Interceptor<ModReq, TxReq, TxRes, ModRes> + 'static,
+    ) -> &mut Self {
+        self.insert_client_interceptor_at(0, interceptor)
+    }
+
+    /// Insert an operation interceptor at position `index`, shifting all interceptors after it.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than the number of operation interceptors.
+    pub fn insert_operation_interceptor_at(
+        &mut self,
+        index: usize,
+        interceptor: impl Interceptor<ModReq, TxReq, TxRes, ModRes> + 'static,
+    ) -> &mut Self {
+        self.operation_interceptors
+            .insert(index, Box::new(interceptor));
+        self
+    }
+
+    /// Insert an operation interceptor so that it runs before all other operation interceptors.
+    pub fn push_front_operation_interceptor(
+        &mut self,
+        interceptor: impl Interceptor<ModReq, TxReq, TxRes, ModRes> + 'static,
+    ) -> &mut Self {
+        self.insert_operation_interceptor_at(0, interceptor)
+    }
+
+    /// Replace the first client interceptor [named](Interceptor::name) `name` with `interceptor`,
+    /// keeping its position, and return the replaced interceptor.
+    ///
+    /// This makes it possible to substitute a test double for a single interceptor, e.g. the
+    /// signing interceptor, without rebuilding the rest of the chain. Returns `None` and drops
+    /// `interceptor` if there is no client interceptor with that name.
+    pub fn swap_client_interceptor(
+        &mut self,
+        name: &str,
+        interceptor: impl Interceptor<ModReq, TxReq, TxRes, ModRes> + 'static,
+    ) -> Option<Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>> {
+        swap_interceptor(&mut self.client_interceptors, name, Box::new(interceptor))
+    }
+
+    /// Replace the first operation interceptor [named](Interceptor::name) `name` with
+    /// `interceptor`, keeping its position, and return the replaced interceptor.
+    ///
+    /// Returns `None` and drops `interceptor` if there is no operation interceptor with that name.
+    pub fn swap_operation_interceptor(
+        &mut self,
+        name: &str,
+        interceptor: impl Interceptor<ModReq, TxReq, TxRes, ModRes> + 'static,
+    ) -> Option<Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>> {
+        swap_interceptor(
+            &mut self.operation_interceptors,
+            name,
+            Box::new(interceptor),
+        )
+    }
+
+    /// Limit how long each hook of every interceptor may run, failing the hook with
+    /// [`InterceptorError::hook_timeout`] if it takes longer.
+    ///
+    /// Interceptors that override [`Interceptor::hook_timeout`] use their own timeout instead.
+    /// The timeout is enforced the same way as [`Self::run_hook_with_timeout`], so it's only
+    /// checked once each hook returns.
+    pub fn with_interceptor_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.interceptor_timeout = Some(timeout);
+        self
+    }
+
+    /// The number of client and operation interceptors.
+    ///
+    /// This includes the interceptors produced by factories for the current execution.
+    pub fn len(&self) -> usize {
+        self.client_interceptors.len()
+            + self.factory_interceptors.len()
+            + self.operation_interceptors.len()
+    }
+
+    /// Returns `true` if there are no client or operation interceptors.
+    pub fn is_empty(&self) -> bool {
+        let is_empty = self.client_interceptors.is_empty()
+            && self.factory_interceptors.is_empty()
+            && self.operation_interceptors.is_empty();
+        debug_assert_eq!(is_empty, self.len() == 0);
+        is_empty
+    }
+
+    /// The registered client interceptors, in the order they run.
+    ///
+    /// This includes the interceptors produced by factories for the current execution.
+    pub fn client_interceptors(
+        &self,
+    ) -> impl Iterator<Item = &Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>> {
+        self.client_interceptors
+            .iter()
+            .chain(self.factory_interceptors.iter())
+    }
+
+    /// The registered operation interceptors, in the order they run.
+    pub fn operation_interceptors(
+        &self,
+    ) -> impl Iterator<Item = &Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>> {
+        self.operation_interceptors.iter()
+    }
+
+    /// The client interceptors [named](Interceptor::name) `name`, in the order they run.
+    ///
+    /// Several interceptors may share a name, e.g. when the same interceptor type is registered
+    /// more than once, so all of them are returned. This includes the interceptors produced by
+    /// factories for the current execution.
+    pub fn client_interceptors_named<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a dyn Interceptor<ModReq, TxReq, TxRes, ModRes>> {
+        self.client_interceptors()
+            .filter(move |interceptor| interceptor.name() == name)
+            .map(|interceptor| interceptor.as_ref())
+    }
+
+    /// Returns `true` if a client interceptor [named](Interceptor::name) `name` is registered,
+    /// e.g. to avoid registering a logging interceptor twice.
+    pub fn contains_client_interceptor_named(&self, name: &str) -> bool {
+        self.client_interceptors_named(name).next().is_some()
+    }
+
+    /// The client and operation interceptors that will run for `hook`, in the order they run.
+    ///
+    /// This makes it possible to inspect the interceptors that will run for a hook without running
+    /// it. `hook` should be a single hook; when it contains several, interceptors that implement
+    /// all of them are returned.
+    #[allow(clippy::type_complexity)]
+    pub fn partition_by_phase(
+        &self,
+        hook: InterceptorHookSet,
+    ) -> (
+        impl Iterator<Item = &Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>>,
+        impl Iterator<Item = &Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>>,
+    ) {
+        (
+            self.client_interceptors()
+                .filter(move |interceptor| interceptor.hooks().contains(hook)),
+            self.operation_interceptors()
+                .filter(move |interceptor| interceptor.hooks().contains(hook)),
+        )
+    }
+
+    /// Retain only the client interceptors for which `pred` returns `true`.
+    pub fn retain_client_interceptors(
+        &mut self,
+        pred: impl Fn(&dyn Interceptor<ModReq, TxReq, TxRes, ModRes>) -> bool,
+    ) -> &mut Self {
+        self.client_interceptors
+            .retain(|interceptor| pred(interceptor.as_ref()));
+        self
+    }
+
+    /// Retain only the operation interceptors for which `pred` returns `true`.
+    pub fn retain_operation_interceptors(
+        &mut self,
+        pred: impl Fn(&dyn Interceptor<ModReq, TxReq, TxRes, ModRes>) -> bool,
+    ) -> &mut Self {
+        self.operation_interceptors
+            .retain(|interceptor| pred(interceptor.as_ref()));
+        self
+    }
+
     fn all_interceptors_mut(
         &mut self,
+        hook: InterceptorHookSet,
+        protocol: Option<ProtocolId>,
     ) -> impl Iterator<Item = &mut Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>> {
         self.client_interceptors
             .iter_mut()
+            .chain(self.factory_interceptors.iter_mut())
             .chain(self.operation_interceptors.iter_mut())
+            .filter(move |interceptor| should_run(interceptor.as_ref(), hook, protocol))
     }
 
     pub fn client_read_before_execution(
@@ -637,10 +1226,25 @@ impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
         context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
         cfg: &mut ConfigBag,
     ) -> Result<(), InterceptorError> {
-        for interceptor in self.client_interceptors.iter_mut() {
-            interceptor.read_before_execution(context, cfg)?;
-        }
-        Ok(())
+        let default_timeout = self.interceptor_timeout;
+        let protocol = context.protocol();
+        InterceptorError::collect(
+            self.client_interceptors
+                .iter_mut()
+                .chain(self.factory_interceptors.iter_mut())
+                .filter(|i| {
+                    should_run(
+                        i.as_ref(),
+                        InterceptorHookSet::READ_BEFORE_EXECUTION,
+                        protocol,
+                    )
+                })
+                .map(|interceptor| {
+                    guard_hook(interceptor, default_timeout, |interceptor| {
+                        interceptor.read_before_execution(context, cfg)
+                    })
+                }),
+        )
     }
 
     pub fn operation_read_before_execution(
@@ -648,10 +1252,50 @@ impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
         context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
         cfg: &mut ConfigBag,
     ) -> Result<(), InterceptorError> {
-        for interceptor in self.operation_interceptors.iter_mut() {
-            interceptor.read_before_execution(context, cfg)?;
-        }
-        Ok(())
+        let default_timeout = self.interceptor_timeout;
+        let protocol = context.protocol();
+        InterceptorError::collect(
+            self.operation_interceptors
+                .iter_mut()
+                .filter(|i| {
+                    should_run(
+                        i.as_ref(),
+                        InterceptorHookSet::READ_BEFORE_EXECUTION,
+                        protocol,
+                    )
+                })
+                .map(|interceptor| {
+                    guard_hook(interceptor, default_timeout, |interceptor| {
+                        interceptor.read_before_execution(context, cfg)
+                    })
+                }),
+        )
+    }
+
+    /// Run `read_before_execution` for every client and operation interceptor, returning all of
+    /// the errors they produced in the order the interceptors ran.
+    ///
+    /// The Smithy interceptor specification requires every interceptor to run during
+    /// `read_before_execution`, even if an earlier one fails. An empty `Vec` means that all
+    /// interceptors succeeded; otherwise the caller decides how to report the errors, e.g. by
+    /// surfacing the last one as the specification describes.
+    pub fn run_read_before_execution(
+        &mut self,
+        context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+    ) -> Vec<InterceptorError> {
+        let default_timeout = self.interceptor_timeout;
+        self.all_interceptors_mut(
+            InterceptorHookSet::READ_BEFORE_EXECUTION,
+            context.protocol(),
+        )
+        .filter_map(|interceptor| {
+            guard_hook(interceptor, default_timeout, |interceptor| {
+                interceptor.read_before_execution(context, cfg)
+            })
+            .err()
+        })
+        .collect()
     }
 
     pub fn modify_before_serialization(
@@ -659,8 +1303,14 @@ impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
         context: &mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
         cfg: &mut ConfigBag,
     ) -> Result<(), InterceptorError> {
-        for interceptor in self.all_interceptors_mut() {
-            interceptor.modify_before_serialization(context, cfg)?;
+        let default_timeout = self.interceptor_timeout;
+        for interceptor in self.all_interceptors_mut(
+            InterceptorHookSet::MODIFY_BEFORE_SERIALIZATION,
+            context.protocol(),
+        ) {
+            guard_hook(interceptor, default_timeout, |interceptor| {
+                interceptor.modify_before_serialization(context, cfg)
+            })?;
         }
 
         Ok(())
@@ -671,8 +1321,14 @@ impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
         context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
         cfg: &mut ConfigBag,
     ) -> Result<(), InterceptorError> {
-        for interceptor in self.all_interceptors_mut() {
-            interceptor.read_before_serialization(context, cfg)?;
+        let default_timeout = self.interceptor_timeout;
+        for interceptor in self.all_interceptors_mut(
+            InterceptorHookSet::READ_BEFORE_SERIALIZATION,
+            context.protocol(),
+        ) {
+            guard_hook(interceptor, default_timeout, |interceptor| {
+                interceptor.read_before_serialization(context, cfg)
+            })?;
         }
         Ok(())
     }
@@ -682,8 +1338,14 @@ impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
         context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
         cfg: &mut ConfigBag,
     ) -> Result<(), InterceptorError> {
-        for interceptor in self.all_interceptors_mut() {
-            interceptor.read_after_serialization(context, cfg)?;
+        let default_timeout = self.interceptor_timeout;
+        for interceptor in self.all_interceptors_mut(
+            InterceptorHookSet::READ_AFTER_SERIALIZATION,
+            context.protocol(),
+        ) {
+            guard_hook(interceptor, default_timeout, |interceptor| {
+                interceptor.read_after_serialization(context, cfg)
+            })?;
         }
         Ok(())
     }
@@ -693,8 +1355,14 @@ impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
         context: &mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
         cfg: &mut ConfigBag,
     ) -> Result<(), InterceptorError> {
-        for interceptor in self.all_interceptors_mut() {
-            interceptor.modify_before_retry_loop(context, cfg)?;
+        let default_timeout = self.interceptor_timeout;
+        for interceptor in self.all_interceptors_mut(
+            InterceptorHookSet::MODIFY_BEFORE_RETRY_LOOP,
+            context.protocol(),
+        ) {
+            guard_hook(interceptor, default_timeout, |interceptor| {
+                interceptor.modify_before_retry_loop(context, cfg)
+            })?;
         }
 
         Ok(())
@@ -705,10 +1373,15 @@ impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
         context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
         cfg: &mut ConfigBag,
     ) -> Result<(), InterceptorError> {
-        for interceptor in self.all_interceptors_mut() {
-            interceptor.read_before_attempt(context, cfg)?;
-        }
-        Ok(())
+        let default_timeout = self.interceptor_timeout;
+        InterceptorError::collect(
+            self.all_interceptors_mut(InterceptorHookSet::READ_BEFORE_ATTEMPT, context.protocol())
+                .map(|interceptor| {
+                    guard_hook(interceptor, default_timeout, |interceptor| {
+                        interceptor.read_before_attempt(context, cfg)
+                    })
+                }),
+        )
     }
 
     pub fn modify_before_signing(
@@ -716,8 +1389,14 @@ impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
         context: &mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
         cfg: &mut ConfigBag,
     ) -> Result<(), InterceptorError> {
-        for interceptor in self.all_interceptors_mut() {
-            interceptor.modify_before_signing(context, cfg)?;
+        let default_timeout = self.interceptor_timeout;
+        for interceptor in self.all_interceptors_mut(
+            InterceptorHookSet::MODIFY_BEFORE_SIGNING,
+            context.protocol(),
+        ) {
+            guard_hook(interceptor, default_timeout, |interceptor| {
+                interceptor.modify_before_signing(context, cfg)
+            })?;
         }
 
         Ok(())
@@ -728,8 +1407,13 @@ impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
         context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
         cfg: &mut ConfigBag,
     ) -> Result<(), InterceptorError> {
-        for interceptor in self.all_interceptors_mut() {
-            interceptor.read_before_signing(context, cfg)?;
+        let default_timeout = self.interceptor_timeout;
+        for interceptor in
+            self.all_interceptors_mut(InterceptorHookSet::READ_BEFORE_SIGNING, context.protocol())
+        {
+            guard_hook(interceptor, default_timeout, |interceptor| {
+                interceptor.read_before_signing(context, cfg)
+            })?;
         }
         Ok(())
     }
@@ -739,8 +1423,13 @@ impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
         context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
         cfg: &mut ConfigBag,
     ) -> Result<(), InterceptorError> {
-        for interceptor in self.all_interceptors_mut() {
-            interceptor.read_after_signing(context, cfg)?;
+        let default_timeout = self.interceptor_timeout;
+        for interceptor in
+            self.all_interceptors_mut(InterceptorHookSet::READ_AFTER_SIGNING, context.protocol())
+        {
+            guard_hook(interceptor, default_timeout, |interceptor| {
+                interceptor.read_after_signing(context, cfg)
+            })?;
         }
         Ok(())
     }
@@ -750,8 +1439,14 @@ impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
         context: &mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
         cfg: &mut ConfigBag,
     ) -> Result<(), InterceptorError> {
-        for interceptor in self.all_interceptors_mut() {
-            interceptor.modify_before_transmit(context, cfg)?;
+        let default_timeout = self.interceptor_timeout;
+        for interceptor in self.all_interceptors_mut(
+            InterceptorHookSet::MODIFY_BEFORE_TRANSMIT,
+            context.protocol(),
+        ) {
+            guard_hook(interceptor, default_timeout, |interceptor| {
+                interceptor.modify_before_transmit(context, cfg)
+            })?;
         }
 
         Ok(())
@@ -762,8 +1457,13 @@ impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
         context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
         cfg: &mut ConfigBag,
     ) -> Result<(), InterceptorError> {
-        for interceptor in self.all_interceptors_mut() {
-            interceptor.read_before_transmit(context, cfg)?;
+        let default_timeout = self.interceptor_timeout;
+        for interceptor in
+            self.all_interceptors_mut(InterceptorHookSet::READ_BEFORE_TRANSMIT, context.protocol())
+        {
+            guard_hook(interceptor, default_timeout, |interceptor| {
+                interceptor.read_before_transmit(context, cfg)
+            })?;
         }
         Ok(())
     }
@@ -773,8 +1473,31 @@ impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
         context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
         cfg: &mut ConfigBag,
     ) -> Result<(), InterceptorError> {
-        for interceptor in self.all_interceptors_mut() {
-            interceptor.read_after_transmit(context, cfg)?;
+        let default_timeout = self.interceptor_timeout;
+        for interceptor in
+            self.all_interceptors_mut(InterceptorHookSet::READ_AFTER_TRANSMIT, context.protocol())
+        {
+            guard_hook(interceptor, default_timeout, |interceptor| {
+                interceptor.read_after_transmit(context, cfg)
+            })?;
+        }
+        Ok(())
+    }
+
+    pub fn read_on_transport_error(
+        &mut self,
+        context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+        error: &(dyn std::error::Error + Send + Sync + 'static),
+    ) -> Result<(), InterceptorError> {
+        let default_timeout = self.interceptor_timeout;
+        for interceptor in self.all_interceptors_mut(
+            InterceptorHookSet::READ_ON_TRANSPORT_ERROR,
+            context.protocol(),
+        ) {
+            guard_hook(interceptor, default_timeout, |interceptor| {
+                interceptor.read_on_transport_error(context, cfg, error)
+            })?;
         }
         Ok(())
     }
@@ -784,8 +1507,14 @@ impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
         context: &mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
         cfg: &mut ConfigBag,
     ) -> Result<(), InterceptorError> {
-        for interceptor in self.all_interceptors_mut() {
-            interceptor.modify_before_deserialization(context, cfg)?;
+        let default_timeout = self.interceptor_timeout;
+        for interceptor in self.all_interceptors_mut(
+            InterceptorHookSet::MODIFY_BEFORE_DESERIALIZATION,
+            context.protocol(),
+        ) {
+            guard_hook(interceptor, default_timeout, |interceptor| {
+                interceptor.modify_before_deserialization(context, cfg)
+            })?;
         }
 
         Ok(())
@@ -796,8 +1525,14 @@ impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
         context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
         cfg: &mut ConfigBag,
     ) -> Result<(), InterceptorError> {
-        for interceptor in self.all_interceptors_mut() {
-            interceptor.read_before_deserialization(context, cfg)?;
+        let default_timeout = self.interceptor_timeout;
+        for interceptor in self.all_interceptors_mut(
+            InterceptorHookSet::READ_BEFORE_DESERIALIZATION,
+            context.protocol(),
+        ) {
+            guard_hook(interceptor, default_timeout, |interceptor| {
+                interceptor.read_before_deserialization(context, cfg)
+            })?;
         }
         Ok(())
     }
@@ -807,8 +1542,14 @@ impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
         context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
         cfg: &mut ConfigBag,
     ) -> Result<(), InterceptorError> {
-        for interceptor in self.all_interceptors_mut() {
-            interceptor.read_after_deserialization(context, cfg)?;
+        let default_timeout = self.interceptor_timeout;
+        for interceptor in self.all_interceptors_mut(
+            InterceptorHookSet::READ_AFTER_DESERIALIZATION,
+            context.protocol(),
+        ) {
+            guard_hook(interceptor, default_timeout, |interceptor| {
+                interceptor.read_after_deserialization(context, cfg)
+            })?;
         }
         Ok(())
     }
@@ -818,8 +1559,14 @@ impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
         context: &mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
         cfg: &mut ConfigBag,
     ) -> Result<(), InterceptorError> {
-        for interceptor in self.all_interceptors_mut() {
-            interceptor.modify_before_attempt_completion(context, cfg)?;
+        let default_timeout = self.interceptor_timeout;
+        for interceptor in self.all_interceptors_mut(
+            InterceptorHookSet::MODIFY_BEFORE_ATTEMPT_COMPLETION,
+            context.protocol(),
+        ) {
+            guard_hook(interceptor, default_timeout, |interceptor| {
+                interceptor.modify_before_attempt_completion(context, cfg)
+            })?;
         }
 
         Ok(())
@@ -830,32 +1577,1232 @@ impl<ModReq, TxReq, TxRes, ModRes> Interceptors<ModReq, TxReq, TxRes, ModRes> {
         context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
         cfg: &mut ConfigBag,
     ) -> Result<(), InterceptorError> {
-        for interceptor in self.all_interceptors_mut() {
-            interceptor.read_after_attempt(context, cfg)?;
+        let default_timeout = self.interceptor_timeout;
+        InterceptorError::collect(
+            self.all_interceptors_mut(InterceptorHookSet::READ_AFTER_ATTEMPT, context.protocol())
+                .map(|interceptor| {
+                    guard_hook(interceptor, default_timeout, |interceptor| {
+                        interceptor.read_after_attempt(context, cfg)
+                    })
+                }),
+        )
+    }
+
+    pub fn modify_before_retry(
+        &mut self,
+        context: &mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+        delay: &mut Duration,
+    ) -> Result<(), InterceptorError> {
+        let default_timeout = self.interceptor_timeout;
+        for interceptor in
+            self.all_interceptors_mut(InterceptorHookSet::MODIFY_BEFORE_RETRY, context.protocol())
+        {
+            guard_hook(interceptor, default_timeout, |interceptor| {
+                interceptor.modify_before_retry(context, cfg, delay)
+            })?;
         }
+
         Ok(())
     }
 
+    pub fn read_between_attempts(
+        &mut self,
+        context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+        actual_sleep: Duration,
+    ) -> Result<(), InterceptorError> {
+        let default_timeout = self.interceptor_timeout;
+        InterceptorError::collect(
+            self.all_interceptors_mut(
+                InterceptorHookSet::READ_BETWEEN_ATTEMPTS,
+                context.protocol(),
+            )
+            .map(|interceptor| {
+                guard_hook(interceptor, default_timeout, |interceptor| {
+                    interceptor.read_between_attempts(context, cfg, actual_sleep)
+                })
+            }),
+        )
+    }
+
     pub fn modify_before_completion(
         &mut self,
         context: &mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
         cfg: &mut ConfigBag,
     ) -> Result<(), InterceptorError> {
-        for interceptor in self.all_interceptors_mut() {
-            interceptor.modify_before_completion(context, cfg)?;
+        let default_timeout = self.interceptor_timeout;
+        for interceptor in self.all_interceptors_mut(
+            InterceptorHookSet::MODIFY_BEFORE_COMPLETION,
+            context.protocol(),
+        ) {
+            guard_hook(interceptor, default_timeout, |interceptor| {
+                interceptor.modify_before_completion(context, cfg)
+            })?;
         }
 
         Ok(())
     }
 
+    /// Run `read_after_execution` for every client and operation interceptor.
+    ///
+    /// As with `read_before_execution`, the Smithy interceptor specification requires every
+    /// interceptor to run even if an earlier one fails. The errors are returned the same way as by
+    /// a [`CompositeInterceptor`]: a single error as-is, and several errors as an
+    /// [aggregated](InterceptorError::aggregate) error whose [`errors`](InterceptorError::errors)
+    /// are in the order the interceptors ran, so that the last one can be surfaced.
     pub fn read_after_execution(
         &mut self,
         context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
         cfg: &mut ConfigBag,
     ) -> Result<(), InterceptorError> {
-        for interceptor in self.all_interceptors_mut() {
-            interceptor.read_after_execution(context, cfg)?;
+        let default_timeout = self.interceptor_timeout;
+        InterceptorError::collect(
+            self.all_interceptors_mut(InterceptorHookSet::READ_AFTER_EXECUTION, context.protocol())
+                .map(|interceptor| {
+                    guard_hook(interceptor, default_timeout, |interceptor| {
+                        interceptor.read_after_execution(context, cfg)
+                    })
+                }),
+        )
+    }
+
+    /// Run the hook for `phase` on every interceptor that implements it, e.g. so that runtime code
+    /// can iterate over [`HookPhase`]s rather than calling each hook's method in turn.
+    ///
+    /// Each phase stops or continues after a failing interceptor exactly like the hook's own
+    /// method. All of the errors are returned, with [aggregated](InterceptorError::aggregate)
+    /// errors flattened, in the order the interceptors ran. `read_before_execution` runs for
+    /// both client and operation interceptors, as with [`Self::run_read_before_execution`].
+    pub fn try_run_hook(
+        &mut self,
+        phase: HookPhase,
+        context: &mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), Vec<InterceptorError>> {
+        let result = match phase {
+            HookPhase::ReadBeforeExecution => {
+                let errors = self.run_read_before_execution(context, cfg);
+                return if errors.is_empty() {
+                    Ok(())
+                } else {
+                    Err(errors)
+                };
+            }
+            HookPhase::ModifyBeforeSerialization => self.modify_before_serialization(context, cfg),
+            HookPhase::ReadBeforeSerialization => self.read_before_serialization(&*context, cfg),
+            HookPhase::ReadAfterSerialization => self.read_after_serialization(&*context, cfg),
+            HookPhase::ModifyBeforeRetryLoop => self.modify_before_retry_loop(context, cfg),
+            HookPhase::ReadBeforeAttempt => self.read_before_attempt(&*context, cfg),
+            HookPhase::ModifyBeforeSigning => self.modify_before_signing(context, cfg),
+            HookPhase::ReadBeforeSigning => self.read_before_signing(&*context, cfg),
+            HookPhase::ReadAfterSigning => self.read_after_signing(&*context, cfg),
+            HookPhase::ModifyBeforeTransmit => self.modify_before_transmit(context, cfg),
+            HookPhase::ReadBeforeTransmit => self.read_before_transmit(&*context, cfg),
+            HookPhase::ReadAfterTransmit => self.read_after_transmit(&*context, cfg),
+            HookPhase::ModifyBeforeDeserialization => {
+                self.modify_before_deserialization(context, cfg)
+            }
+            HookPhase::ReadBeforeDeserialization => {
+                self.read_before_deserialization(&*context, cfg)
+            }
+            HookPhase::ReadAfterDeserialization => self.read_after_deserialization(&*context, cfg),
+            HookPhase::ModifyBeforeAttemptCompletion => {
+                self.modify_before_attempt_completion(context, cfg)
+            }
+            HookPhase::ReadAfterAttempt => self.read_after_attempt(&*context, cfg),
+            HookPhase::ModifyBeforeCompletion => self.modify_before_completion(context, cfg),
+            HookPhase::ReadAfterExecution => self.read_after_execution(&*context, cfg),
+        };
+        result.map_err(InterceptorError::into_errors)
+    }
+
+    /// Describe which interceptors will run at each hook, in what order, and with what timeouts,
+    /// without running any of them.
+    ///
+    /// The plan is based on each interceptor's [hooks](Interceptor::hooks). Interceptors that
+    /// don't [support](Interceptor::supports_protocol) the operation's protocol, and conditional
+    /// interceptors whose condition doesn't hold, are still listed since they're only skipped
+    /// once the execution is running. Deferred interceptors are listed once they're resolved, and
+    /// factories once they've been instantiated.
+    pub fn debug_execution_plan(&self) -> ExecutionPlan {
+        let info = |interceptor: &dyn Interceptor<ModReq, TxReq, TxRes, ModRes>,
+                    kind: InterceptorKind| {
+            InterceptorInfo::new(
+                interceptor.name(),
+                kind,
+                interceptor.hook_timeout().or(self.interceptor_timeout),
+            )
+        };
+        let steps = HookPhase::ALL
+            .iter()
+            .map(|&phase| {
+                let hook = phase.hook_set();
+                let client = self
+                    .client_interceptors
+                    .iter()
+                    .chain(self.factory_interceptors.iter())
+                    .filter(|i| i.hooks().contains(hook))
+                    .map(|i| info(i.as_ref(), InterceptorKind::Client));
+                let operation = self
+                    .operation_interceptors
+                    .iter()
+                    .filter(|i| i.hooks().contains(hook))
+                    .map(|i| info(i.as_ref(), InterceptorKind::Operation));
+                HookStep::new(phase, client.chain(operation).collect())
+            })
+            .collect();
+        ExecutionPlan::new(steps)
+    }
+
+    /// Run `hook` on these interceptors, failing with [`InterceptorError::hook_timeout`] if it
+    /// takes longer than `timeout`, e.g. because an interceptor waited on a slow credentials
+    /// provider.
+    ///
+    /// Hooks are synchronous and borrow the interceptors, the context, and the config bag, so
+    /// they can't be moved to another thread or abandoned part-way through without giving up
+    /// those borrows. The hook therefore always runs to completion, and the timeout is enforced
+    /// once it returns: a hook that never returns isn't interrupted. Interceptors that block on
+    /// I/O should bound the I/O themselves. An error raised by the hook itself takes precedence
+    /// over the timeout. The runtime applies the [`InterceptorHookTimeout`] from the config bag
+    /// to every hook it runs.
+    pub fn run_hook_with_timeout<F>(
+        &mut self,
+        hook: F,
+        timeout: Duration,
+    ) -> Result<(), InterceptorError>
+    where
+        F: FnOnce(&mut Self) -> Result<(), InterceptorError>,
+    {
+        enforce_hook_timeout(self, timeout, hook)
+    }
+
+    /// Run every attempt-phase hook in the order required by the Smithy interceptor
+    /// specification, from `read_before_attempt` through `read_after_attempt`.
+    ///
+    /// `send_fn` transmits the signed request and is called between `read_before_transmit` and
+    /// `read_after_transmit`. `deserialize_fn` turns the transport response into the modeled
+    /// response and is called between `read_before_deserialization` and
+    /// `read_after_deserialization`. The transport request must already be set on `context`.
+    ///
+    /// This may be called once per attempt with the same `context`: the response state left over
+    /// from the previous attempt is cleared when each attempt starts.
+    ///
+    /// If a `modify_*` hook cancels the execution, the remaining hooks are skipped and `Ok(())`
+    /// is returned so that the caller can complete the execution.
+    pub fn orchestrate_attempt(
+        &mut self,
+        context: &mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+        send_fn: impl FnOnce(&mut TxReq, &mut ConfigBag) -> Result<TxRes, InterceptorError>,
+        deserialize_fn: impl FnOnce(&mut TxRes, &mut ConfigBag) -> Result<ModRes, InterceptorError>,
+    ) -> Result<(), InterceptorError> {
+        context.start_attempt();
+        self.read_before_attempt(context, cfg)?;
+        self.modify_before_signing(context, cfg)?;
+        if context.is_cancelled() {
+            return Ok(());
         }
-        Ok(())
+        self.read_before_signing(context, cfg)?;
+        self.read_after_signing(context, cfg)?;
+        self.modify_before_transmit(context, cfg)?;
+        if context.is_cancelled() {
+            return Ok(());
+        }
+        self.read_before_transmit(context, cfg)?;
+
+        context.mark_transmit_start();
+        let tx_res = send_fn(context.tx_request_mut()?, cfg)?;
+        context.mark_transmit_end();
+        context.set_tx_response(tx_res);
+
+        self.read_after_transmit(context, cfg)?;
+        self.modify_before_deserialization(context, cfg)?;
+        if context.is_cancelled() {
+            return Ok(());
+        }
+        self.read_before_deserialization(context, cfg)?;
+        let mod_res = deserialize_fn(context.tx_response_mut()?, cfg)?;
+        context.set_modeled_response(mod_res);
+        self.read_after_deserialization(context, cfg)?;
+
+        self.modify_before_attempt_completion(context, cfg)?;
+        if context.is_cancelled() {
+            return Ok(());
+        }
+        self.read_after_attempt(context, cfg)
+    }
+}
+
+fn swap_interceptor<ModReq, TxReq, TxRes, ModRes>(
+    interceptors: &mut [Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>],
+    name: &str,
+    interceptor: Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>,
+) -> Option<Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>> {
+    let existing = interceptors.iter_mut().find(|i| i.name() == name)?;
+    Some(std::mem::replace(existing, interceptor))
+}
+
+/// Run `hook` on `interceptor` with [`Interceptors::run_hook_with_timeout`]'s timeout, using the
+/// interceptor's own [hook timeout](Interceptor::hook_timeout) or, if it doesn't have one,
+/// `default_timeout`
+fn guard_hook<ModReq, TxReq, TxRes, ModRes>(
+    interceptor: &mut Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>,
+    default_timeout: Option<Duration>,
+    hook: impl FnOnce(
+        &mut Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>,
+    ) -> Result<(), InterceptorError>,
+) -> Result<(), InterceptorError> {
+    match interceptor.hook_timeout().or(default_timeout) {
+        Some(timeout) => enforce_hook_timeout(interceptor, timeout, hook),
+        None => hook(interceptor),
+    }
+}
+
+/// Run `hook` on `target`, failing with [`InterceptorError::hook_timeout`] once it returns if it
+/// took longer than `timeout`
+fn enforce_hook_timeout<T: ?Sized>(
+    target: &mut T,
+    timeout: Duration,
+    hook: impl FnOnce(&mut T) -> Result<(), InterceptorError>,
+) -> Result<(), InterceptorError> {
+    let start = Instant::now();
+    hook(target)?;
+    if start.elapsed() > timeout {
+        return Err(InterceptorError::hook_timeout(timeout));
+    }
+    Ok(())
+}
+
+/// Returns `true` if `interceptor` implements `hook` and supports the current `protocol`
+fn should_run<ModReq, TxReq, TxRes, ModRes>(
+    interceptor: &dyn Interceptor<ModReq, TxReq, TxRes, ModRes>,
+    hook: InterceptorHookSet,
+    protocol: Option<ProtocolId>,
+) -> bool {
+    let supports_protocol = match protocol {
+        Some(protocol) => interceptor.supports_protocol(&protocol),
+        // Without a known protocol, every interceptor runs
+        None => true,
+    };
+    interceptor.hooks().contains(hook) && supports_protocol
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        HookPhase, Interceptor, InterceptorContext, InterceptorError, InterceptorHookSet,
+        InterceptorKind, Interceptors,
+    };
+    use crate::config_bag::ConfigBag;
+    use crate::protocol::ProtocolId;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    type Calls = Arc<Mutex<Vec<&'static str>>>;
+
+    struct NamedInterceptor {
+        name: &'static str,
+        calls: Calls,
+    }
+
+    impl NamedInterceptor {
+        fn new(name: &'static str, calls: &Calls) -> Self {
+            Self {
+                name,
+                calls: calls.clone(),
+            }
+        }
+    }
+
+    impl Interceptor<(), (), (), ()> for NamedInterceptor {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn read_before_serialization(
+            &mut self,
+            _context: &InterceptorContext<(), (), (), ()>,
+            _cfg: &mut ConfigBag,
+        ) -> Result<(), InterceptorError> {
+            self.calls.lock().unwrap().push(self.name);
+            Ok(())
+        }
+
+        fn read_after_execution(
+            &mut self,
+            _context: &InterceptorContext<(), (), (), ()>,
+            _cfg: &mut ConfigBag,
+        ) -> Result<(), InterceptorError> {
+            self.calls.lock().unwrap().push(self.name);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn retain_removes_interceptors_by_name() {
+        let calls = Calls::default();
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_client_interceptor(NamedInterceptor::new("auth", &calls))
+            .with_client_interceptor(NamedInterceptor::new("logging", &calls))
+            .with_operation_interceptor(NamedInterceptor::new("auth", &calls))
+            .with_operation_interceptor(NamedInterceptor::new("checksum", &calls));
+
+        interceptors
+            .retain_client_interceptors(|i| i.name() != "auth")
+            .retain_operation_interceptors(|i| i.name() != "auth");
+
+        let ctx = InterceptorContext::new(());
+        let mut cfg = ConfigBag::base();
+        interceptors
+            .read_before_serialization(&ctx, &mut cfg)
+            .unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["logging", "checksum"]);
+    }
+
+    #[test]
+    fn swap_replaces_interceptors_in_place() {
+        let calls = Calls::default();
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_client_interceptor(NamedInterceptor::new("auth", &calls))
+            .with_client_interceptor(NamedInterceptor::new("logging", &calls))
+            .with_operation_interceptor(NamedInterceptor::new("checksum", &calls));
+
+        let replaced = interceptors
+            .swap_client_interceptor("auth", NamedInterceptor::new("mock-auth", &calls))
+            .unwrap();
+        assert_eq!(replaced.name(), "auth");
+        assert!(interceptors
+            .swap_operation_interceptor("auth", NamedInterceptor::new("unused", &calls))
+            .is_none());
+
+        let ctx = InterceptorContext::new(());
+        let mut cfg = ConfigBag::base();
+        interceptors
+            .read_before_serialization(&ctx, &mut cfg)
+            .unwrap();
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec!["mock-auth", "logging", "checksum"]
+        );
+    }
+
+    #[test]
+    fn conditional_interceptors_check_the_config_bag() {
+        #[derive(Debug)]
+        struct DebugMode;
+
+        let calls = Calls::default();
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_conditional_client_interceptor(
+                |cfg| cfg.contains::<DebugMode>(),
+                NamedInterceptor::new("body-logger", &calls),
+            )
+            .with_client_interceptor(NamedInterceptor::new("always", &calls));
+
+        let ctx = InterceptorContext::new(());
+        let mut cfg = ConfigBag::base();
+        interceptors
+            .read_before_serialization(&ctx, &mut cfg)
+            .unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["always"]);
+
+        calls.lock().unwrap().clear();
+        cfg.put(DebugMode);
+        interceptors
+            .read_before_serialization(&ctx, &mut cfg)
+            .unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["body-logger", "always"]);
+    }
+
+    #[test]
+    fn interceptor_predicates_gate_every_hook() {
+        let calls = Calls::default();
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_client_interceptor(NamedInterceptor::new("auth", &calls))
+            .with_client_interceptor(NamedInterceptor::new("logging", &calls))
+            .with_operation_interceptor(NamedInterceptor::new("auth", &calls));
+        interceptors.with_interceptor_predicate("auth", |ctx, _cfg| ctx.attempt_number() > 1);
+
+        let mut ctx = InterceptorContext::new(());
+        let mut cfg = ConfigBag::base();
+        ctx.with_attempt(1, |ctx| {
+            interceptors.read_before_serialization(ctx, &mut cfg)
+        })
+        .unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["logging"]);
+
+        calls.lock().unwrap().clear();
+        ctx.with_attempt(2, |ctx| {
+            interceptors.read_before_serialization(ctx, &mut cfg)
+        })
+        .unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["auth", "logging", "auth"]);
+    }
+
+    #[test]
+    fn client_interceptors_named() {
+        let calls = Calls::default();
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_client_interceptor(NamedInterceptor::new("logging", &calls))
+            .with_client_interceptor(NamedInterceptor::new("auth", &calls))
+            .with_client_interceptor(NamedInterceptor::new("logging", &calls))
+            .with_operation_interceptor(NamedInterceptor::new("checksum", &calls));
+
+        assert_eq!(interceptors.client_interceptors_named("logging").count(), 2);
+        assert!(interceptors.contains_client_interceptor_named("auth"));
+        // Operation interceptors aren't client interceptors
+        assert!(!interceptors.contains_client_interceptor_named("checksum"));
+        assert!(!interceptors.contains_client_interceptor_named("metrics"));
+    }
+
+    #[test]
+    fn debug_lists_interceptor_names() {
+        let calls = Calls::default();
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_client_interceptor(NamedInterceptor::new("auth", &calls))
+            .with_operation_interceptor(NamedInterceptor::new("checksum", &calls));
+
+        assert_eq!(
+            format!("{:?}", interceptors),
+            r#"Interceptors { client_interceptors: ["auth"], operation_interceptors: ["checksum"] }"#
+        );
+    }
+
+    #[test]
+    fn into_parts_round_trips() {
+        let calls = Calls::default();
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_client_interceptor(NamedInterceptor::new("auth", &calls))
+            .with_client_interceptor(NamedInterceptor::new("logging", &calls))
+            .with_operation_interceptor(NamedInterceptor::new("checksum", &calls));
+
+        let (client, operation) = interceptors.into_parts();
+        assert_eq!(client.len(), 2);
+        assert_eq!(operation.len(), 1);
+        let interceptors = Interceptors::from_parts(client, operation);
+        assert_eq!(
+            format!("{:?}", interceptors),
+            r#"Interceptors { client_interceptors: ["auth", "logging"], operation_interceptors: ["checksum"] }"#
+        );
+    }
+
+    #[test]
+    fn drain_client_interceptors_into() {
+        let calls = Calls::default();
+        let mut outer = Interceptors::new();
+        outer
+            .with_client_interceptor(NamedInterceptor::new("auth", &calls))
+            .with_client_interceptor(NamedInterceptor::new("logging", &calls))
+            .with_operation_interceptor(NamedInterceptor::new("checksum", &calls));
+        let mut inner = Interceptors::new();
+        inner.with_client_interceptor(NamedInterceptor::new("retry", &calls));
+
+        outer.drain_client_interceptors_into(&mut inner);
+        assert_eq!(
+            format!("{:?}", outer),
+            r#"Interceptors { client_interceptors: [], operation_interceptors: ["checksum"] }"#
+        );
+        assert_eq!(
+            format!("{:?}", inner),
+            r#"Interceptors { client_interceptors: ["retry", "auth", "logging"], operation_interceptors: [] }"#
+        );
+    }
+
+    #[test]
+    fn run_hook_with_timeout() {
+        let calls = Calls::default();
+        let mut interceptors = Interceptors::new();
+        interceptors.with_client_interceptor(NamedInterceptor::new("auth", &calls));
+        let ctx = InterceptorContext::new(());
+        let mut cfg = ConfigBag::base();
+
+        interceptors
+            .run_hook_with_timeout(
+                |i| i.read_before_serialization(&ctx, &mut cfg),
+                Duration::from_secs(60),
+            )
+            .unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["auth"]);
+
+        let err = interceptors
+            .run_hook_with_timeout(
+                |i| {
+                    std::thread::sleep(Duration::from_millis(20));
+                    i.read_before_serialization(&ctx, &mut cfg)
+                },
+                Duration::from_millis(1),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "an interceptor hook took longer than its timeout of 1ms"
+        );
+        // The hook still ran to completion
+        assert_eq!(*calls.lock().unwrap(), vec!["auth", "auth"]);
+
+        let err = interceptors
+            .run_hook_with_timeout(
+                |_| {
+                    std::thread::sleep(Duration::from_millis(20));
+                    Err(InterceptorError::read_before_serialization("oops"))
+                },
+                Duration::from_millis(1),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "read_before_serialization interceptor encountered an error"
+        );
+    }
+
+    struct SlowInterceptor {
+        hook_timeout: Option<Duration>,
+    }
+
+    impl Interceptor<(), (), (), ()> for SlowInterceptor {
+        fn hook_timeout(&self) -> Option<Duration> {
+            self.hook_timeout
+        }
+
+        fn read_before_serialization(
+            &mut self,
+            _context: &InterceptorContext<(), (), (), ()>,
+            _cfg: &mut ConfigBag,
+        ) -> Result<(), InterceptorError> {
+            std::thread::sleep(Duration::from_millis(20));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn interceptor_timeout_applies_unless_overridden() {
+        let ctx = InterceptorContext::new(());
+        let mut cfg = ConfigBag::base();
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_interceptor_timeout(Duration::from_millis(1))
+            .with_client_interceptor(SlowInterceptor {
+                hook_timeout: Some(Duration::from_secs(60)),
+            });
+        interceptors
+            .read_before_serialization(&ctx, &mut cfg)
+            .unwrap();
+
+        interceptors.with_operation_interceptor(SlowInterceptor { hook_timeout: None });
+        let err = interceptors
+            .read_before_serialization(&ctx, &mut cfg)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "an interceptor hook took longer than its timeout of 1ms"
+        );
+    }
+
+    #[test]
+    fn len_counts_client_and_operation_interceptors() {
+        let calls = Calls::default();
+        let mut interceptors = Interceptors::new();
+        assert!(interceptors.is_empty());
+        assert_eq!(interceptors.len(), 0);
+
+        interceptors
+            .with_client_interceptor(NamedInterceptor::new("auth", &calls))
+            .with_operation_interceptor(NamedInterceptor::new("checksum", &calls));
+        assert!(!interceptors.is_empty());
+        assert_eq!(interceptors.len(), 2);
+    }
+
+    #[test]
+    fn iterate_registered_interceptors() {
+        let calls = Calls::default();
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_client_interceptor(NamedInterceptor::new("auth", &calls))
+            .with_client_interceptor(NamedInterceptor::new("logging", &calls))
+            .with_operation_interceptor(NamedInterceptor::new("checksum", &calls));
+
+        let client: Vec<_> = interceptors
+            .client_interceptors()
+            .map(|i| i.name())
+            .collect();
+        let operation: Vec<_> = interceptors
+            .operation_interceptors()
+            .map(|i| i.name())
+            .collect();
+        assert_eq!(client, vec!["auth", "logging"]);
+        assert_eq!(operation, vec!["checksum"]);
+    }
+
+    struct OptedOutInterceptor {
+        calls: Calls,
+    }
+
+    impl Interceptor<(), (), (), ()> for OptedOutInterceptor {
+        fn hooks(&self) -> InterceptorHookSet {
+            InterceptorHookSet::READ_AFTER_EXECUTION
+        }
+
+        fn read_before_serialization(
+            &mut self,
+            _context: &InterceptorContext<(), (), (), ()>,
+            _cfg: &mut ConfigBag,
+        ) -> Result<(), InterceptorError> {
+            self.calls.lock().unwrap().push("opted_out");
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn insert_interceptors_at_positions() {
+        let calls = Calls::default();
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_client_interceptor(NamedInterceptor::new("auth", &calls))
+            .with_client_interceptor(NamedInterceptor::new("checksum", &calls))
+            .insert_client_interceptor_at(1, NamedInterceptor::new("retry", &calls))
+            .push_front_client_interceptor(NamedInterceptor::new("logging", &calls))
+            .push_front_operation_interceptor(NamedInterceptor::new("signing", &calls))
+            .insert_operation_interceptor_at(1, NamedInterceptor::new("tracing", &calls));
+
+        let client: Vec<_> = interceptors
+            .client_interceptors()
+            .map(|i| i.name())
+            .collect();
+        let operation: Vec<_> = interceptors
+            .operation_interceptors()
+            .map(|i| i.name())
+            .collect();
+        assert_eq!(client, vec!["logging", "auth", "retry", "checksum"]);
+        assert_eq!(operation, vec!["signing", "tracing"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_interceptor_out_of_bounds() {
+        let calls = Calls::default();
+        Interceptors::new().insert_client_interceptor_at(1, NamedInterceptor::new("auth", &calls));
+    }
+
+    #[test]
+    fn build_from_boxed_interceptors() {
+        type Boxed = Box<dyn Interceptor<(), (), (), ()>>;
+        let calls = Calls::default();
+        let boxed = |name| -> Boxed { Box::new(NamedInterceptor::new(name, &calls)) };
+
+        let mut interceptors: Interceptors<(), (), (), ()> =
+            vec![boxed("auth"), boxed("logging")].into_iter().collect();
+        interceptors
+            .extend_with_client_interceptors(vec![boxed("checksum")])
+            .extend_with_operation_interceptors(vec![boxed("signing")]);
+
+        let client: Vec<_> = interceptors
+            .client_interceptors()
+            .map(|i| i.name())
+            .collect();
+        let operation: Vec<_> = interceptors
+            .operation_interceptors()
+            .map(|i| i.name())
+            .collect();
+        assert_eq!(client, vec!["auth", "logging", "checksum"]);
+        assert_eq!(operation, vec!["signing"]);
+        assert_eq!(
+            Interceptors::from_client_interceptors(vec![boxed("auth")])
+                .client_interceptors()
+                .count(),
+            1
+        );
+    }
+
+    struct FailingInterceptor(&'static str);
+
+    impl Interceptor<(), (), (), ()> for FailingInterceptor {
+        fn read_before_execution(
+            &mut self,
+            _context: &InterceptorContext<(), (), (), ()>,
+            _cfg: &mut ConfigBag,
+        ) -> Result<(), InterceptorError> {
+            Err(InterceptorError::read_before_execution(self.0))
+        }
+
+        fn read_after_execution(
+            &mut self,
+            _context: &InterceptorContext<(), (), (), ()>,
+            _cfg: &mut ConfigBag,
+        ) -> Result<(), InterceptorError> {
+            Err(InterceptorError::read_after_execution(self.0))
+        }
+    }
+
+    #[test]
+    fn run_read_before_execution_collects_every_error() {
+        let calls = Calls::default();
+        let mut interceptors = Interceptors::new();
+        let ctx = InterceptorContext::new(());
+        let mut cfg = ConfigBag::base();
+        interceptors.with_client_interceptor(NamedInterceptor::new("logging", &calls));
+        assert!(interceptors
+            .run_read_before_execution(&ctx, &mut cfg)
+            .is_empty());
+
+        interceptors
+            .with_client_interceptor(FailingInterceptor("client"))
+            .with_operation_interceptor(FailingInterceptor("operation"));
+        let errors = interceptors.run_read_before_execution(&ctx, &mut cfg);
+        let sources: Vec<_> = errors
+            .iter()
+            .map(|err| std::error::Error::source(err).unwrap().to_string())
+            .collect();
+        assert_eq!(sources, vec!["client", "operation"]);
+    }
+
+    #[test]
+    fn read_after_execution_runs_every_interceptor() {
+        let calls = Calls::default();
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_client_interceptor(FailingInterceptor("first"))
+            .with_client_interceptor(FailingInterceptor("second"))
+            .with_operation_interceptor(NamedInterceptor::new("third", &calls));
+        let err = interceptors
+            .read_after_execution(&InterceptorContext::new(()), &mut ConfigBag::base())
+            .unwrap_err();
+        assert_eq!(*calls.lock().unwrap(), vec!["third"]);
+        let sources: Vec<_> = err
+            .errors()
+            .iter()
+            .map(|err| std::error::Error::source(err).unwrap().to_string())
+            .collect();
+        assert_eq!(sources, vec!["first", "second"]);
+    }
+
+    #[test]
+    fn try_run_hook_dispatches_by_phase() {
+        let calls = Calls::default();
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_client_interceptor(NamedInterceptor::new("logging", &calls))
+            .with_client_interceptor(FailingInterceptor("first"))
+            .with_operation_interceptor(FailingInterceptor("second"));
+        let mut ctx = InterceptorContext::new(());
+        let mut cfg = ConfigBag::base();
+
+        interceptors
+            .try_run_hook(HookPhase::ReadBeforeSerialization, &mut ctx, &mut cfg)
+            .unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["logging"]);
+
+        for phase in [
+            HookPhase::ReadBeforeExecution,
+            HookPhase::ReadAfterExecution,
+        ] {
+            let errors = interceptors
+                .try_run_hook(phase, &mut ctx, &mut cfg)
+                .unwrap_err();
+            let sources: Vec<_> = errors
+                .iter()
+                .map(|err| std::error::Error::source(err).unwrap().to_string())
+                .collect();
+            assert_eq!(sources, vec!["first", "second"]);
+        }
+    }
+
+    #[test]
+    fn debug_execution_plan() {
+        let calls = Calls::default();
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_interceptor_timeout(Duration::from_secs(1))
+            .with_client_interceptor(NamedInterceptor::new("logging", &calls))
+            .with_operation_interceptor(SlowInterceptor {
+                hook_timeout: Some(Duration::from_secs(5)),
+            })
+            .with_operation_interceptor(OptedOutInterceptor {
+                calls: calls.clone(),
+            });
+
+        let plan = interceptors.debug_execution_plan();
+        assert_eq!(plan.steps().len(), HookPhase::ALL.len());
+        let step = plan.step(HookPhase::ReadBeforeSerialization).unwrap();
+        let names: Vec<_> = step.interceptors().iter().map(|i| i.name()).collect();
+        assert_eq!(names[0], "logging");
+        assert_eq!(step.interceptors()[0].kind(), InterceptorKind::Client);
+        assert_eq!(
+            step.interceptors()[1].timeout(),
+            Some(Duration::from_secs(5))
+        );
+        // The opted-out interceptor doesn't implement this hook
+        assert_eq!(names.len(), 2);
+        // Nothing runs yet
+        assert!(calls.lock().unwrap().is_empty());
+        assert!(plan
+            .to_string()
+            .starts_with("read_before_execution: logging (client, timeout 1s), "));
+    }
+
+    struct DoubleDelayInterceptor;
+
+    impl Interceptor<(), (), (), ()> for DoubleDelayInterceptor {
+        fn hooks(&self) -> InterceptorHookSet {
+            InterceptorHookSet::MODIFY_BEFORE_RETRY
+        }
+
+        fn modify_before_retry(
+            &mut self,
+            _context: &mut InterceptorContext<(), (), (), ()>,
+            _cfg: &mut ConfigBag,
+            delay: &mut Duration,
+        ) -> Result<(), InterceptorError> {
+            *delay *= 2;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn modify_before_retry_can_override_the_delay() {
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_client_interceptor(DoubleDelayInterceptor)
+            .with_operation_interceptor(DoubleDelayInterceptor);
+
+        let mut ctx = InterceptorContext::new(());
+        let mut delay = Duration::from_millis(100);
+        interceptors
+            .modify_before_retry(&mut ctx, &mut ConfigBag::base(), &mut delay)
+            .unwrap();
+        assert_eq!(delay, Duration::from_millis(400));
+    }
+
+    #[test]
+    fn factories_produce_interceptors_for_each_execution() {
+        #[derive(Debug)]
+        struct InterceptorName(&'static str);
+
+        let calls = Calls::default();
+        let factory_calls = calls.clone();
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_client_interceptor_factory(move |cfg| {
+                let name = cfg.get::<InterceptorName>().unwrap().0;
+                Box::new(NamedInterceptor::new(name, &factory_calls))
+            })
+            .with_client_interceptor(NamedInterceptor::new("logging", &calls));
+        let ctx = InterceptorContext::new(());
+
+        for name in ["first", "second"] {
+            let mut cfg = ConfigBag::base();
+            cfg.put(InterceptorName(name));
+            interceptors.instantiate_interceptor_factories(&cfg);
+            interceptors
+                .read_before_serialization(&ctx, &mut cfg)
+                .unwrap();
+        }
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec!["logging", "first", "logging", "second"]
+        );
+    }
+
+    #[tokio::test]
+    async fn deferred_interceptors_are_resolved_in_order() {
+        let calls = Calls::default();
+        let mut interceptors = Interceptors::new();
+        let (slow_calls, fast_calls) = (calls.clone(), calls.clone());
+        interceptors
+            .with_deferred_client_interceptor(async move {
+                tokio::task::yield_now().await;
+                Box::new(NamedInterceptor::new("slow", &slow_calls))
+                    as Box<dyn Interceptor<_, _, _, _>>
+            })
+            .with_deferred_client_interceptor(async move {
+                Box::new(NamedInterceptor::new("fast", &fast_calls))
+                    as Box<dyn Interceptor<_, _, _, _>>
+            })
+            .with_client_interceptor(NamedInterceptor::new("eager", &calls));
+        assert_eq!(interceptors.len(), 1);
+
+        interceptors.resolve_deferred_interceptors().await;
+        interceptors.resolve_deferred_interceptors().await;
+        assert_eq!(interceptors.len(), 3);
+        interceptors
+            .read_before_serialization(&InterceptorContext::new(()), &mut ConfigBag::base())
+            .unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["eager", "slow", "fast"]);
+    }
+
+    struct TransportErrorRecorder(Arc<Mutex<Vec<String>>>);
+
+    impl Interceptor<(), (), (), ()> for TransportErrorRecorder {
+        fn hooks(&self) -> InterceptorHookSet {
+            InterceptorHookSet::READ_ON_TRANSPORT_ERROR
+        }
+
+        fn read_on_transport_error(
+            &mut self,
+            _context: &InterceptorContext<(), (), (), ()>,
+            _cfg: &mut ConfigBag,
+            error: &(dyn std::error::Error + Send + Sync + 'static),
+        ) -> Result<(), InterceptorError> {
+            self.0.lock().unwrap().push(error.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn read_on_transport_error_sees_the_transport_error() {
+        let errors = Arc::default();
+        let mut interceptors = Interceptors::new();
+        interceptors.with_client_interceptor(TransportErrorRecorder(Arc::clone(&errors)));
+
+        let ctx = InterceptorContext::new(());
+        let error = InterceptorError::transport("connection refused");
+        interceptors
+            .read_on_transport_error(&ctx, &mut ConfigBag::base(), &error)
+            .unwrap();
+        assert_eq!(*errors.lock().unwrap(), vec!["failed to send the request"]);
+    }
+
+    struct SleepRecorder(Arc<Mutex<Vec<Duration>>>);
+
+    impl Interceptor<(), (), (), ()> for SleepRecorder {
+        fn hooks(&self) -> InterceptorHookSet {
+            InterceptorHookSet::READ_BETWEEN_ATTEMPTS
+        }
+
+        fn read_between_attempts(
+            &mut self,
+            _context: &InterceptorContext<(), (), (), ()>,
+            _cfg: &mut ConfigBag,
+            actual_sleep: Duration,
+        ) -> Result<(), InterceptorError> {
+            self.0.lock().unwrap().push(actual_sleep);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn read_between_attempts_sees_the_actual_sleep() {
+        let sleeps = Arc::default();
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_client_interceptor(SleepRecorder(Arc::clone(&sleeps)))
+            .with_operation_interceptor(SleepRecorder(Arc::clone(&sleeps)));
+
+        let ctx = InterceptorContext::new(());
+        interceptors
+            .read_between_attempts(&ctx, &mut ConfigBag::base(), Duration::from_millis(120))
+            .unwrap();
+        assert_eq!(
+            *sleeps.lock().unwrap(),
+            vec![Duration::from_millis(120), Duration::from_millis(120)]
+        );
+    }
+
+    struct RestXmlOnlyInterceptor {
+        calls: Calls,
+    }
+
+    impl Interceptor<(), (), (), ()> for RestXmlOnlyInterceptor {
+        fn supports_protocol(&self, protocol: &ProtocolId) -> bool {
+            *protocol == ProtocolId::REST_XML
+        }
+
+        fn read_before_serialization(
+            &mut self,
+            _context: &InterceptorContext<(), (), (), ()>,
+            _cfg: &mut ConfigBag,
+        ) -> Result<(), InterceptorError> {
+            self.calls.lock().unwrap().push("rest_xml_only");
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn interceptors_for_other_protocols_are_skipped() {
+        let calls = Calls::default();
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_client_interceptor(RestXmlOnlyInterceptor {
+                calls: calls.clone(),
+            })
+            .with_client_interceptor(NamedInterceptor::new("logging", &calls));
+
+        let mut cfg = ConfigBag::base();
+        let mut ctx = InterceptorContext::new(());
+        // Without a known protocol, every interceptor runs
+        interceptors
+            .read_before_serialization(&ctx, &mut cfg)
+            .unwrap();
+        ctx.set_protocol(ProtocolId::REST_JSON_1);
+        interceptors
+            .read_before_serialization(&ctx, &mut cfg)
+            .unwrap();
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec!["rest_xml_only", "logging", "logging"]
+        );
+    }
+
+    #[test]
+    fn partition_by_phase() {
+        let calls = Calls::default();
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_client_interceptor(OptedOutInterceptor {
+                calls: calls.clone(),
+            })
+            .with_client_interceptor(NamedInterceptor::new("logging", &calls))
+            .with_operation_interceptor(OptedOutInterceptor {
+                calls: calls.clone(),
+            });
+
+        let (client, operation) =
+            interceptors.partition_by_phase(InterceptorHookSet::READ_BEFORE_SERIALIZATION);
+        assert_eq!(
+            client.map(|i| i.name()).collect::<Vec<_>>(),
+            vec!["logging"]
+        );
+        assert_eq!(operation.count(), 0);
+
+        let (client, operation) =
+            interceptors.partition_by_phase(InterceptorHookSet::READ_AFTER_EXECUTION);
+        assert_eq!(client.count(), 2);
+        assert_eq!(operation.count(), 1);
+    }
+
+    #[test]
+    fn hooks_outside_the_hook_set_are_skipped() {
+        let calls = Calls::default();
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_client_interceptor(OptedOutInterceptor {
+                calls: calls.clone(),
+            })
+            .with_client_interceptor(NamedInterceptor::new("logging", &calls));
+
+        let ctx = InterceptorContext::new(());
+        let mut cfg = ConfigBag::base();
+        interceptors
+            .read_before_serialization(&ctx, &mut cfg)
+            .unwrap();
+        // `OptedOutInterceptor` overrides `read_before_serialization` without declaring it, so
+        // its implementation is never called
+        assert_eq!(*calls.lock().unwrap(), vec!["logging"]);
+    }
+
+    macro_rules! record_hooks {
+        ($($hook:ident($($mutability:tt)?)),+ $(,)?) => {
+            $(
+                fn $hook(
+                    &mut self,
+                    _context: &$($mutability)? InterceptorContext<(), (), (), ()>,
+                    _cfg: &mut ConfigBag,
+                ) -> Result<(), InterceptorError> {
+                    self.calls.lock().unwrap().push(stringify!($hook));
+                    Ok(())
+                }
+            )+
+        };
+    }
+
+    struct AttemptHookRecorder {
+        calls: Calls,
+    }
+
+    impl Interceptor<(), (), (), ()> for AttemptHookRecorder {
+        record_hooks!(
+            read_before_attempt(),
+            modify_before_signing(mut),
+            read_before_signing(),
+            read_after_signing(),
+            modify_before_transmit(mut),
+            read_before_transmit(),
+            read_after_transmit(),
+            modify_before_deserialization(mut),
+            read_before_deserialization(),
+            read_after_deserialization(),
+            modify_before_attempt_completion(mut),
+            read_after_attempt(),
+        );
+    }
+
+    #[test]
+    fn orchestrate_attempt_runs_hooks_in_order() {
+        let calls = Calls::default();
+        let mut interceptors = Interceptors::new();
+        interceptors.with_client_interceptor(AttemptHookRecorder {
+            calls: calls.clone(),
+        });
+
+        let mut ctx = InterceptorContext::new(());
+        ctx.set_tx_request(());
+        let mut cfg = ConfigBag::base();
+        interceptors
+            .orchestrate_attempt(
+                &mut ctx,
+                &mut cfg,
+                |_req, _cfg| {
+                    calls.lock().unwrap().push("send");
+                    Ok(())
+                },
+                |_res, _cfg| {
+                    calls.lock().unwrap().push("deserialize");
+                    Ok(())
+                },
+            )
+            .unwrap();
+
+        assert_eq!(ctx.attempt_number(), 1);
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                "read_before_attempt",
+                "modify_before_signing",
+                "read_before_signing",
+                "read_after_signing",
+                "modify_before_transmit",
+                "read_before_transmit",
+                "send",
+                "read_after_transmit",
+                "modify_before_deserialization",
+                "read_before_deserialization",
+                "deserialize",
+                "read_after_deserialization",
+                "modify_before_attempt_completion",
+                "read_after_attempt",
+            ]
+        );
+    }
+
+    #[test]
+    fn orchestrate_attempt_can_be_retried_with_the_same_context() {
+        let mut interceptors = Interceptors::<(), (), u16, u16>::new();
+        let mut ctx = InterceptorContext::new(());
+        ctx.set_tx_request(());
+        let mut cfg = ConfigBag::base();
+        let mut statuses = vec![503, 200].into_iter();
+
+        for _ in 0..2 {
+            let status = statuses.next().unwrap();
+            interceptors
+                .orchestrate_attempt(
+                    &mut ctx,
+                    &mut cfg,
+                    |_req, _cfg| Ok(status),
+                    |res, _cfg| Ok(*res),
+                )
+                .unwrap();
+        }
+
+        assert_eq!(ctx.attempt_number(), 2);
+        assert_eq!(ctx.tx_response().ok(), Some(&200));
+        assert_eq!(ctx.modeled_response().ok(), Some(&200));
     }
 }
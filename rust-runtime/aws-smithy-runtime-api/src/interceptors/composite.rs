@@ -0,0 +1,255 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An interceptor made up of other interceptors
+
+use super::{should_run, Interceptor, InterceptorContext, InterceptorError, InterceptorHookSet};
+use crate::config_bag::ConfigBag;
+use crate::protocol::ProtocolId;
+use std::time::Duration;
+
+/// Run every interceptor, collecting all errors
+fn run_all(
+    results: impl Iterator<Item = Result<(), InterceptorError>>,
+) -> Result<(), InterceptorError> {
+    InterceptorError::collect(results)
+}
+
+/// Run interceptors until one of them fails
+fn run_until_error(
+    mut results: impl Iterator<Item = Result<(), InterceptorError>>,
+) -> Result<(), InterceptorError> {
+    results.try_for_each(|result| result)
+}
+
+macro_rules! composite_hooks {
+    ($($strategy:ident $hook:ident($context:ty) => $flag:ident;)+) => {
+        $(
+            fn $hook(
+                &mut self,
+                context: $context,
+                cfg: &mut ConfigBag,
+            ) -> Result<(), InterceptorError> {
+                let protocol = context.protocol();
+                $strategy(
+                    self.interceptors
+                        .iter_mut()
+                        .filter(|i| should_run(i.as_ref(), InterceptorHookSet::$flag, protocol))
+                        .map(|i| i.$hook(context, cfg)),
+                )
+            }
+        )+
+    };
+}
+
+/// An interceptor that runs a list of interceptors in order.
+///
+/// The `read_before_execution`, `read_before_attempt`, `read_after_attempt`, and
+/// `read_after_execution` hooks are run for every interceptor even if some of them fail, as
+/// required by the Smithy interceptor specification. When more than one interceptor fails, their
+/// errors are returned together as an [aggregated](InterceptorError::aggregate) error. All other
+/// hooks stop at the first interceptor that fails.
+pub struct CompositeInterceptor<ModReq, TxReq, TxRes, ModRes> {
+    interceptors: Vec<Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>>,
+}
+
+impl<ModReq, TxReq, TxRes, ModRes> Default for CompositeInterceptor<ModReq, TxReq, TxRes, ModRes> {
+    fn default() -> Self {
+        Self {
+            interceptors: Vec::new(),
+        }
+    }
+}
+
+impl<ModReq, TxReq, TxRes, ModRes> CompositeInterceptor<ModReq, TxReq, TxRes, ModRes> {
+    /// Create a new, empty `CompositeInterceptor`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an interceptor to the end of the list.
+    pub fn with_interceptor(
+        mut self,
+        interceptor: impl Interceptor<ModReq, TxReq, TxRes, ModRes> + 'static,
+    ) -> Self {
+        self.interceptors.push(Box::new(interceptor));
+        self
+    }
+}
+
+impl<ModReq, TxReq, TxRes, ModRes> Interceptor<ModReq, TxReq, TxRes, ModRes>
+    for CompositeInterceptor<ModReq, TxReq, TxRes, ModRes>
+{
+    fn hooks(&self) -> InterceptorHookSet {
+        self.interceptors
+            .iter()
+            .fold(InterceptorHookSet::empty(), |hooks, i| hooks | i.hooks())
+    }
+
+    fn supports_protocol(&self, protocol: &ProtocolId) -> bool {
+        self.interceptors
+            .iter()
+            .any(|i| i.supports_protocol(protocol))
+    }
+
+    fn modify_before_retry(
+        &mut self,
+        context: &mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+        delay: &mut Duration,
+    ) -> Result<(), InterceptorError> {
+        let protocol = context.protocol();
+        run_until_error(
+            self.interceptors
+                .iter_mut()
+                .filter(|i| {
+                    should_run(
+                        i.as_ref(),
+                        InterceptorHookSet::MODIFY_BEFORE_RETRY,
+                        protocol,
+                    )
+                })
+                .map(|i| i.modify_before_retry(context, cfg, delay)),
+        )
+    }
+
+    fn read_between_attempts(
+        &mut self,
+        context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+        actual_sleep: Duration,
+    ) -> Result<(), InterceptorError> {
+        let protocol = context.protocol();
+        run_all(
+            self.interceptors
+                .iter_mut()
+                .filter(|i| {
+                    should_run(
+                        i.as_ref(),
+                        InterceptorHookSet::READ_BETWEEN_ATTEMPTS,
+                        protocol,
+                    )
+                })
+                .map(|i| i.read_between_attempts(context, cfg, actual_sleep)),
+        )
+    }
+
+    fn read_on_transport_error(
+        &mut self,
+        context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+        error: &(dyn std::error::Error + Send + Sync + 'static),
+    ) -> Result<(), InterceptorError> {
+        let protocol = context.protocol();
+        run_all(
+            self.interceptors
+                .iter_mut()
+                .filter(|i| {
+                    should_run(
+                        i.as_ref(),
+                        InterceptorHookSet::READ_ON_TRANSPORT_ERROR,
+                        protocol,
+                    )
+                })
+                .map(|i| i.read_on_transport_error(context, cfg, error)),
+        )
+    }
+
+    composite_hooks! {
+        run_all read_before_execution(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>) => READ_BEFORE_EXECUTION;
+        run_until_error modify_before_serialization(&mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>) => MODIFY_BEFORE_SERIALIZATION;
+        run_until_error read_before_serialization(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>) => READ_BEFORE_SERIALIZATION;
+        run_until_error read_after_serialization(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>) => READ_AFTER_SERIALIZATION;
+        run_until_error modify_before_retry_loop(&mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>) => MODIFY_BEFORE_RETRY_LOOP;
+        run_all read_before_attempt(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>) => READ_BEFORE_ATTEMPT;
+        run_until_error modify_before_signing(&mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>) => MODIFY_BEFORE_SIGNING;
+        run_until_error read_before_signing(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>) => READ_BEFORE_SIGNING;
+        run_until_error read_after_signing(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>) => READ_AFTER_SIGNING;
+        run_until_error modify_before_transmit(&mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>) => MODIFY_BEFORE_TRANSMIT;
+        run_until_error read_before_transmit(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>) => READ_BEFORE_TRANSMIT;
+        run_until_error read_after_transmit(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>) => READ_AFTER_TRANSMIT;
+        run_until_error modify_before_deserialization(&mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>) => MODIFY_BEFORE_DESERIALIZATION;
+        run_until_error read_before_deserialization(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>) => READ_BEFORE_DESERIALIZATION;
+        run_until_error read_after_deserialization(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>) => READ_AFTER_DESERIALIZATION;
+        run_until_error modify_before_attempt_completion(&mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>) => MODIFY_BEFORE_ATTEMPT_COMPLETION;
+        run_all read_after_attempt(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>) => READ_AFTER_ATTEMPT;
+        run_until_error modify_before_completion(&mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>) => MODIFY_BEFORE_COMPLETION;
+        run_all read_after_execution(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>) => READ_AFTER_EXECUTION;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CompositeInterceptor;
+    use crate::config_bag::ConfigBag;
+    use crate::interceptors::{Interceptor, InterceptorContext, InterceptorError};
+    use std::sync::{Arc, Mutex};
+
+    type Calls = Arc<Mutex<Vec<&'static str>>>;
+
+    struct FailingInterceptor {
+        name: &'static str,
+        calls: Calls,
+    }
+
+    impl Interceptor<(), (), (), ()> for FailingInterceptor {
+        fn read_before_serialization(
+            &mut self,
+            _context: &InterceptorContext<(), (), (), ()>,
+            _cfg: &mut ConfigBag,
+        ) -> Result<(), InterceptorError> {
+            self.calls.lock().unwrap().push(self.name);
+            Err(InterceptorError::read_before_serialization(self.name))
+        }
+
+        fn read_after_execution(
+            &mut self,
+            _context: &InterceptorContext<(), (), (), ()>,
+            _cfg: &mut ConfigBag,
+        ) -> Result<(), InterceptorError> {
+            self.calls.lock().unwrap().push(self.name);
+            Err(InterceptorError::read_after_execution(self.name))
+        }
+    }
+
+    fn composite(calls: &Calls) -> CompositeInterceptor<(), (), (), ()> {
+        CompositeInterceptor::new()
+            .with_interceptor(FailingInterceptor {
+                name: "first",
+                calls: calls.clone(),
+            })
+            .with_interceptor(FailingInterceptor {
+                name: "second",
+                calls: calls.clone(),
+            })
+    }
+
+    #[test]
+    fn accumulating_hooks_run_every_interceptor() {
+        let calls = Calls::default();
+        let ctx = InterceptorContext::new(());
+        let mut cfg = ConfigBag::base();
+
+        let err = composite(&calls)
+            .read_after_execution(&ctx, &mut cfg)
+            .unwrap_err();
+        assert_eq!(*calls.lock().unwrap(), vec!["first", "second"]);
+        assert_eq!(err.to_string(), "2 interceptors encountered errors");
+        assert_eq!(err.errors().len(), 2);
+    }
+
+    #[test]
+    fn other_hooks_stop_at_the_first_error() {
+        let calls = Calls::default();
+        let ctx = InterceptorContext::new(());
+        let mut cfg = ConfigBag::base();
+
+        let err = composite(&calls)
+            .read_before_serialization(&ctx, &mut cfg)
+            .unwrap_err();
+        assert_eq!(*calls.lock().unwrap(), vec!["first"]);
+        assert!(err.errors().is_empty());
+    }
+}
@@ -0,0 +1,162 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An interceptor that only runs when a runtime condition holds
+
+use super::{Interceptor, InterceptorContext, InterceptorError, InterceptorHookSet};
+use crate::config_bag::ConfigBag;
+use crate::protocol::ProtocolId;
+use std::sync::Arc;
+use std::time::Duration;
+
+macro_rules! conditional_hooks {
+    ($($hook:ident($context:ty);)+) => {
+        $(
+            fn $hook(
+                &mut self,
+                context: $context,
+                cfg: &mut ConfigBag,
+            ) -> Result<(), InterceptorError> {
+                if (self.condition)(context, cfg) {
+                    self.interceptor.$hook(context, cfg)
+                } else {
+                    Ok(())
+                }
+            }
+        )+
+    };
+}
+
+/// A predicate deciding whether a [`ConditionalInterceptor`] runs its hooks
+pub type InterceptorPredicate<ModReq, TxReq, TxRes, ModRes> = Arc<
+    dyn Fn(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>, &ConfigBag) -> bool + Send + Sync,
+>;
+
+/// An interceptor that wraps another interceptor, only running it when a condition holds.
+///
+/// The condition is evaluated against the current [`ConfigBag`] before every hook, so it can
+/// inspect feature flags or other settings stored there, e.g. to only log request bodies in debug
+/// mode. Conditions created with [`ConditionalInterceptor::with_predicate`] can also inspect the
+/// [`InterceptorContext`].
+pub struct ConditionalInterceptor<ModReq, TxReq, TxRes, ModRes> {
+    condition: InterceptorPredicate<ModReq, TxReq, TxRes, ModRes>,
+    interceptor: Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>,
+}
+
+impl<ModReq, TxReq, TxRes, ModRes> ConditionalInterceptor<ModReq, TxReq, TxRes, ModRes> {
+    /// Create a new `ConditionalInterceptor` that runs `interceptor` when `condition` returns
+    /// `true`.
+    pub fn new(
+        condition: impl Fn(&ConfigBag) -> bool + Send + Sync + 'static,
+        interceptor: impl Interceptor<ModReq, TxReq, TxRes, ModRes> + 'static,
+    ) -> Self {
+        Self::with_predicate(move |_context, cfg| condition(cfg), interceptor)
+    }
+
+    /// Create a new `ConditionalInterceptor` that runs `interceptor` when `predicate` returns
+    /// `true` for the current context and [`ConfigBag`].
+    pub fn with_predicate(
+        predicate: impl Fn(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>, &ConfigBag) -> bool
+            + Send
+            + Sync
+            + 'static,
+        interceptor: impl Interceptor<ModReq, TxReq, TxRes, ModRes> + 'static,
+    ) -> Self {
+        Self::from_parts(Arc::new(predicate), Box::new(interceptor))
+    }
+
+    pub(crate) fn from_parts(
+        condition: InterceptorPredicate<ModReq, TxReq, TxRes, ModRes>,
+        interceptor: Box<dyn Interceptor<ModReq, TxReq, TxRes, ModRes>>,
+    ) -> Self {
+        Self {
+            condition,
+            interceptor,
+        }
+    }
+}
+
+impl<ModReq, TxReq, TxRes, ModRes> Interceptor<ModReq, TxReq, TxRes, ModRes>
+    for ConditionalInterceptor<ModReq, TxReq, TxRes, ModRes>
+{
+    fn name(&self) -> &'static str {
+        self.interceptor.name()
+    }
+
+    fn hooks(&self) -> InterceptorHookSet {
+        self.interceptor.hooks()
+    }
+
+    fn supports_protocol(&self, protocol: &ProtocolId) -> bool {
+        self.interceptor.supports_protocol(protocol)
+    }
+
+    fn hook_timeout(&self) -> Option<Duration> {
+        self.interceptor.hook_timeout()
+    }
+
+    fn modify_before_retry(
+        &mut self,
+        context: &mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+        delay: &mut Duration,
+    ) -> Result<(), InterceptorError> {
+        if (self.condition)(context, cfg) {
+            self.interceptor.modify_before_retry(context, cfg, delay)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn read_between_attempts(
+        &mut self,
+        context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+        actual_sleep: Duration,
+    ) -> Result<(), InterceptorError> {
+        if (self.condition)(context, cfg) {
+            self.interceptor
+                .read_between_attempts(context, cfg, actual_sleep)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn read_on_transport_error(
+        &mut self,
+        context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+        error: &(dyn std::error::Error + Send + Sync + 'static),
+    ) -> Result<(), InterceptorError> {
+        if (self.condition)(context, cfg) {
+            self.interceptor
+                .read_on_transport_error(context, cfg, error)
+        } else {
+            Ok(())
+        }
+    }
+
+    conditional_hooks! {
+        read_before_execution(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>);
+        modify_before_serialization(&mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>);
+        read_before_serialization(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>);
+        read_after_serialization(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>);
+        modify_before_retry_loop(&mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>);
+        read_before_attempt(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>);
+        modify_before_signing(&mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>);
+        read_before_signing(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>);
+        read_after_signing(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>);
+        modify_before_transmit(&mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>);
+        read_before_transmit(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>);
+        read_after_transmit(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>);
+        modify_before_deserialization(&mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>);
+        read_before_deserialization(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>);
+        read_after_deserialization(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>);
+        modify_before_attempt_completion(&mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>);
+        read_after_attempt(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>);
+        modify_before_completion(&mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>);
+        read_after_execution(&InterceptorContext<ModReq, TxReq, TxRes, ModRes>);
+    }
+}
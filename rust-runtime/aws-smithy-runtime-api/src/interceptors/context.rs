@@ -3,14 +3,410 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
-use super::InterceptorError;
+use super::error::BoxError;
+use super::{ErrorKind, InterceptorError};
+use crate::config_bag::ConfigBag;
+use crate::protocol::ProtocolId;
+use http::{HeaderMap, Uri};
+use std::any::{Any, TypeId};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// The version of the smithy-rs runtime, taken from this crate's version at compile time
+const SDK_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Values attached to a context, keyed by the `TypeId` of a key type
+type TypeMap = HashMap<TypeId, Box<dyn Any + Send + Sync>>;
+
+/// The reason an interceptor [cancelled](InterceptorContext::cancel) an execution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CancellationReason(Cow<'static, str>);
+
+impl CancellationReason {
+    /// Create a new `CancellationReason` with a human-readable description.
+    pub fn new(reason: impl Into<Cow<'static, str>>) -> Self {
+        Self(reason.into())
+    }
+}
+
+impl fmt::Display for CancellationReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// The distributed tracing context of an execution, propagated to the service by interceptors.
+///
+/// The IDs are opaque strings so that both W3C Trace Context and AWS X-Ray formats can be used.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TraceContext {
+    trace_id: String,
+    span_id: String,
+    baggage: HashMap<String, String>,
+}
+
+impl TraceContext {
+    /// Create a new `TraceContext` for the given trace and span.
+    pub fn new(trace_id: impl Into<String>, span_id: impl Into<String>) -> Self {
+        Self {
+            trace_id: trace_id.into(),
+            span_id: span_id.into(),
+            baggage: HashMap::new(),
+        }
+    }
+
+    /// Add a baggage item that will be propagated along with the trace.
+    pub fn with_baggage(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.insert_baggage(key, value);
+        self
+    }
+
+    /// Retrieve the ID of the trace.
+    ///
+    /// This will be empty if there's no trace to propagate.
+    pub fn trace_id(&self) -> &str {
+        &self.trace_id
+    }
+
+    /// Retrieve the ID of the parent span.
+    pub fn span_id(&self) -> &str {
+        &self.span_id
+    }
+
+    /// Set the ID of the parent span, e.g. when an interceptor starts a new span for an attempt.
+    pub fn set_span_id(&mut self, span_id: impl Into<String>) {
+        self.span_id = span_id.into();
+    }
+
+    /// Retrieve the baggage items propagated along with the trace.
+    pub fn baggage(&self) -> &HashMap<String, String> {
+        &self.baggage
+    }
+
+    /// Add a baggage item, replacing any existing item with the same key.
+    pub fn insert_baggage(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.baggage.insert(key.into(), value.into());
+    }
+
+    /// Returns `true` if there's no trace to propagate.
+    pub fn is_empty(&self) -> bool {
+        self.trace_id.is_empty()
+    }
+}
+
+/// The structured details of an error response returned by the service, e.g. an S3
+/// `<Error><Code>SlowDown</Code>...</Error>` body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ErrorDetails {
+    code: String,
+    message: String,
+    request_id: Option<String>,
+}
+
+impl ErrorDetails {
+    /// Create a new `ErrorDetails` with the given error code and message.
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            message: message.into(),
+            request_id: None,
+        }
+    }
+
+    /// Set the ID the service assigned to the failed request.
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Retrieve the error code, e.g. `ThrottlingException`.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Retrieve the human-readable error message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Retrieve the ID the service assigned to the failed request, if the response included one.
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+}
+
+/// Why the runtime decided to retry an attempt, or not to.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RetryReason {
+    /// The attempt succeeded, so there was nothing to retry.
+    Success,
+    /// The attempt failed with an error that can never succeed on retry, so the retry strategy
+    /// wasn't consulted.
+    NotRetryable,
+    /// The retry strategy decided to retry the failed attempt.
+    RetryStrategy,
+    /// The retry strategy decided not to retry the failed attempt, e.g. because it ran out of
+    /// attempts.
+    RetryStrategyDeclined,
+}
+
+/// The runtime's decision on whether to retry an attempt, made once the retry strategy has
+/// judged the attempt's result.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryDecision {
+    should_retry: bool,
+    delay: Option<Duration>,
+    reason: RetryReason,
+}
+
+impl RetryDecision {
+    /// Create a decision to retry after `delay`.
+    pub fn retry(delay: Duration) -> Self {
+        Self {
+            should_retry: true,
+            delay: Some(delay),
+            reason: RetryReason::RetryStrategy,
+        }
+    }
+
+    /// Create a decision not to retry for the given `reason`.
+    pub fn no_retry(reason: RetryReason) -> Self {
+        Self {
+            should_retry: false,
+            delay: None,
+            reason,
+        }
+    }
+
+    /// Returns `true` if the attempt will be retried.
+    pub fn should_retry(&self) -> bool {
+        self.should_retry
+    }
+
+    /// Retrieve how long the runtime will wait before retrying, if the attempt will be retried.
+    ///
+    /// Interceptors may still change the delay in `modify_before_retry`; the time actually spent
+    /// waiting is passed to `read_between_attempts`.
+    pub fn delay(&self) -> Option<Duration> {
+        self.delay
+    }
+
+    /// Retrieve why the runtime decided to retry the attempt, or not to.
+    pub fn reason(&self) -> RetryReason {
+        self.reason
+    }
+}
+
+/// How a single attempt ended.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttemptOutcome {
+    /// The response was deserialized into a successful output.
+    Success,
+    /// The response was deserialized into an error returned by the service.
+    ServiceError,
+    /// The attempt failed with an [`InterceptorError`] of the given kind, e.g. a transport error,
+    /// before a response could be deserialized.
+    Failed(ErrorKind),
+}
+
+/// The record of a single attempt, kept on the [`InterceptorContext`] for the rest of the
+/// execution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AttemptResult {
+    attempt_number: u32,
+    latency: Duration,
+    outcome: AttemptOutcome,
+    retry_decision: RetryDecision,
+}
+
+impl AttemptResult {
+    /// Create a new `AttemptResult`.
+    pub fn new(
+        attempt_number: u32,
+        latency: Duration,
+        outcome: AttemptOutcome,
+        retry_decision: RetryDecision,
+    ) -> Self {
+        Self {
+            attempt_number,
+            latency,
+            outcome,
+            retry_decision,
+        }
+    }
+
+    /// Retrieve the number of the attempt, starting at 1 for the first attempt.
+    pub fn attempt_number(&self) -> u32 {
+        self.attempt_number
+    }
+
+    /// Retrieve how long the attempt took, from its start until its result was judged.
+    pub fn latency(&self) -> Duration {
+        self.latency
+    }
+
+    /// Retrieve how the attempt ended.
+    pub fn outcome(&self) -> AttemptOutcome {
+        self.outcome
+    }
+
+    /// Retrieve the runtime's decision on whether to retry the attempt.
+    pub fn retry_decision(&self) -> &RetryDecision {
+        &self.retry_decision
+    }
+}
+
+/// Signs transport requests for an [`AuthScheme`].
+pub trait Signer<TxReq>: Send + Sync + fmt::Debug {
+    /// Sign `request`, e.g. by adding an `Authorization` header.
+    fn sign_request(&self, request: &mut TxReq, cfg: &ConfigBag) -> Result<(), BoxError>;
+}
+
+/// An auth scheme selected at runtime, e.g. token auth for a service that also accepts SigV4.
+///
+/// Set with [`InterceptorContext::set_auth_scheme`] to override the auth scheme from the config
+/// bag for a single execution.
+pub struct AuthScheme<TxReq> {
+    name: &'static str,
+    signer: Arc<dyn Signer<TxReq>>,
+}
+
+impl<TxReq> AuthScheme<TxReq> {
+    /// Create a new `AuthScheme` named `name` that signs requests with `signer`.
+    pub fn new(name: &'static str, signer: impl Signer<TxReq> + 'static) -> Self {
+        Self {
+            name,
+            signer: Arc::new(signer),
+        }
+    }
+
+    /// The name of the auth scheme, e.g. `sigv4`.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The signer that signs requests for this auth scheme.
+    pub fn signer(&self) -> &Arc<dyn Signer<TxReq>> {
+        &self.signer
+    }
+}
+
+impl<TxReq> Clone for AuthScheme<TxReq> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name,
+            signer: self.signer.clone(),
+        }
+    }
+}
+
+impl<TxReq> fmt::Debug for AuthScheme<TxReq> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthScheme")
+            .field("name", &self.name)
+            .field("signer", &self.signer)
+            .finish()
+    }
+}
+
+/// The name of the service an operation belongs to, e.g. `S3`, stored in the config bag by the
+/// service's runtime plugin.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ServiceName(&'static str);
+
+impl ServiceName {
+    /// Create a new `ServiceName`.
+    pub const fn new(name: &'static str) -> Self {
+        Self(name)
+    }
+
+    /// The name as a string.
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+/// The client-level limit on how long the service may take to respond to a request, stored in
+/// the config bag.
+///
+/// An interceptor can override it for a single execution with
+/// [`InterceptorContext::set_operation_timeout`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OperationTimeout(Duration);
+
+impl OperationTimeout {
+    /// Create a new `OperationTimeout`.
+    pub const fn new(timeout: Duration) -> Self {
+        Self(timeout)
+    }
+
+    /// The timeout as a `Duration`.
+    pub fn as_duration(&self) -> Duration {
+        self.0
+    }
+}
+
+/// The longest a single interceptor hook may run before it fails, stored in the config bag.
+///
+/// The timeout is only checked once a hook returns; see
+/// [`Interceptors::run_hook_with_timeout`](super::Interceptors::run_hook_with_timeout).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InterceptorHookTimeout(Duration);
+
+impl InterceptorHookTimeout {
+    /// Create a new `InterceptorHookTimeout`.
+    pub const fn new(timeout: Duration) -> Self {
+        Self(timeout)
+    }
+
+    /// The timeout as a `Duration`.
+    pub fn as_duration(&self) -> Duration {
+        self.0
+    }
+}
 
 /// A container for the data currently available to an interceptor.
 pub struct InterceptorContext<ModReq, TxReq, TxRes, ModRes> {
+    sdk_version: &'static str,
+    service_name: &'static str,
+    operation_name: &'static str,
+    protocol: Option<ProtocolId>,
+    attempt_number: u32,
     modeled_request: ModReq,
     tx_request: Option<TxReq>,
     modeled_response: Option<ModRes>,
     tx_response: Option<TxRes>,
+    properties: TypeMap,
+    transmit_start: Option<Instant>,
+    transmit_end: Option<Instant>,
+    attempt_start: Option<Instant>,
+    cancellation: Option<CancellationReason>,
+    has_injected_response: bool,
+    service_endpoint: Option<Uri>,
+    request_id: Option<String>,
+    correlation_id: Option<String>,
+    previous_attempt_errors: Vec<InterceptorError>,
+    trace_context: TraceContext,
+    auth_scheme: Option<AuthScheme<TxReq>>,
+    is_streaming_response: bool,
+    error_details: Option<ErrorDetails>,
+    operation_timeout: Option<Duration>,
+    retry_decision: Option<RetryDecision>,
+    attempt_results: Vec<AttemptResult>,
+    request_checksum: Option<Vec<u8>>,
+    response_checksum: Option<Vec<u8>>,
+    expected_response_checksum: Option<Vec<u8>>,
+    body_size_hint: Option<u64>,
+    request_payload_size: Option<u64>,
+    response_payload_size: Option<u64>,
+    canonical_request_string: Option<String>,
+    max_attempts: Option<u32>,
+    custom_attributes: HashMap<String, String>,
 }
 
 // TODO(interceptors) we could use types to ensure that people calling methods on interceptor context can't access
@@ -18,13 +414,207 @@ pub struct InterceptorContext<ModReq, TxReq, TxRes, ModRes> {
 impl<ModReq, TxReq, TxRes, ModRes> InterceptorContext<ModReq, TxReq, TxRes, ModRes> {
     pub fn new(request: ModReq) -> Self {
         Self {
+            sdk_version: SDK_VERSION,
+            service_name: "",
+            operation_name: "",
+            protocol: None,
+            attempt_number: 0,
             modeled_request: request,
             tx_request: None,
             tx_response: None,
             modeled_response: None,
+            properties: TypeMap::new(),
+            transmit_start: None,
+            transmit_end: None,
+            attempt_start: None,
+            cancellation: None,
+            has_injected_response: false,
+            service_endpoint: None,
+            request_id: None,
+            correlation_id: None,
+            previous_attempt_errors: Vec::new(),
+            trace_context: TraceContext::default(),
+            auth_scheme: None,
+            is_streaming_response: false,
+            error_details: None,
+            operation_timeout: None,
+            retry_decision: None,
+            attempt_results: Vec::new(),
+            request_checksum: None,
+            response_checksum: None,
+            expected_response_checksum: None,
+            body_size_hint: None,
+            request_payload_size: None,
+            response_payload_size: None,
+            canonical_request_string: None,
+            max_attempts: None,
+            custom_attributes: HashMap::new(),
         }
     }
 
+    /// Set the name of the operation being invoked.
+    ///
+    /// This must be set before any hooks are run so that every hook sees the same name.
+    pub fn with_operation_name(mut self, operation_name: &'static str) -> Self {
+        self.operation_name = operation_name;
+        self
+    }
+
+    /// Set the name of the service the operation belongs to.
+    ///
+    /// The runtime sets this from the [`ServiceName`] in the config bag, if there is one.
+    pub fn with_service_name(mut self, service_name: &'static str) -> Self {
+        self.service_name = service_name;
+        self
+    }
+
+    /// Set the tracing context the execution starts with.
+    ///
+    /// The runtime sets this from the [`TraceContext`] in the config bag, if there is one.
+    pub fn with_trace_context(mut self, trace_context: TraceContext) -> Self {
+        self.trace_context = trace_context;
+        self
+    }
+
+    /// Retrieve the name of the service the operation belongs to, e.g. `S3`.
+    ///
+    /// Together with [`Self::operation_name`], this forms a fully-qualified label such as
+    /// `S3/GetObject`, e.g. for namespacing metrics. This will be empty if the service name isn't
+    /// known.
+    pub fn service_name(&self) -> &'static str {
+        self.service_name
+    }
+
+    /// Retrieve the version of the SDK that is executing the operation, e.g. `1.2.3`, for
+    /// interceptors that record client metadata such as tracing or audit logging.
+    ///
+    /// This is the version of the smithy-rs runtime that the client was compiled against.
+    pub fn sdk_version(&self) -> &'static str {
+        self.sdk_version
+    }
+
+    /// Retrieve the name of the operation being invoked, e.g. `GetObject`.
+    ///
+    /// This will be empty if the context was created without an operation name.
+    pub fn operation_name(&self) -> &'static str {
+        self.operation_name
+    }
+
+    /// Retrieve the protocol used by the operation being invoked, if known.
+    ///
+    /// The runtime sets this from the [`ProtocolId`] in the config bag once the operation's
+    /// runtime plugins have been applied.
+    pub fn protocol(&self) -> Option<ProtocolId> {
+        self.protocol
+    }
+
+    /// Set the protocol used by the operation being invoked.
+    pub fn set_protocol(&mut self, protocol: ProtocolId) {
+        self.protocol = Some(protocol);
+    }
+
+    /// Retrieve the number of the current attempt, starting at 1 for the first attempt.
+    ///
+    /// This will be 0 before the first attempt has started.
+    pub fn attempt_number(&self) -> u32 {
+        self.attempt_number
+    }
+
+    /// Returns `true` if the current attempt is a retry, i.e. not the first attempt.
+    pub fn is_retry(&self) -> bool {
+        self.attempt_number > 1
+    }
+
+    /// Returns `true` if the current attempt is the first attempt.
+    #[inline]
+    pub fn is_first_attempt(&self) -> bool {
+        self.attempt_number() == 1
+    }
+
+    /// Returns `true` if the current attempt is the last one the retry configuration allows.
+    ///
+    /// This is always `false` if the maximum number of attempts isn't known.
+    pub fn is_last_attempt(&self) -> bool {
+        self.max_attempts == Some(self.attempt_number())
+    }
+
+    /// Retrieve the maximum number of attempts the retry configuration allows, if known.
+    ///
+    /// The runtime sets this from the `RetryConfig` in the config bag once the operation's
+    /// runtime plugins have been applied.
+    pub fn max_attempts(&self) -> Option<u32> {
+        self.max_attempts
+    }
+
+    /// Set the maximum number of attempts the retry configuration allows.
+    pub fn set_max_attempts(&mut self, max_attempts: u32) {
+        self.max_attempts = Some(max_attempts);
+    }
+
+    /// Record that a new attempt is starting.
+    ///
+    /// This is called by the runtime before `read_before_attempt` is run for each attempt. Like
+    /// [`Self::with_attempt`], it clears the state left over from the previous attempt so that
+    /// the new attempt's response can be set.
+    pub fn start_attempt(&mut self) {
+        self.begin_attempt(self.attempt_number + 1);
+    }
+
+    /// Run `f` as attempt number `attempt`, returning its result.
+    ///
+    /// Before calling `f`, this sets the attempt number, records when the attempt started, and
+    /// clears the state left over from the previous attempt: the transport and modeled responses,
+    /// the error details, the retry decision, the response checksums and payload size, the
+    /// canonical request string, the transmit timing, and whether the response is streaming. The
+    /// transport request is kept so that it can be sent again. Errors recorded with
+    /// [`Self::record_attempt_error`] accumulate across attempts and are never cleared.
+    pub fn with_attempt<R>(&mut self, attempt: u32, f: impl FnOnce(&mut Self) -> R) -> R {
+        self.begin_attempt(attempt);
+        f(self)
+    }
+
+    fn begin_attempt(&mut self, attempt: u32) {
+        self.attempt_number = attempt;
+        self.attempt_start = Some(Instant::now());
+        self.tx_response = None;
+        self.modeled_response = None;
+        self.transmit_start = None;
+        self.transmit_end = None;
+        self.is_streaming_response = false;
+        self.error_details = None;
+        self.retry_decision = None;
+        self.response_checksum = None;
+        self.expected_response_checksum = None;
+        self.canonical_request_string = None;
+        self.response_payload_size = None;
+    }
+
+    /// Retrieve when the current attempt started, if an attempt has started.
+    pub fn attempt_start(&self) -> Option<Instant> {
+        self.attempt_start
+    }
+
+    /// Retrieve the errors that caused earlier attempts of this execution to be retried, oldest
+    /// first.
+    ///
+    /// This lets interceptors adapt their behavior on retries, e.g. switching auth schemes after
+    /// a rejected signature. The errors are kept for the entire execution.
+    pub fn previous_attempt_errors(&self) -> &[InterceptorError] {
+        &self.previous_attempt_errors
+    }
+
+    /// Retrieve the error that caused the most recent attempt to be retried, if any.
+    pub fn last_attempt_error(&self) -> Option<&InterceptorError> {
+        self.previous_attempt_errors.last()
+    }
+
+    /// Record the error that caused an attempt to be retried.
+    ///
+    /// This is called by the runtime before starting the next attempt.
+    pub fn record_attempt_error(&mut self, error: InterceptorError) {
+        self.previous_attempt_errors.push(error);
+    }
+
     /// Retrieve the modeled request for the operation being invoked.
     pub fn modeled_request(&self) -> &ModReq {
         &self.modeled_request
@@ -87,6 +677,357 @@ impl<ModReq, TxReq, TxRes, ModRes> InterceptorContext<ModReq, TxReq, TxRes, ModR
             .ok_or_else(InterceptorError::invalid_modeled_response_access)
     }
 
+    /// Attach `value` to this context under the key type `K`, replacing any value previously
+    /// set for `K`.
+    ///
+    /// Properties let interceptors pass data to interceptors that run later in the same execution
+    /// without storing it in the [`ConfigBag`]. Using a private key type ensures that other
+    /// interceptors can't read or overwrite the value.
+    pub fn set_property<K: 'static, V: Send + Sync + 'static>(&mut self, value: V) {
+        self.properties.insert(TypeId::of::<K>(), Box::new(value));
+    }
+
+    /// Retrieve the value set for the key type `K` with [`Self::set_property`].
+    ///
+    /// Returns `None` if no value was set for `K`, or if the value set for `K` isn't a `V`.
+    pub fn get_property<K: 'static, V: 'static>(&self) -> Option<&V> {
+        self.properties
+            .get(&TypeId::of::<K>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    /// Retrieve the endpoint the request is sent to.
+    ///
+    /// This is set by the runtime just before `read_before_transmit` and remains available in all
+    /// later hooks, including `read_after_execution`.
+    pub fn service_endpoint(&self) -> Option<&Uri> {
+        self.service_endpoint.as_ref()
+    }
+
+    /// Set the endpoint the request is sent to.
+    pub fn set_service_endpoint(&mut self, service_endpoint: Uri) {
+        self.service_endpoint = Some(service_endpoint);
+    }
+
+    /// Retrieve the ID the service assigned to the request, e.g. from the `x-amzn-requestid`
+    /// response header.
+    ///
+    /// This is only available once an interceptor has set it with [`Self::set_request_id`], which
+    /// the built-in `RequestIdInterceptor` does before deserialization.
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+
+    /// Set the ID the service assigned to the request.
+    pub fn set_request_id(&mut self, request_id: impl Into<String>) {
+        self.request_id = Some(request_id.into());
+    }
+
+    /// Retrieve the ID used to correlate this execution with the other service calls made for the
+    /// same user request.
+    ///
+    /// Unlike the [request ID](Self::request_id), which is assigned by the service, the
+    /// correlation ID is chosen by the client and sent to the service, e.g. by the built-in
+    /// `CorrelationIdInterceptor`.
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.correlation_id.as_deref()
+    }
+
+    /// Set the ID used to correlate this execution with other service calls.
+    ///
+    /// This should be set before the request is transmitted, e.g. in
+    /// `modify_before_serialization`, so that every attempt sends the same ID.
+    pub fn set_correlation_id(&mut self, correlation_id: impl Into<String>) {
+        self.correlation_id = Some(correlation_id.into());
+    }
+
+    /// Retrieve the custom attributes that interceptors have attached to this execution.
+    ///
+    /// The attributes are kept for the entire execution so that tracing interceptors can emit them
+    /// as span attributes, e.g. for OpenTelemetry or AWS X-Ray. The built-in
+    /// `ExecutionTracingInterceptor` records them on the execution span in `read_after_execution`.
+    pub fn custom_attributes(&self) -> &HashMap<String, String> {
+        &self.custom_attributes
+    }
+
+    /// Retrieve the custom attribute named `key`, if one has been set.
+    pub fn get_attribute(&self, key: &str) -> Option<&str> {
+        self.custom_attributes.get(key).map(String::as_str)
+    }
+
+    /// Attach a custom attribute to this execution, replacing any previous value for `key`.
+    pub fn set_attribute(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.custom_attributes.insert(key.into(), value.into());
+    }
+
+    /// Returns `true` if the response has a streaming body, e.g. for S3's `GetObject`.
+    ///
+    /// The runtime sets this from the response deserializer before `read_before_deserialization`.
+    /// A streaming body hasn't been consumed when `read_after_deserialization` runs, so interceptors
+    /// that process the body should defer that work until the stream has been read.
+    pub fn is_streaming_response(&self) -> bool {
+        self.is_streaming_response
+    }
+
+    /// Set whether the response has a streaming body.
+    pub fn set_streaming_response(&mut self, is_streaming_response: bool) {
+        self.is_streaming_response = is_streaming_response;
+    }
+
+    /// Retrieve the structured details of the error response returned by the service, if the
+    /// response was deserialized into a service error.
+    ///
+    /// The runtime sets this from the response deserializer, so it's available starting with
+    /// `read_after_deserialization`, e.g. for retry decisions based on the error code.
+    pub fn error_details(&self) -> Option<&ErrorDetails> {
+        self.error_details.as_ref()
+    }
+
+    /// Set the structured details of the error response returned by the service.
+    pub fn set_error_details(&mut self, error_details: ErrorDetails) {
+        self.error_details = Some(error_details);
+    }
+
+    /// Retrieve the runtime's decision on whether to retry the current attempt.
+    ///
+    /// The runtime sets this once the retry strategy has judged the attempt, before
+    /// `read_after_attempt` is run, so that interceptors can observe retries without
+    /// reimplementing the retry logic.
+    pub fn retry_decision(&self) -> Option<&RetryDecision> {
+        self.retry_decision.as_ref()
+    }
+
+    /// Set the runtime's decision on whether to retry the current attempt.
+    pub fn set_retry_decision(&mut self, retry_decision: RetryDecision) {
+        self.retry_decision = Some(retry_decision);
+    }
+
+    /// Retrieve the results of the attempts made so far, oldest first.
+    ///
+    /// The runtime records each attempt once its retry decision has been made, so the full retry
+    /// history is available in `read_after_execution`. Like [`Self::previous_attempt_errors`],
+    /// the results are kept for the entire execution.
+    pub fn attempt_results(&self) -> &[AttemptResult] {
+        &self.attempt_results
+    }
+
+    /// Record the result of an attempt.
+    pub fn record_attempt_result(&mut self, attempt_result: AttemptResult) {
+        self.attempt_results.push(attempt_result);
+    }
+
+    /// Retrieve the expected size of the transport request's body in bytes, if it's known.
+    ///
+    /// The runtime sets this once the request has been serialized, so that interceptors can
+    /// report upload progress, e.g. as a percentage in `read_before_transmit`.
+    pub fn body_size_hint(&self) -> Option<u64> {
+        self.body_size_hint
+    }
+
+    /// Set the expected size of the transport request's body in bytes.
+    pub fn set_body_size_hint(&mut self, body_size_hint: u64) {
+        self.body_size_hint = Some(body_size_hint);
+    }
+
+    /// Retrieve the exact size of the serialized request's body in bytes, if it's known.
+    ///
+    /// The runtime sets this once the request has been serialized, so it's available from
+    /// `read_after_serialization` onwards. Unlike [`Self::body_size_hint`], this is only set when
+    /// the size is known exactly, e.g. for bodies held in memory, which makes it suitable for
+    /// cost-based rate limiting.
+    pub fn request_payload_size(&self) -> Option<u64> {
+        self.request_payload_size
+    }
+
+    /// Set the exact size of the serialized request's body in bytes.
+    pub fn set_request_payload_size(&mut self, request_payload_size: u64) {
+        self.request_payload_size = Some(request_payload_size);
+    }
+
+    /// Retrieve the size of the transport response's body in bytes, if it's known.
+    ///
+    /// The runtime sets this once the response has been deserialized, e.g. from the
+    /// `Content-Length` header or by measuring the consumed body, so it's available from
+    /// `read_after_deserialization` onwards. It describes the current attempt's response.
+    pub fn response_payload_size(&self) -> Option<u64> {
+        self.response_payload_size
+    }
+
+    /// Set the size of the transport response's body in bytes.
+    pub fn set_response_payload_size(&mut self, response_payload_size: u64) {
+        self.response_payload_size = Some(response_payload_size);
+    }
+
+    /// Retrieve the canonical request string that the signer signed for the current attempt, if
+    /// the signer provides it.
+    ///
+    /// This is available from `read_after_signing` onwards, so that audit interceptors can log the
+    /// inputs to e.g. a SigV4 signature without reimplementing the signing algorithm.
+    pub fn canonical_request_string(&self) -> Option<&str> {
+        self.canonical_request_string.as_deref()
+    }
+
+    /// Set the canonical request string that was signed for the current attempt.
+    pub fn set_canonical_request_string(&mut self, canonical_request: impl Into<String>) {
+        self.canonical_request_string = Some(canonical_request.into());
+    }
+
+    /// Retrieve the checksum of the transport request's body, if one was computed, e.g. by a
+    /// checksum interceptor once the request has been serialized.
+    pub fn request_checksum(&self) -> Option<&[u8]> {
+        self.request_checksum.as_deref()
+    }
+
+    /// Set the checksum of the transport request's body.
+    pub fn set_request_checksum(&mut self, checksum: Vec<u8>) {
+        self.request_checksum = Some(checksum);
+    }
+
+    /// Retrieve the checksum of the transport response's body, if one was computed.
+    pub fn response_checksum(&self) -> Option<&[u8]> {
+        self.response_checksum.as_deref()
+    }
+
+    /// Set the checksum of the transport response's body, along with the checksum the service
+    /// sent for it, if any.
+    ///
+    /// When both are set, the runtime [verifies](Self::verify_response_checksum) that they match
+    /// once `modify_before_attempt_completion` has run.
+    pub fn set_response_checksum(&mut self, checksum: Vec<u8>, expected: Option<Vec<u8>>) {
+        self.response_checksum = Some(checksum);
+        self.expected_response_checksum = expected;
+    }
+
+    /// Retrieve the checksum the service sent for the transport response's body, if any.
+    pub fn expected_response_checksum(&self) -> Option<&[u8]> {
+        self.expected_response_checksum.as_deref()
+    }
+
+    /// Check that the response checksum matches the checksum sent by the service.
+    ///
+    /// This succeeds if either checksum is missing, since there's nothing to compare.
+    pub fn verify_response_checksum(&self) -> Result<(), InterceptorError> {
+        match (self.response_checksum(), self.expected_response_checksum()) {
+            (Some(actual), Some(expected)) if actual != expected => {
+                Err(InterceptorError::response_checksum_mismatch())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Retrieve the distributed tracing context of this execution.
+    ///
+    /// This is empty unless a [`TraceContext`] was set in the config bag.
+    pub fn trace_context(&self) -> &TraceContext {
+        &self.trace_context
+    }
+
+    /// Mutably retrieve the distributed tracing context of this execution.
+    ///
+    /// This should only be used from modify hooks.
+    pub fn trace_context_mut(&mut self) -> &mut TraceContext {
+        &mut self.trace_context
+    }
+
+    /// Retrieve the auth scheme selected for this execution, if one was set with
+    /// [`Self::set_auth_scheme`].
+    pub fn auth_scheme(&self) -> Option<&AuthScheme<TxReq>> {
+        self.auth_scheme.as_ref()
+    }
+
+    /// Select the auth scheme used to sign the request, overriding the default from the config
+    /// bag.
+    ///
+    /// This should be called from `modify_before_signing`: the runtime signs the request once
+    /// `read_before_signing` has run, using the selected scheme's [`Signer`] if one was set. The
+    /// selection is kept for every later attempt of the execution.
+    pub fn set_auth_scheme(&mut self, scheme: AuthScheme<TxReq>) {
+        self.auth_scheme = Some(scheme);
+    }
+
+    /// Record that the request is about to be sent.
+    ///
+    /// This is called by the runtime immediately before handing the request to the connection, and
+    /// clears any timing recorded for a previous attempt.
+    pub fn mark_transmit_start(&mut self) {
+        self.transmit_start = Some(Instant::now());
+        self.transmit_end = None;
+    }
+
+    /// Record that the response has been received.
+    ///
+    /// This is called by the runtime immediately after the connection returns a response.
+    pub fn mark_transmit_end(&mut self) {
+        self.transmit_end = Some(Instant::now());
+    }
+
+    /// Retrieve the timeout set with [`Self::set_operation_timeout`], if any.
+    pub fn operation_timeout(&self) -> Option<Duration> {
+        self.operation_timeout
+    }
+
+    /// Limit how long the service may take to respond to each attempt of this execution.
+    ///
+    /// This lets an interceptor pick a timeout based on the request, e.g. its size, and takes
+    /// precedence over the client's [`OperationTimeout`]. It should only be called from
+    /// `modify_before_retry_loop` or `modify_before_signing`; the runtime reads it before each
+    /// attempt is transmitted and applies it as the transport's deadline.
+    pub fn set_operation_timeout(&mut self, timeout: Duration) {
+        self.operation_timeout = Some(timeout);
+    }
+
+    /// Retrieve the time spent communicating with the service during the current attempt.
+    ///
+    /// This will only be available once a response has been received, starting with
+    /// `read_after_transmit`. Unlike timing the `read_before_transmit` and `read_after_transmit`
+    /// hooks, this doesn't include time spent in other interceptors.
+    pub fn transmit_duration(&self) -> Option<Duration> {
+        match (self.transmit_start, self.transmit_end) {
+            (Some(start), Some(end)) => Some(end.saturating_duration_since(start)),
+            _ => None,
+        }
+    }
+
+    /// Cancel the execution.
+    ///
+    /// Once the current hook returns, the runtime skips all remaining hooks and jumps to
+    /// `modify_before_completion`, with a
+    /// [`CancelledError`](super::error::CancelledError) as the modeled response. Because this
+    /// requires mutable access to the context, it can only be called from `modify_*` hooks.
+    pub fn cancel(&mut self, reason: CancellationReason) {
+        self.cancellation = Some(reason);
+    }
+
+    /// Cancel the execution, completing it with `response` as if it came from the service.
+    ///
+    /// This lets an interceptor such as a response cache short-circuit the execution. As with
+    /// [`Self::cancel`], the runtime skips all remaining hooks and jumps to
+    /// `modify_before_completion`, but `response` is kept as the modeled response instead of
+    /// being replaced with a [`CancelledError`](super::error::CancelledError).
+    pub fn cancel_with_response(&mut self, response: ModRes) {
+        self.cancellation = Some(CancellationReason::new(
+            "an interceptor provided the response",
+        ));
+        self.modeled_response = Some(response);
+        self.has_injected_response = true;
+    }
+
+    /// Returns `true` if the execution was cancelled with
+    /// [`cancel_with_response`](Self::cancel_with_response).
+    pub fn has_injected_response(&self) -> bool {
+        self.has_injected_response
+    }
+
+    /// Returns `true` if an interceptor has [cancelled](Self::cancel) the execution.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_some()
+    }
+
+    /// Retrieve the reason the execution was cancelled, if it was.
+    pub fn cancellation_reason(&self) -> Option<&CancellationReason> {
+        self.cancellation.as_ref()
+    }
+
     // There is no set_modeled_request method because that can only be set once, during context construction
 
     pub fn set_tx_request(&mut self, transmit_request: TxReq) {
@@ -113,6 +1054,12 @@ impl<ModReq, TxReq, TxRes, ModRes> InterceptorContext<ModReq, TxReq, TxRes, ModR
         self.modeled_response = Some(modeled_response);
     }
 
+    /// Consume the context, returning the response to the customer.
+    pub fn into_modeled_response(self) -> Result<ModRes, InterceptorError> {
+        self.modeled_response
+            .ok_or_else(InterceptorError::invalid_modeled_response_access)
+    }
+
     pub fn into_responses(self) -> Result<(ModRes, TxRes), InterceptorError> {
         let mod_res = self
             .modeled_response
@@ -124,3 +1071,480 @@ impl<ModReq, TxReq, TxRes, ModRes> InterceptorContext<ModReq, TxReq, TxRes, ModR
         Ok((mod_res, tx_res))
     }
 }
+
+/// A modeled response that is either a success or an error
+///
+/// This lets generic interceptors branch on the outcome of an operation with
+/// [`InterceptorContext::is_modeled_error`] without knowing the concrete response type.
+pub trait ModeledResponse {
+    /// Returns `true` if the response is an error.
+    fn is_error(&self) -> bool;
+}
+
+impl<T, E> ModeledResponse for Result<T, E> {
+    fn is_error(&self) -> bool {
+        self.is_err()
+    }
+}
+
+impl<ModReq, TxReq, TxRes, ModRes> InterceptorContext<ModReq, TxReq, TxRes, ModRes>
+where
+    ModRes: ModeledResponse,
+{
+    /// Returns `true` if the modeled response has been set and is an error.
+    pub fn is_modeled_error(&self) -> bool {
+        matches!(&self.modeled_response, Some(response) if response.is_error())
+    }
+
+    /// Returns `true` if the modeled response has been set and is a success.
+    pub fn is_modeled_success(&self) -> bool {
+        matches!(&self.modeled_response, Some(response) if !response.is_error())
+    }
+}
+
+/// Conveniences for the common case of a modeled response that's a `Result`
+impl<ModReq, TxReq, TxRes, T, E> InterceptorContext<ModReq, TxReq, TxRes, Result<T, E>> {
+    /// Retrieve the error of the modeled response, if the response has been set and is an error.
+    ///
+    /// This is a shortcut for `modify_before_attempt_completion` and `modify_before_completion`,
+    /// which usually need to inspect the error that ended the attempt or execution.
+    pub fn last_error(&self) -> Option<&E> {
+        self.modeled_response.as_ref()?.as_ref().err()
+    }
+}
+
+/// Conveniences for the common case of an HTTP transport request
+impl<ModReq, B, TxRes, ModRes> InterceptorContext<ModReq, http::Request<B>, TxRes, ModRes> {
+    /// Mutably retrieve the headers of the transport request, if it has been serialized.
+    ///
+    /// This is a shortcut for header-only changes, e.g. adding a custom auth header in
+    /// `modify_before_transmit`. It should only be called from modify hooks, since read hooks
+    /// expect the request to stay unchanged.
+    pub fn headers_mut(&mut self) -> Option<&mut HeaderMap> {
+        self.tx_request.as_mut().map(http::Request::headers_mut)
+    }
+
+    /// Mutably retrieve the URI of the transport request, if it has been serialized.
+    ///
+    /// This allows interceptors to rewrite the request URL, e.g. for endpoint overrides,
+    /// path-based routing, or proxies. It should only be called from `modify_before_signing` or
+    /// `modify_before_transmit`. Changing the URI invalidates any signature that was already
+    /// computed, so a URI changed in `modify_before_transmit` will fail signature verification
+    /// unless the signature doesn't cover it.
+    pub fn request_uri_mut(&mut self) -> Option<&mut Uri> {
+        self.tx_request.as_mut().map(http::Request::uri_mut)
+    }
+}
+
+/// Conveniences for the common case of an HTTP transport response
+impl<ModReq, TxReq, B, ModRes> InterceptorContext<ModReq, TxReq, http::Response<B>, ModRes> {
+    /// Retrieve the headers of the transport response, if it has been received.
+    pub fn response_headers(&self) -> Option<&HeaderMap> {
+        self.tx_response.as_ref().map(http::Response::headers)
+    }
+
+    /// Retrieve the value of the response header `name`, e.g. `Retry-After`.
+    ///
+    /// Returns `None` if the response hasn't been received, doesn't contain the header, or the
+    /// header's value isn't valid UTF-8. If the header appears more than once, the first value is
+    /// returned.
+    pub fn response_header(&self, name: &str) -> Option<&str> {
+        self.response_headers()?.get(name)?.to_str().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AttemptOutcome, AttemptResult, AuthScheme, CancellationReason, ErrorDetails,
+        InterceptorContext, RetryDecision, RetryReason, Signer, TraceContext,
+    };
+    use crate::config_bag::ConfigBag;
+    use crate::interceptors::error::BoxError;
+    use crate::interceptors::{ErrorKind, InterceptorError};
+    use std::time::Duration;
+
+    #[test]
+    fn properties_are_keyed_by_key_type() {
+        struct RequestId;
+        struct CacheKey;
+
+        let mut ctx = InterceptorContext::<(), (), (), ()>::new(());
+        ctx.set_property::<RequestId, _>("abc-123".to_string());
+        ctx.set_property::<CacheKey, _>("abc-123".to_string());
+        ctx.set_property::<CacheKey, _>(42_u64);
+
+        assert_eq!(
+            ctx.get_property::<RequestId, String>().map(String::as_str),
+            Some("abc-123")
+        );
+        assert_eq!(ctx.get_property::<CacheKey, u64>(), Some(&42));
+        assert_eq!(ctx.get_property::<CacheKey, String>(), None);
+    }
+
+    #[test]
+    fn with_attempt_resets_per_attempt_state() {
+        let mut ctx = InterceptorContext::<(), (), u16, ()>::new(());
+        ctx.set_tx_request(());
+        ctx.with_attempt(1, |ctx| {
+            ctx.mark_transmit_start();
+            ctx.mark_transmit_end();
+            ctx.set_tx_response(503);
+            ctx.set_modeled_response(());
+            ctx.record_attempt_error(InterceptorError::retryable("throttled"));
+            ctx.set_error_details(ErrorDetails::new(
+                "SlowDown",
+                "Please reduce your request rate.",
+            ));
+            ctx.set_canonical_request_string("GET\n/\n");
+            ctx.set_response_payload_size(128);
+            assert_eq!(ctx.canonical_request_string(), Some("GET\n/\n"));
+        });
+        // The operation timeout applies to every attempt
+        ctx.set_operation_timeout(Duration::from_secs(5));
+
+        let status = ctx.with_attempt(2, |ctx| {
+            assert!(ctx.is_retry());
+            assert!(ctx.attempt_start().is_some());
+            assert!(ctx.tx_request().is_ok());
+            assert!(ctx.tx_response().is_err());
+            assert!(ctx.modeled_response().is_err());
+            assert_eq!(ctx.transmit_duration(), None);
+            assert_eq!(ctx.error_details(), None);
+            assert_eq!(ctx.canonical_request_string(), None);
+            assert_eq!(ctx.response_payload_size(), None);
+            assert_eq!(ctx.operation_timeout(), Some(Duration::from_secs(5)));
+            ctx.set_tx_response(200);
+            *ctx.tx_response().unwrap()
+        });
+        assert_eq!(status, 200);
+        assert_eq!(ctx.attempt_number(), 2);
+        assert_eq!(ctx.previous_attempt_errors().len(), 1);
+    }
+
+    #[test]
+    fn sdk_version() {
+        let ctx = InterceptorContext::<(), (), (), ()>::new(());
+        assert_eq!(ctx.sdk_version(), env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn custom_attributes() {
+        let mut ctx = InterceptorContext::<(), (), (), ()>::new(());
+        assert!(ctx.custom_attributes().is_empty());
+        ctx.set_attribute("tenant", "a");
+        ctx.set_attribute("tenant", "b");
+        ctx.set_attribute(String::from("region"), "us-east-1");
+        // Attributes are kept across attempts
+        ctx.with_attempt(2, |_| {});
+        assert_eq!(ctx.get_attribute("tenant"), Some("b"));
+        assert_eq!(ctx.get_attribute("missing"), None);
+        assert_eq!(ctx.custom_attributes().len(), 2);
+    }
+
+    #[test]
+    fn modeled_response_outcome() {
+        let mut ctx = InterceptorContext::<(), (), (), Result<u32, String>>::new(());
+        assert!(!ctx.is_modeled_error());
+        assert!(!ctx.is_modeled_success());
+
+        ctx.set_modeled_response(Ok(200));
+        assert!(ctx.is_modeled_success());
+        assert!(!ctx.is_modeled_error());
+
+        let mut ctx = InterceptorContext::<(), (), (), Result<u32, String>>::new(());
+        ctx.set_modeled_response(Err("throttled".to_string()));
+        assert!(ctx.is_modeled_error());
+        assert!(!ctx.is_modeled_success());
+    }
+
+    #[test]
+    fn last_error() {
+        let mut ctx = InterceptorContext::<(), (), (), Result<u32, String>>::new(());
+        assert_eq!(ctx.last_error(), None);
+        ctx.set_modeled_response(Err("throttled".to_string()));
+        assert_eq!(ctx.last_error().map(String::as_str), Some("throttled"));
+
+        let mut ctx = InterceptorContext::<(), (), (), Result<u32, String>>::new(());
+        ctx.set_modeled_response(Ok(200));
+        assert_eq!(ctx.last_error(), None);
+    }
+
+    #[test]
+    fn response_headers() {
+        let mut ctx = InterceptorContext::<(), (), http::Response<()>, ()>::new(());
+        assert!(ctx.response_headers().is_none());
+        ctx.set_tx_response(
+            http::Response::builder()
+                .header("Retry-After", "5")
+                .header("x-amz-request-id", "abc-123")
+                .body(())
+                .unwrap(),
+        );
+        assert_eq!(ctx.response_headers().unwrap().len(), 2);
+        assert_eq!(ctx.response_header("retry-after"), Some("5"));
+        assert_eq!(ctx.response_header("x-amz-request-id"), Some("abc-123"));
+        assert_eq!(ctx.response_header("content-type"), None);
+    }
+
+    #[test]
+    fn headers_mut() {
+        let mut ctx = InterceptorContext::<(), http::Request<()>, (), ()>::new(());
+        assert!(ctx.headers_mut().is_none());
+        ctx.set_tx_request(
+            http::Request::builder()
+                .header("x-amz-security-token", "secret")
+                .body(())
+                .unwrap(),
+        );
+        let headers = ctx.headers_mut().unwrap();
+        headers.remove("x-amz-security-token");
+        headers.insert("x-custom-auth", http::HeaderValue::from_static("token"));
+        let headers = ctx.tx_request().unwrap().headers();
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers["x-custom-auth"], "token");
+    }
+
+    #[test]
+    fn request_uri_mut() {
+        let mut ctx = InterceptorContext::<(), http::Request<()>, (), ()>::new(());
+        assert!(ctx.request_uri_mut().is_none());
+        ctx.set_tx_request(
+            http::Request::builder()
+                .uri("https://s3.us-east-1.amazonaws.com/bucket/key")
+                .body(())
+                .unwrap(),
+        );
+        *ctx.request_uri_mut().unwrap() = "http://localhost:9000/bucket/key".parse().unwrap();
+        assert_eq!(
+            ctx.tx_request().unwrap().uri(),
+            "http://localhost:9000/bucket/key"
+        );
+    }
+
+    #[test]
+    fn error_details() {
+        let mut ctx = InterceptorContext::<(), (), (), ()>::new(());
+        assert_eq!(ctx.error_details(), None);
+        ctx.set_error_details(
+            ErrorDetails::new("ThrottlingException", "Rate exceeded").with_request_id("abc-123"),
+        );
+        let details = ctx.error_details().unwrap();
+        assert_eq!(details.code(), "ThrottlingException");
+        assert_eq!(details.message(), "Rate exceeded");
+        assert_eq!(details.request_id(), Some("abc-123"));
+    }
+
+    #[test]
+    fn attempts_after_the_first_are_retries() {
+        let mut ctx = InterceptorContext::<(), (), (), ()>::new(());
+        assert_eq!(ctx.attempt_number(), 0);
+        ctx.start_attempt();
+        assert_eq!(ctx.attempt_number(), 1);
+        assert!(!ctx.is_retry());
+        ctx.start_attempt();
+        assert_eq!(ctx.attempt_number(), 2);
+        assert!(ctx.is_retry());
+    }
+
+    #[test]
+    fn retry_decision_is_cleared_between_attempts() {
+        let mut ctx = InterceptorContext::<(), (), (), ()>::new(());
+        assert_eq!(ctx.retry_decision(), None);
+        ctx.with_attempt(1, |ctx| {
+            ctx.set_retry_decision(RetryDecision::retry(Duration::from_secs(1)))
+        });
+        let decision = ctx.retry_decision().unwrap();
+        assert!(decision.should_retry());
+        assert_eq!(decision.delay(), Some(Duration::from_secs(1)));
+        assert_eq!(decision.reason(), RetryReason::RetryStrategy);
+
+        ctx.with_attempt(2, |ctx| {
+            assert_eq!(ctx.retry_decision(), None);
+            ctx.set_retry_decision(RetryDecision::no_retry(RetryReason::Success))
+        });
+        assert!(!ctx.retry_decision().unwrap().should_retry());
+    }
+
+    #[test]
+    fn attempt_results_accumulate_across_attempts() {
+        let mut ctx = InterceptorContext::<(), (), (), ()>::new(());
+        assert!(ctx.attempt_results().is_empty());
+        ctx.with_attempt(1, |ctx| {
+            ctx.record_attempt_result(AttemptResult::new(
+                1,
+                Duration::from_millis(30),
+                AttemptOutcome::Failed(ErrorKind::Transport),
+                RetryDecision::retry(Duration::from_millis(100)),
+            ))
+        });
+        ctx.with_attempt(2, |ctx| {
+            ctx.record_attempt_result(AttemptResult::new(
+                2,
+                Duration::from_millis(20),
+                AttemptOutcome::Success,
+                RetryDecision::no_retry(RetryReason::Success),
+            ))
+        });
+
+        let results = ctx.attempt_results();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].attempt_number(), 1);
+        assert_eq!(
+            results[0].outcome(),
+            AttemptOutcome::Failed(ErrorKind::Transport)
+        );
+        assert!(results[0].retry_decision().should_retry());
+        assert_eq!(results[1].latency(), Duration::from_millis(20));
+        assert_eq!(results[1].outcome(), AttemptOutcome::Success);
+    }
+
+    #[test]
+    fn response_checksum_verification() {
+        let mut ctx = InterceptorContext::<(), (), (), ()>::new(());
+        ctx.set_request_checksum(vec![1, 2, 3]);
+        assert_eq!(ctx.request_checksum(), Some(&[1, 2, 3][..]));
+        assert!(ctx.verify_response_checksum().is_ok());
+
+        ctx.with_attempt(1, |ctx| ctx.set_response_checksum(vec![4, 5], None));
+        assert!(ctx.verify_response_checksum().is_ok());
+
+        ctx.with_attempt(2, |ctx| {
+            assert_eq!(ctx.response_checksum(), None);
+            ctx.set_response_checksum(vec![4, 5], Some(vec![4, 5]))
+        });
+        assert!(ctx.verify_response_checksum().is_ok());
+
+        ctx.set_response_checksum(vec![4, 5], Some(vec![6, 7]));
+        let err = ctx.verify_response_checksum().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Deserialization);
+        assert!(err.is_retryable());
+        // The request checksum is kept across attempts
+        assert_eq!(ctx.request_checksum(), Some(&[1, 2, 3][..]));
+    }
+
+    #[test]
+    fn body_size_hint() {
+        let mut ctx = InterceptorContext::<(), (), (), ()>::new(());
+        assert_eq!(ctx.body_size_hint(), None);
+        ctx.set_body_size_hint(1024);
+        // The hint describes the request, so it's kept across attempts
+        ctx.with_attempt(2, |_| {});
+        assert_eq!(ctx.body_size_hint(), Some(1024));
+    }
+
+    #[test]
+    fn request_payload_size() {
+        let mut ctx = InterceptorContext::<(), (), (), ()>::new(());
+        assert_eq!(ctx.request_payload_size(), None);
+        ctx.set_request_payload_size(512);
+        // The payload is sent again on retries, so its size is kept across attempts
+        ctx.with_attempt(2, |_| {});
+        assert_eq!(ctx.request_payload_size(), Some(512));
+    }
+
+    #[test]
+    fn first_and_last_attempts() {
+        let mut ctx = InterceptorContext::<(), (), (), ()>::new(());
+        ctx.start_attempt();
+        assert!(ctx.is_first_attempt());
+        // Without a maximum, no attempt is known to be the last
+        assert!(!ctx.is_last_attempt());
+
+        ctx.set_max_attempts(2);
+        assert!(!ctx.is_last_attempt());
+        ctx.start_attempt();
+        assert!(!ctx.is_first_attempt());
+        assert!(ctx.is_last_attempt());
+    }
+
+    #[test]
+    fn previous_attempt_errors() {
+        let mut ctx = InterceptorContext::<(), (), (), ()>::new(());
+        assert!(ctx.last_attempt_error().is_none());
+        ctx.record_attempt_error(InterceptorError::transport("connection reset"));
+        ctx.record_attempt_error(InterceptorError::signing("clock skew"));
+        assert_eq!(ctx.previous_attempt_errors().len(), 2);
+        assert_eq!(ctx.last_attempt_error().unwrap().kind(), ErrorKind::Signing);
+    }
+
+    #[test]
+    fn auth_scheme() {
+        #[derive(Debug)]
+        struct BearerToken;
+
+        impl Signer<http::Request<()>> for BearerToken {
+            fn sign_request(
+                &self,
+                request: &mut http::Request<()>,
+                _cfg: &ConfigBag,
+            ) -> Result<(), BoxError> {
+                request
+                    .headers_mut()
+                    .insert(http::header::AUTHORIZATION, "Bearer token".parse()?);
+                Ok(())
+            }
+        }
+
+        let mut ctx = InterceptorContext::<(), http::Request<()>, (), ()>::new(());
+        assert!(ctx.auth_scheme().is_none());
+        ctx.set_auth_scheme(AuthScheme::new("bearer", BearerToken));
+
+        ctx.with_attempt(2, |_| {});
+        let scheme = ctx.auth_scheme().unwrap().clone();
+        assert_eq!(scheme.name(), "bearer");
+        let mut request = http::Request::new(());
+        scheme
+            .signer()
+            .sign_request(&mut request, &ConfigBag::base())
+            .unwrap();
+        assert_eq!(request.headers()["authorization"], "Bearer token");
+    }
+
+    #[test]
+    fn trace_context() {
+        let ctx = InterceptorContext::<(), (), (), ()>::new(());
+        assert!(ctx.trace_context().is_empty());
+
+        let mut ctx = ctx.with_trace_context(TraceContext::new("trace", "parent"));
+        ctx.trace_context_mut().set_span_id("attempt");
+        assert_eq!(ctx.trace_context().trace_id(), "trace");
+        assert_eq!(ctx.trace_context().span_id(), "attempt");
+    }
+
+    #[test]
+    fn cancel() {
+        let mut ctx = InterceptorContext::<(), (), (), ()>::new(());
+        assert!(!ctx.is_cancelled());
+        ctx.cancel(CancellationReason::new("circuit breaker is open"));
+        assert!(ctx.is_cancelled());
+        assert_eq!(
+            ctx.cancellation_reason().unwrap().to_string(),
+            "circuit breaker is open"
+        );
+    }
+
+    #[test]
+    fn cancel_with_response() {
+        let mut ctx = InterceptorContext::<(), (), (), &'static str>::new(());
+        ctx.set_modeled_response("from the service");
+        ctx.cancel_with_response("from the cache");
+        assert!(ctx.is_cancelled());
+        assert!(ctx.has_injected_response());
+        assert_eq!(ctx.modeled_response().unwrap(), &"from the cache");
+    }
+
+    #[test]
+    fn transmit_duration_is_only_available_after_transmit() {
+        let mut ctx = InterceptorContext::<(), (), (), ()>::new(());
+        assert_eq!(ctx.transmit_duration(), None);
+        ctx.mark_transmit_start();
+        assert_eq!(ctx.transmit_duration(), None);
+        ctx.mark_transmit_end();
+        assert!(ctx.transmit_duration().is_some());
+
+        // A new attempt discards the previous attempt's timing
+        ctx.mark_transmit_start();
+        assert_eq!(ctx.transmit_duration(), None);
+    }
+}
@@ -5,15 +5,39 @@
 
 //! Errors related to smithy interceptors
 
+use super::context::CancellationReason;
 use std::fmt;
+use std::time::Duration;
 
 /// A generic error that behaves itself in async contexts
 pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
 
+/// The category of failure an [`InterceptorError`] represents, e.g. for routing alerts.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// The request couldn't be serialized
+    Serialization,
+    /// The request couldn't be signed
+    Signing,
+    /// The request couldn't be sent or no response was received
+    Transport,
+    /// The response couldn't be deserialized
+    Deserialization,
+    /// An interceptor raised an error from one of its hooks
+    User,
+    /// The runtime was used incorrectly, e.g. an interceptor accessed data that isn't available yet
+    Internal,
+}
+
 /// An error related to smithy interceptors.
 #[derive(Debug)]
 pub struct InterceptorError {
     kind: ErrorKind,
+    detail: ErrorDetail,
+    retryable: bool,
+    retry_after: Option<Duration>,
+    status_code: Option<u16>,
     source: Option<BoxError>,
 }
 
@@ -23,7 +47,11 @@ impl InterceptorError {
         source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     ) -> Self {
         Self {
-            kind: ErrorKind::ReadBeforeExecution,
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ReadBeforeExecution,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: Some(source.into()),
         }
     }
@@ -32,7 +60,11 @@ impl InterceptorError {
         source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     ) -> Self {
         Self {
-            kind: ErrorKind::ModifyBeforeSerialization,
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ModifyBeforeSerialization,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: Some(source.into()),
         }
     }
@@ -41,7 +73,11 @@ impl InterceptorError {
         source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     ) -> Self {
         Self {
-            kind: ErrorKind::ReadBeforeSerialization,
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ReadBeforeSerialization,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: Some(source.into()),
         }
     }
@@ -50,7 +86,11 @@ impl InterceptorError {
         source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     ) -> Self {
         Self {
-            kind: ErrorKind::ReadAfterSerialization,
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ReadAfterSerialization,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: Some(source.into()),
         }
     }
@@ -59,7 +99,11 @@ impl InterceptorError {
         source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     ) -> Self {
         Self {
-            kind: ErrorKind::ModifyBeforeRetryLoop,
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ModifyBeforeRetryLoop,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: Some(source.into()),
         }
     }
@@ -68,7 +112,11 @@ impl InterceptorError {
         source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     ) -> Self {
         Self {
-            kind: ErrorKind::ReadBeforeAttempt,
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ReadBeforeAttempt,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: Some(source.into()),
         }
     }
@@ -77,7 +125,11 @@ impl InterceptorError {
         source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     ) -> Self {
         Self {
-            kind: ErrorKind::ModifyBeforeSigning,
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ModifyBeforeSigning,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: Some(source.into()),
         }
     }
@@ -86,7 +138,11 @@ impl InterceptorError {
         source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     ) -> Self {
         Self {
-            kind: ErrorKind::ReadBeforeSigning,
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ReadBeforeSigning,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: Some(source.into()),
         }
     }
@@ -95,7 +151,11 @@ impl InterceptorError {
         source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     ) -> Self {
         Self {
-            kind: ErrorKind::ReadAfterSigning,
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ReadAfterSigning,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: Some(source.into()),
         }
     }
@@ -104,7 +164,11 @@ impl InterceptorError {
         source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     ) -> Self {
         Self {
-            kind: ErrorKind::ModifyBeforeTransmit,
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ModifyBeforeTransmit,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: Some(source.into()),
         }
     }
@@ -113,7 +177,11 @@ impl InterceptorError {
         source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     ) -> Self {
         Self {
-            kind: ErrorKind::ReadBeforeTransmit,
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ReadBeforeTransmit,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: Some(source.into()),
         }
     }
@@ -122,7 +190,11 @@ impl InterceptorError {
         source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     ) -> Self {
         Self {
-            kind: ErrorKind::ReadAfterTransmit,
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ReadAfterTransmit,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: Some(source.into()),
         }
     }
@@ -131,7 +203,11 @@ impl InterceptorError {
         source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     ) -> Self {
         Self {
-            kind: ErrorKind::ModifyBeforeDeserialization,
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ModifyBeforeDeserialization,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: Some(source.into()),
         }
     }
@@ -140,7 +216,11 @@ impl InterceptorError {
         source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     ) -> Self {
         Self {
-            kind: ErrorKind::ReadBeforeDeserialization,
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ReadBeforeDeserialization,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: Some(source.into()),
         }
     }
@@ -149,7 +229,11 @@ impl InterceptorError {
         source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     ) -> Self {
         Self {
-            kind: ErrorKind::ReadAfterDeserialization,
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ReadAfterDeserialization,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: Some(source.into()),
         }
     }
@@ -158,7 +242,11 @@ impl InterceptorError {
         source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     ) -> Self {
         Self {
-            kind: ErrorKind::ModifyBeforeAttemptCompletion,
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ModifyBeforeAttemptCompletion,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: Some(source.into()),
         }
     }
@@ -167,7 +255,11 @@ impl InterceptorError {
         source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     ) -> Self {
         Self {
-            kind: ErrorKind::ReadAfterAttempt,
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ReadAfterAttempt,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: Some(source.into()),
         }
     }
@@ -176,7 +268,50 @@ impl InterceptorError {
         source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     ) -> Self {
         Self {
-            kind: ErrorKind::ModifyBeforeCompletion,
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ModifyBeforeCompletion,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
+            source: Some(source.into()),
+        }
+    }
+    /// Create a new error indicating a failure withing a modify_before_retry interceptor
+    pub fn modify_before_retry(
+        source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    ) -> Self {
+        Self {
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ModifyBeforeRetry,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
+            source: Some(source.into()),
+        }
+    }
+    /// Create a new error indicating a failure withing a read_between_attempts interceptor
+    pub fn read_between_attempts(
+        source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    ) -> Self {
+        Self {
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ReadBetweenAttempts,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
+            source: Some(source.into()),
+        }
+    }
+    /// Create a new error indicating a failure withing a read_on_transport_error interceptor
+    pub fn read_on_transport_error(
+        source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    ) -> Self {
+        Self {
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ReadOnTransportError,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: Some(source.into()),
         }
     }
@@ -185,35 +320,244 @@ impl InterceptorError {
         source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     ) -> Self {
         Self {
-            kind: ErrorKind::ReadAfterExecution,
+            kind: ErrorKind::User,
+            detail: ErrorDetail::ReadAfterExecution,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: Some(source.into()),
         }
     }
+    /// Create a new error indicating that the request couldn't be serialized
+    pub fn serialization(
+        source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    ) -> Self {
+        Self::categorized(ErrorKind::Serialization, source)
+    }
+    /// Create a new error indicating that the request couldn't be signed
+    pub fn signing(source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>) -> Self {
+        Self::categorized(ErrorKind::Signing, source)
+    }
+    /// Create a new error indicating that the request couldn't be sent
+    pub fn transport(
+        source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    ) -> Self {
+        Self::categorized(ErrorKind::Transport, source)
+    }
+    /// Create a new error indicating that the response couldn't be deserialized
+    pub fn deserialization(
+        source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    ) -> Self {
+        Self::categorized(ErrorKind::Deserialization, source)
+    }
+    /// Create a new error indicating that an interceptor failed
+    pub fn user(source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>) -> Self {
+        Self::categorized(ErrorKind::User, source)
+    }
+    /// Create a new error indicating a bug in the runtime or its configuration
+    pub fn internal(source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>) -> Self {
+        Self::categorized(ErrorKind::Internal, source)
+    }
+    fn categorized(
+        kind: ErrorKind,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    ) -> Self {
+        Self {
+            kind,
+            detail: ErrorDetail::Other,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
+            source: Some(source.into()),
+        }
+    }
+    /// The category of failure this error represents
+    ///
+    /// Errors raised from a specific hook are categorized as [`ErrorKind::User`], and errors
+    /// caused by accessing data out of turn as [`ErrorKind::Internal`].
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+    /// Create a new error indicating a transient failure within an interceptor
+    ///
+    /// Executions that fail with a retryable error may be retried according to the client's
+    /// retry strategy.
+    pub fn retryable(
+        source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    ) -> Self {
+        Self {
+            kind: ErrorKind::User,
+            detail: ErrorDetail::Other,
+            retryable: true,
+            retry_after: None,
+            status_code: None,
+            source: Some(source.into()),
+        }
+    }
+    /// Create a new error indicating a permanent failure within an interceptor
+    ///
+    /// Executions that fail with a fatal error will never be retried.
+    pub fn fatal(source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>) -> Self {
+        Self {
+            kind: ErrorKind::User,
+            detail: ErrorDetail::Other,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
+            source: Some(source.into()),
+        }
+    }
+    /// Create a new error wrapping every error raised by a hook that runs all interceptors
+    /// even when some of them fail
+    ///
+    /// The aggregated error is only retryable if all of `errors` are retryable.
+    pub fn aggregate(errors: Vec<InterceptorError>) -> Self {
+        Self {
+            retryable: errors.iter().all(InterceptorError::is_retryable),
+            retry_after: None,
+            status_code: None,
+            kind: ErrorKind::User,
+            detail: ErrorDetail::Aggregate(errors),
+            source: None,
+        }
+    }
+    /// Collect the errors from running a hook on several interceptors
+    ///
+    /// Unlike collecting into a `Result`, this consumes every item of `results`, so every
+    /// interceptor is run even if an earlier one fails. A single error is returned as-is, and
+    /// multiple errors are wrapped with [`InterceptorError::aggregate`].
+    pub fn collect(
+        results: impl IntoIterator<Item = Result<(), InterceptorError>>,
+    ) -> Result<(), Self> {
+        let mut errors: Vec<_> = results.into_iter().filter_map(Result::err).collect();
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.remove(0)),
+            _ => Err(Self::aggregate(errors)),
+        }
+    }
+    /// The errors wrapped by an [aggregated](InterceptorError::aggregate) error
+    ///
+    /// Returns an empty slice for all other errors.
+    pub fn errors(&self) -> &[InterceptorError] {
+        match &self.detail {
+            ErrorDetail::Aggregate(errors) => errors,
+            _ => &[],
+        }
+    }
+    /// Convert this error into the errors it [aggregates](InterceptorError::aggregate), or a
+    /// single-element `Vec` if it isn't aggregated
+    pub fn into_errors(self) -> Vec<InterceptorError> {
+        match self.detail {
+            ErrorDetail::Aggregate(errors) => errors,
+            _ => vec![self],
+        }
+    }
+    /// Returns `true` if the execution that raised this error may be retried.
+    ///
+    /// Errors are not retryable unless they were created with [`InterceptorError::retryable`].
+    pub fn is_retryable(&self) -> bool {
+        self.retryable
+    }
+    /// Attach a server-suggested delay to wait before retrying the execution
+    ///
+    /// When set, the retry loop sleeps for at least this long, even if the client's retry strategy
+    /// would back off for less, e.g. for a throttling error that includes a `Retry-After` hint.
+    /// The runtime caps the delay so that a server can't stall the client indefinitely.
+    pub fn with_retry_after(mut self, duration: Duration) -> Self {
+        self.retry_after = Some(duration);
+        self
+    }
+    /// The server-suggested delay to wait before retrying, if one was attached with
+    /// [`InterceptorError::with_retry_after`]
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry_after
+    }
+    /// Attach the HTTP status code of the response that caused this error
+    ///
+    /// This lets an interceptor that rejects a transport response keep its status code, e.g. so
+    /// that a `429 Too Many Requests` is still recognized as [throttling](Self::is_throttling).
+    pub fn with_status_code(mut self, status_code: u16) -> Self {
+        self.status_code = Some(status_code);
+        self
+    }
+    /// The HTTP status code of the response that caused this error, if one was attached with
+    /// [`InterceptorError::with_status_code`]
+    pub fn status_code(&self) -> Option<u16> {
+        self.status_code
+    }
+    /// Returns `true` if this error was caused by the service throttling the request, i.e. it has
+    /// a `429 Too Many Requests` [status code](Self::status_code)
+    ///
+    /// Throttling errors are offered to the retry strategy even if they aren't
+    /// [retryable](Self::is_retryable).
+    pub fn is_throttling(&self) -> bool {
+        self.status_code == Some(429)
+    }
+    /// Create a new error indicating that the checksum of the response body didn't match the
+    /// checksum sent by the service
+    ///
+    /// The body may have been corrupted in transit, so the error is retryable.
+    pub fn response_checksum_mismatch() -> Self {
+        Self {
+            kind: ErrorKind::Deserialization,
+            detail: ErrorDetail::ResponseChecksumMismatch,
+            retryable: true,
+            retry_after: None,
+            status_code: None,
+            source: None,
+        }
+    }
+    /// Create a new error indicating that an interceptor hook ran for longer than `timeout`
+    ///
+    /// See [`InterceptorHookTimeout`](super::InterceptorHookTimeout).
+    pub fn hook_timeout(timeout: Duration) -> Self {
+        Self {
+            kind: ErrorKind::User,
+            detail: ErrorDetail::HookTimeout(timeout),
+            retryable: false,
+            retry_after: None,
+            status_code: None,
+            source: None,
+        }
+    }
     /// Create a new error indicating that an interceptor tried to access the tx_request out of turn
     pub fn invalid_tx_request_access() -> Self {
         Self {
-            kind: ErrorKind::InvalidTxRequestAccess,
+            kind: ErrorKind::Internal,
+            detail: ErrorDetail::InvalidTxRequestAccess,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: None,
         }
     }
     /// Create a new error indicating that an interceptor tried to access the tx_response out of turn
     pub fn invalid_tx_response_access() -> Self {
         Self {
-            kind: ErrorKind::InvalidTxResponseAccess,
+            kind: ErrorKind::Internal,
+            detail: ErrorDetail::InvalidTxResponseAccess,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: None,
         }
     }
     /// Create a new error indicating that an interceptor tried to access the modeled_response out of turn
     pub fn invalid_modeled_response_access() -> Self {
         Self {
-            kind: ErrorKind::InvalidModeledResponseAccess,
+            kind: ErrorKind::Internal,
+            detail: ErrorDetail::InvalidModeledResponseAccess,
+            retryable: false,
+            retry_after: None,
+            status_code: None,
             source: None,
         }
     }
 }
 
 #[derive(Debug)]
-enum ErrorKind {
+enum ErrorDetail {
     /// An error occurred within the read_before_execution interceptor
     ReadBeforeExecution,
     /// An error occurred within the modify_before_serialization interceptor
@@ -252,6 +596,16 @@ enum ErrorKind {
     ModifyBeforeCompletion,
     /// An error occurred within the read_after_execution interceptor
     ReadAfterExecution,
+    /// An error occurred within the modify_before_retry interceptor
+    ModifyBeforeRetry,
+    /// An error occurred within the read_between_attempts interceptor
+    ReadBetweenAttempts,
+    /// An error occurred within the read_on_transport_error interceptor
+    ReadOnTransportError,
+    /// An error that isn't tied to a specific hook, described by its [`ErrorKind`]
+    Other,
+    /// Several interceptors raised errors from the same hook
+    Aggregate(Vec<InterceptorError>),
     // There is no InvalidModeledRequestAccess because it's always accessible
     /// An interceptor tried to access the tx_request out of turn
     InvalidTxRequestAccess,
@@ -259,12 +613,16 @@ enum ErrorKind {
     InvalidTxResponseAccess,
     /// An interceptor tried to access the modeled_response out of turn
     InvalidModeledResponseAccess,
+    /// The response body's checksum didn't match the checksum sent by the service
+    ResponseChecksumMismatch,
+    /// An interceptor hook ran for longer than the configured timeout
+    HookTimeout(Duration),
 }
 
 impl fmt::Display for InterceptorError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use ErrorKind::*;
-        match &self.kind {
+        use ErrorDetail::*;
+        match &self.detail {
             ReadBeforeExecution => {
                 write!(f, "read_before_execution interceptor encountered an error")
             }
@@ -321,6 +679,27 @@ impl fmt::Display for InterceptorError {
             ReadAfterExecution => {
                 write!(f, "read_after_execution interceptor encountered an error")
             }
+            ModifyBeforeRetry => {
+                write!(f, "modify_before_retry interceptor encountered an error")
+            }
+            ReadBetweenAttempts => {
+                write!(f, "read_between_attempts interceptor encountered an error")
+            }
+            ReadOnTransportError => {
+                write!(
+                    f,
+                    "read_on_transport_error interceptor encountered an error"
+                )
+            }
+            Other => match self.kind {
+                ErrorKind::Serialization => write!(f, "failed to serialize the request"),
+                ErrorKind::Signing => write!(f, "failed to sign the request"),
+                ErrorKind::Transport => write!(f, "failed to send the request"),
+                ErrorKind::Deserialization => write!(f, "failed to deserialize the response"),
+                ErrorKind::User => write!(f, "an interceptor encountered an error"),
+                ErrorKind::Internal => write!(f, "an internal error occurred"),
+            },
+            Aggregate(errors) => write!(f, "{} interceptors encountered errors", errors.len()),
             InvalidTxRequestAccess => {
                 write!(f, "tried to access tx_request before request serialization")
             }
@@ -332,6 +711,15 @@ impl fmt::Display for InterceptorError {
                 f,
                 "tried to access modeled_response before response deserialization"
             ),
+            ResponseChecksumMismatch => write!(
+                f,
+                "the response checksum didn't match the checksum sent by the service"
+            ),
+            HookTimeout(timeout) => write!(
+                f,
+                "an interceptor hook took longer than its timeout of {:?}",
+                timeout
+            ),
         }
     }
 }
@@ -341,3 +729,73 @@ impl std::error::Error for InterceptorError {
         self.source.as_ref().map(|err| err.as_ref() as _)
     }
 }
+
+/// The error returned by an execution that an interceptor
+/// [cancelled](super::InterceptorContext::cancel).
+#[derive(Debug)]
+pub struct CancelledError {
+    reason: CancellationReason,
+}
+
+impl CancelledError {
+    /// Create a new error for an execution cancelled for the given `reason`
+    pub fn new(reason: CancellationReason) -> Self {
+        Self { reason }
+    }
+
+    /// The reason the execution was cancelled
+    pub fn reason(&self) -> &CancellationReason {
+        &self.reason
+    }
+}
+
+impl fmt::Display for CancelledError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the execution was cancelled: {}", self.reason)
+    }
+}
+
+impl std::error::Error for CancelledError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{ErrorKind, InterceptorError};
+    use std::time::Duration;
+
+    #[test]
+    fn kinds() {
+        let err = InterceptorError::signing("no credentials");
+        assert_eq!(err.kind(), ErrorKind::Signing);
+        assert_eq!(err.to_string(), "failed to sign the request");
+        assert_eq!(
+            InterceptorError::read_before_signing("oops").kind(),
+            ErrorKind::User
+        );
+        assert_eq!(
+            InterceptorError::invalid_tx_response_access().kind(),
+            ErrorKind::Internal
+        );
+    }
+
+    #[test]
+    fn retry_after() {
+        let err = InterceptorError::retryable("throttled");
+        assert_eq!(err.retry_after(), None);
+        let err = err.with_retry_after(Duration::from_secs(2));
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(2)));
+        assert!(err.is_retryable());
+    }
+
+    #[test]
+    fn status_code() {
+        let err = InterceptorError::read_after_transmit("rejected");
+        assert_eq!(err.status_code(), None);
+        assert!(!err.is_throttling());
+        let err = err.with_status_code(503);
+        assert_eq!(err.status_code(), Some(503));
+        assert!(!err.is_throttling());
+        assert!(InterceptorError::read_after_transmit("rejected")
+            .with_status_code(429)
+            .is_throttling());
+    }
+}
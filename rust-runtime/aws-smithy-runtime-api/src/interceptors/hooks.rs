@@ -0,0 +1,296 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Types used to declare which hooks an interceptor implements
+
+use std::fmt;
+use std::ops::{BitOr, BitOrAssign};
+
+macro_rules! hook_set {
+    ($($(#[$meta:meta])* $name:ident = $bit:expr => $hook:literal,)+) => {
+        impl InterceptorHookSet {
+            $(
+                $(#[$meta])*
+                pub const $name: Self = Self(1 << $bit);
+            )+
+
+            /// A set containing every hook.
+            pub const ALL: Self = Self($(Self::$name.0)|+);
+
+            const NAMES: &'static [(Self, &'static str)] = &[$((Self::$name, $hook)),+];
+        }
+    };
+}
+
+/// A set of interceptor hooks.
+///
+/// An [`Interceptor`](super::Interceptor) returns this set from
+/// [`Interceptor::hooks`](super::Interceptor::hooks) to declare which hooks it implements.
+/// [`Interceptors`](super::Interceptors) will only dispatch to an interceptor for hooks that are
+/// members of its set. The [default](Default::default) set is [empty](Self::empty).
+///
+/// Sets can be combined with `|`:
+/// ```
+/// use aws_smithy_runtime_api::interceptors::InterceptorHookSet;
+/// let hooks = InterceptorHookSet::READ_BEFORE_ATTEMPT | InterceptorHookSet::READ_AFTER_ATTEMPT;
+/// assert!(hooks.contains(InterceptorHookSet::READ_AFTER_ATTEMPT));
+/// assert!(!hooks.contains(InterceptorHookSet::READ_AFTER_EXECUTION));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InterceptorHookSet(u32);
+
+hook_set! {
+    /// The `read_before_execution` hook
+    READ_BEFORE_EXECUTION = 0 => "read_before_execution",
+    /// The `modify_before_serialization` hook
+    MODIFY_BEFORE_SERIALIZATION = 1 => "modify_before_serialization",
+    /// The `read_before_serialization` hook
+    READ_BEFORE_SERIALIZATION = 2 => "read_before_serialization",
+    /// The `read_after_serialization` hook
+    READ_AFTER_SERIALIZATION = 3 => "read_after_serialization",
+    /// The `modify_before_retry_loop` hook
+    MODIFY_BEFORE_RETRY_LOOP = 4 => "modify_before_retry_loop",
+    /// The `read_before_attempt` hook
+    READ_BEFORE_ATTEMPT = 5 => "read_before_attempt",
+    /// The `modify_before_signing` hook
+    MODIFY_BEFORE_SIGNING = 6 => "modify_before_signing",
+    /// The `read_before_signing` hook
+    READ_BEFORE_SIGNING = 7 => "read_before_signing",
+    /// The `read_after_signing` hook
+    READ_AFTER_SIGNING = 8 => "read_after_signing",
+    /// The `modify_before_transmit` hook
+    MODIFY_BEFORE_TRANSMIT = 9 => "modify_before_transmit",
+    /// The `read_before_transmit` hook
+    READ_BEFORE_TRANSMIT = 10 => "read_before_transmit",
+    /// The `read_after_transmit` hook
+    READ_AFTER_TRANSMIT = 11 => "read_after_transmit",
+    /// The `modify_before_deserialization` hook
+    MODIFY_BEFORE_DESERIALIZATION = 12 => "modify_before_deserialization",
+    /// The `read_before_deserialization` hook
+    READ_BEFORE_DESERIALIZATION = 13 => "read_before_deserialization",
+    /// The `read_after_deserialization` hook
+    READ_AFTER_DESERIALIZATION = 14 => "read_after_deserialization",
+    /// The `modify_before_attempt_completion` hook
+    MODIFY_BEFORE_ATTEMPT_COMPLETION = 15 => "modify_before_attempt_completion",
+    /// The `read_after_attempt` hook
+    READ_AFTER_ATTEMPT = 16 => "read_after_attempt",
+    /// The `modify_before_completion` hook
+    MODIFY_BEFORE_COMPLETION = 17 => "modify_before_completion",
+    /// The `read_after_execution` hook
+    READ_AFTER_EXECUTION = 18 => "read_after_execution",
+    /// The `modify_before_retry` hook
+    MODIFY_BEFORE_RETRY = 19 => "modify_before_retry",
+    /// The `read_between_attempts` hook
+    READ_BETWEEN_ATTEMPTS = 20 => "read_between_attempts",
+    /// The `read_on_transport_error` hook
+    READ_ON_TRANSPORT_ERROR = 21 => "read_on_transport_error",
+}
+
+impl InterceptorHookSet {
+    /// A set containing no hooks.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Returns `true` if every hook in `other` is also in this set.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns `true` if this set contains no hooks.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl Default for InterceptorHookSet {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl BitOr for InterceptorHookSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for InterceptorHookSet {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0
+    }
+}
+
+impl fmt::Debug for InterceptorHookSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries(
+                Self::NAMES
+                    .iter()
+                    .filter(|(hook, _)| self.contains(*hook))
+                    .map(|(_, name)| name),
+            )
+            .finish()
+    }
+}
+
+/// A hook that can be run with [`Interceptors::try_run_hook`](super::Interceptors::try_run_hook).
+///
+/// This covers every hook that only needs the context and the config bag. `modify_before_retry`,
+/// `read_between_attempts` and `read_on_transport_error` take additional arguments, so they are
+/// only run with their dedicated methods.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum HookPhase {
+    /// The `read_before_execution` hook
+    ReadBeforeExecution,
+    /// The `modify_before_serialization` hook
+    ModifyBeforeSerialization,
+    /// The `read_before_serialization` hook
+    ReadBeforeSerialization,
+    /// The `read_after_serialization` hook
+    ReadAfterSerialization,
+    /// The `modify_before_retry_loop` hook
+    ModifyBeforeRetryLoop,
+    /// The `read_before_attempt` hook
+    ReadBeforeAttempt,
+    /// The `modify_before_signing` hook
+    ModifyBeforeSigning,
+    /// The `read_before_signing` hook
+    ReadBeforeSigning,
+    /// The `read_after_signing` hook
+    ReadAfterSigning,
+    /// The `modify_before_transmit` hook
+    ModifyBeforeTransmit,
+    /// The `read_before_transmit` hook
+    ReadBeforeTransmit,
+    /// The `read_after_transmit` hook
+    ReadAfterTransmit,
+    /// The `modify_before_deserialization` hook
+    ModifyBeforeDeserialization,
+    /// The `read_before_deserialization` hook
+    ReadBeforeDeserialization,
+    /// The `read_after_deserialization` hook
+    ReadAfterDeserialization,
+    /// The `modify_before_attempt_completion` hook
+    ModifyBeforeAttemptCompletion,
+    /// The `read_after_attempt` hook
+    ReadAfterAttempt,
+    /// The `modify_before_completion` hook
+    ModifyBeforeCompletion,
+    /// The `read_after_execution` hook
+    ReadAfterExecution,
+}
+
+impl HookPhase {
+    /// Every phase, in the order the hooks run during an execution with a single attempt.
+    pub const ALL: [Self; 19] = [
+        Self::ReadBeforeExecution,
+        Self::ModifyBeforeSerialization,
+        Self::ReadBeforeSerialization,
+        Self::ReadAfterSerialization,
+        Self::ModifyBeforeRetryLoop,
+        Self::ReadBeforeAttempt,
+        Self::ModifyBeforeSigning,
+        Self::ReadBeforeSigning,
+        Self::ReadAfterSigning,
+        Self::ModifyBeforeTransmit,
+        Self::ReadBeforeTransmit,
+        Self::ReadAfterTransmit,
+        Self::ModifyBeforeDeserialization,
+        Self::ReadBeforeDeserialization,
+        Self::ReadAfterDeserialization,
+        Self::ModifyBeforeAttemptCompletion,
+        Self::ReadAfterAttempt,
+        Self::ModifyBeforeCompletion,
+        Self::ReadAfterExecution,
+    ];
+
+    /// The name of the phase's hook, e.g. `read_before_attempt`.
+    pub fn name(self) -> &'static str {
+        let hook = self.hook_set();
+        InterceptorHookSet::NAMES
+            .iter()
+            .find(|(other, _)| *other == hook)
+            .map(|(_, name)| *name)
+            .expect("every phase has a hook")
+    }
+
+    /// The phase's hook as an [`InterceptorHookSet`].
+    pub const fn hook_set(self) -> InterceptorHookSet {
+        match self {
+            Self::ReadBeforeExecution => InterceptorHookSet::READ_BEFORE_EXECUTION,
+            Self::ModifyBeforeSerialization => InterceptorHookSet::MODIFY_BEFORE_SERIALIZATION,
+            Self::ReadBeforeSerialization => InterceptorHookSet::READ_BEFORE_SERIALIZATION,
+            Self::ReadAfterSerialization => InterceptorHookSet::READ_AFTER_SERIALIZATION,
+            Self::ModifyBeforeRetryLoop => InterceptorHookSet::MODIFY_BEFORE_RETRY_LOOP,
+            Self::ReadBeforeAttempt => InterceptorHookSet::READ_BEFORE_ATTEMPT,
+            Self::ModifyBeforeSigning => InterceptorHookSet::MODIFY_BEFORE_SIGNING,
+            Self::ReadBeforeSigning => InterceptorHookSet::READ_BEFORE_SIGNING,
+            Self::ReadAfterSigning => InterceptorHookSet::READ_AFTER_SIGNING,
+            Self::ModifyBeforeTransmit => InterceptorHookSet::MODIFY_BEFORE_TRANSMIT,
+            Self::ReadBeforeTransmit => InterceptorHookSet::READ_BEFORE_TRANSMIT,
+            Self::ReadAfterTransmit => InterceptorHookSet::READ_AFTER_TRANSMIT,
+            Self::ModifyBeforeDeserialization => InterceptorHookSet::MODIFY_BEFORE_DESERIALIZATION,
+            Self::ReadBeforeDeserialization => InterceptorHookSet::READ_BEFORE_DESERIALIZATION,
+            Self::ReadAfterDeserialization => InterceptorHookSet::READ_AFTER_DESERIALIZATION,
+            Self::ModifyBeforeAttemptCompletion => {
+                InterceptorHookSet::MODIFY_BEFORE_ATTEMPT_COMPLETION
+            }
+            Self::ReadAfterAttempt => InterceptorHookSet::READ_AFTER_ATTEMPT,
+            Self::ModifyBeforeCompletion => InterceptorHookSet::MODIFY_BEFORE_COMPLETION,
+            Self::ReadAfterExecution => InterceptorHookSet::READ_AFTER_EXECUTION,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HookPhase, InterceptorHookSet};
+
+    #[test]
+    fn all_contains_every_hook() {
+        for (hook, _) in InterceptorHookSet::NAMES {
+            assert!(InterceptorHookSet::ALL.contains(*hook));
+            assert!(!InterceptorHookSet::empty().contains(*hook));
+        }
+        assert_eq!(InterceptorHookSet::NAMES.len(), 22);
+    }
+
+    #[test]
+    fn default_is_empty() {
+        assert!(InterceptorHookSet::default().is_empty());
+        assert_eq!(InterceptorHookSet::default(), InterceptorHookSet::empty());
+    }
+
+    #[test]
+    fn phases_map_to_distinct_hooks() {
+        let mut hooks = InterceptorHookSet::empty();
+        for phase in HookPhase::ALL {
+            assert!(!hooks.contains(phase.hook_set()));
+            hooks |= phase.hook_set();
+        }
+        // Only the hooks that take additional arguments are missing
+        assert_eq!(
+            hooks
+                | InterceptorHookSet::MODIFY_BEFORE_RETRY
+                | InterceptorHookSet::READ_BETWEEN_ATTEMPTS
+                | InterceptorHookSet::READ_ON_TRANSPORT_ERROR,
+            InterceptorHookSet::ALL
+        );
+    }
+
+    #[test]
+    fn debug_lists_hook_names() {
+        let hooks =
+            InterceptorHookSet::READ_BEFORE_ATTEMPT | InterceptorHookSet::READ_AFTER_EXECUTION;
+        assert_eq!(
+            format!("{:?}", hooks),
+            r#"{"read_before_attempt", "read_after_execution"}"#
+        );
+    }
+}
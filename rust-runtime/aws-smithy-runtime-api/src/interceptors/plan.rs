@@ -0,0 +1,132 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A description of the interceptors that will run at each hook, for debugging
+
+use super::HookPhase;
+use std::fmt;
+use std::time::Duration;
+
+/// Whether an interceptor was registered for the client or for a single operation.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InterceptorKind {
+    /// The interceptor runs for every operation invoked by the client, including interceptors
+    /// produced by factories.
+    Client,
+    /// The interceptor runs only for the operation being invoked.
+    Operation,
+}
+
+/// An interceptor that will run at a [`HookStep`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InterceptorInfo {
+    name: &'static str,
+    kind: InterceptorKind,
+    timeout: Option<Duration>,
+}
+
+impl InterceptorInfo {
+    pub(crate) fn new(
+        name: &'static str,
+        kind: InterceptorKind,
+        timeout: Option<Duration>,
+    ) -> Self {
+        Self {
+            name,
+            kind,
+            timeout,
+        }
+    }
+
+    /// The [name](super::Interceptor::name) of the interceptor.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Whether the interceptor is a client or an operation interceptor.
+    pub fn kind(&self) -> InterceptorKind {
+        self.kind
+    }
+
+    /// How long the interceptor's hook may run, if it's limited.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+}
+
+/// The interceptors that will run at a single hook, in the order they'll run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HookStep {
+    hook: HookPhase,
+    interceptors: Vec<InterceptorInfo>,
+}
+
+impl HookStep {
+    pub(crate) fn new(hook: HookPhase, interceptors: Vec<InterceptorInfo>) -> Self {
+        Self { hook, interceptors }
+    }
+
+    /// The hook this step describes.
+    pub fn hook(&self) -> HookPhase {
+        self.hook
+    }
+
+    /// The interceptors that implement the hook, in the order they'll run.
+    pub fn interceptors(&self) -> &[InterceptorInfo] {
+        &self.interceptors
+    }
+}
+
+/// A description of which interceptors will run at each hook, created with
+/// [`Interceptors::debug_execution_plan`](super::Interceptors::debug_execution_plan).
+///
+/// The `Display` implementation lists every hook that has interceptors, one per line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExecutionPlan {
+    steps: Vec<HookStep>,
+}
+
+impl ExecutionPlan {
+    pub(crate) fn new(steps: Vec<HookStep>) -> Self {
+        Self { steps }
+    }
+
+    /// A step for every [`HookPhase`], in the order the hooks run.
+    pub fn steps(&self) -> &[HookStep] {
+        &self.steps
+    }
+
+    /// The step for `hook`.
+    pub fn step(&self, hook: HookPhase) -> Option<&HookStep> {
+        self.steps.iter().find(|step| step.hook == hook)
+    }
+}
+
+impl fmt::Display for ExecutionPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in self
+            .steps
+            .iter()
+            .filter(|step| !step.interceptors.is_empty())
+        {
+            write!(f, "{}:", step.hook.name())?;
+            for (i, interceptor) in step.interceptors.iter().enumerate() {
+                let separator = if i == 0 { " " } else { ", " };
+                let kind = match interceptor.kind {
+                    InterceptorKind::Client => "client",
+                    InterceptorKind::Operation => "operation",
+                };
+                write!(f, "{}{} ({}", separator, interceptor.name, kind)?;
+                if let Some(timeout) = interceptor.timeout {
+                    write!(f, ", timeout {:?}", timeout)?;
+                }
+                write!(f, ")")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -18,6 +18,8 @@ pub mod config_bag;
 ///
 /// Interceptors are lifecycle hooks that can read/modify requests and responses.
 pub mod interceptors;
+/// Identifiers for Smithy protocols.
+pub mod protocol;
 /// Smithy code related to retry handling and token bucket.
 ///
 /// This code defines when and how failed requests should be retried. It also defines the behavior
@@ -0,0 +1,41 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::fmt;
+
+/// The Smithy shape ID of a protocol, e.g. `aws.protocols#restJson1`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ProtocolId(&'static str);
+
+impl ProtocolId {
+    /// The `aws.protocols#restJson1` protocol
+    pub const REST_JSON_1: Self = Self("aws.protocols#restJson1");
+    /// The `aws.protocols#restXml` protocol
+    pub const REST_XML: Self = Self("aws.protocols#restXml");
+    /// The `aws.protocols#awsJson1_0` protocol
+    pub const AWS_JSON_1_0: Self = Self("aws.protocols#awsJson1_0");
+    /// The `aws.protocols#awsJson1_1` protocol
+    pub const AWS_JSON_1_1: Self = Self("aws.protocols#awsJson1_1");
+    /// The `aws.protocols#awsQuery` protocol
+    pub const AWS_QUERY: Self = Self("aws.protocols#awsQuery");
+    /// The `aws.protocols#ec2Query` protocol
+    pub const EC2_QUERY: Self = Self("aws.protocols#ec2Query");
+
+    /// Create a `ProtocolId` from a protocol's shape ID.
+    pub const fn new(id: &'static str) -> Self {
+        Self(id)
+    }
+
+    /// The protocol's shape ID.
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl fmt::Display for ProtocolId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
@@ -7,8 +7,83 @@ use crate::config_bag::ConfigBag;
 
 type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
 
+/// A runtime plugin that another plugin needs to be applied first
+///
+/// Returned from [`RuntimePlugin::depends_on`], and matched against the
+/// [names](RuntimePlugin::name) of the registered plugins.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PluginDependency {
+    plugin_name: &'static str,
+    required: bool,
+}
+
+impl PluginDependency {
+    /// A dependency on a plugin that must be registered
+    pub fn required(plugin_name: &'static str) -> Self {
+        Self {
+            plugin_name,
+            required: true,
+        }
+    }
+
+    /// A dependency on a plugin that only needs to be applied first if it's registered
+    pub fn optional(plugin_name: &'static str) -> Self {
+        Self {
+            plugin_name,
+            required: false,
+        }
+    }
+
+    /// The name of the plugin that's depended on
+    pub fn plugin_name(&self) -> &'static str {
+        self.plugin_name
+    }
+
+    /// Returns `true` if applying the plugins fails when the depended-on plugin isn't registered
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+}
+
 pub trait RuntimePlugin {
     fn configure(&self, cfg: &mut ConfigBag) -> Result<(), BoxError>;
+
+    /// The name of this plugin, used to refer to it in a [`PluginDependency`].
+    ///
+    /// Defaults to the type name of the plugin.
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
+    /// The plugins that must be applied before this one, e.g. a credentials plugin for a signing
+    /// plugin.
+    ///
+    /// Plugins are applied in registration order unless a dependency requires otherwise. Client
+    /// plugins are applied before all operation plugins, so an operation plugin's dependency on a
+    /// client plugin is always satisfied. Defaults to no dependencies.
+    fn depends_on(&self) -> Vec<PluginDependency> {
+        Vec::new()
+    }
+
+    /// Check the configuration once every plugin has been applied.
+    ///
+    /// This runs after all client and operation plugins have [configured](RuntimePlugin::configure)
+    /// the bag, so plugins can check for required values, valid ranges, and compatibility with
+    /// config contributed by other plugins. An error fails the operation before a request is sent.
+    /// Defaults to accepting any configuration.
+    fn validate(&self, cfg: &ConfigBag) -> Result<(), BoxError> {
+        let _cfg = cfg;
+        Ok(())
+    }
+
+    /// Release any resources acquired by [`configure`](RuntimePlugin::configure).
+    ///
+    /// Plugins are torn down in the reverse of the order they were registered in. Defaults to
+    /// doing nothing.
+    fn teardown(&self, cfg: &mut ConfigBag) -> Result<(), BoxError> {
+        let _cfg = cfg;
+        Ok(())
+    }
 }
 
 impl<T> From<T> for Box<dyn RuntimePlugin>
@@ -47,27 +122,167 @@ impl RuntimePlugins {
         self
     }
 
+    /// Apply the client plugins, ordered by their [dependencies](RuntimePlugin::depends_on).
+    ///
+    /// Fails without applying any plugin if a required dependency isn't registered or the
+    /// dependencies form a cycle.
     pub fn apply_client_configuration(&self, cfg: &mut ConfigBag) -> Result<(), BoxError> {
-        for plugin in self.client_plugins.iter() {
+        for plugin in order_by_dependencies(&self.client_plugins, &[])? {
             plugin.configure(cfg)?;
         }
 
         Ok(())
     }
 
+    /// Apply the operation plugins, ordered by their [dependencies](RuntimePlugin::depends_on).
+    ///
+    /// The client plugins are assumed to have been applied already.
     pub fn apply_operation_configuration(&self, cfg: &mut ConfigBag) -> Result<(), BoxError> {
-        for plugin in self.operation_plugins.iter() {
+        let client_plugins: Vec<_> = self.client_plugins.iter().map(|p| p.name()).collect();
+        for plugin in order_by_dependencies(&self.operation_plugins, &client_plugins)? {
             plugin.configure(cfg)?;
         }
 
         Ok(())
     }
+
+    /// [Validate](RuntimePlugin::validate) the configuration with every plugin, client plugins
+    /// first, each in registration order.
+    ///
+    /// Returns the first error encountered, naming the plugin that rejected the configuration.
+    pub fn validate(&self, cfg: &ConfigBag) -> Result<(), BoxError> {
+        for plugin in self.client_plugins.iter().chain(&self.operation_plugins) {
+            plugin.validate(cfg).map_err(|err| {
+                format!(
+                    "runtime plugin `{}` rejected the configuration: {}",
+                    plugin.name(),
+                    err
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Tear down all plugins, operation plugins first, each in reverse-registration order.
+    ///
+    /// Every plugin is torn down even if an earlier one fails. The first error encountered is
+    /// returned.
+    pub fn teardown(&self, cfg: &mut ConfigBag) -> Result<(), BoxError> {
+        let mut result = Ok(());
+        for plugin in self
+            .operation_plugins
+            .iter()
+            .rev()
+            .chain(self.client_plugins.iter().rev())
+        {
+            let plugin_result = plugin.teardown(cfg);
+            if result.is_ok() {
+                result = plugin_result;
+            }
+        }
+        result
+    }
+
+    /// Wrap these plugins and the config they were applied to so that the plugins are torn down
+    /// when the returned guard is dropped.
+    pub fn teardown_on_drop(self, cfg: ConfigBag) -> TeardownOnDrop {
+        TeardownOnDrop { plugins: self, cfg }
+    }
+}
+
+/// Order `plugins` so that every plugin comes after the plugins it depends on, keeping the
+/// registration order otherwise.
+///
+/// `applied` names the plugins that have already been applied, which satisfy dependencies without
+/// being part of the ordering.
+fn order_by_dependencies<'a>(
+    plugins: &'a [Box<dyn RuntimePlugin>],
+    applied: &[&'static str],
+) -> Result<Vec<&'a dyn RuntimePlugin>, BoxError> {
+    let is_registered =
+        |name| applied.contains(&name) || plugins.iter().any(|plugin| plugin.name() == name);
+    for plugin in plugins {
+        if let Some(missing) = plugin
+            .depends_on()
+            .into_iter()
+            .find(|dep| dep.is_required() && !is_registered(dep.plugin_name()))
+        {
+            return Err(format!(
+                "runtime plugin `{}` depends on `{}`, which isn't registered",
+                plugin.name(),
+                missing.plugin_name()
+            )
+            .into());
+        }
+    }
+
+    let mut done = applied.to_vec();
+    let mut pending: Vec<&dyn RuntimePlugin> = plugins.iter().map(|p| p.as_ref()).collect();
+    let mut ordered = Vec::with_capacity(pending.len());
+    while !pending.is_empty() {
+        // Unregistered optional dependencies are ignored
+        let next = pending.iter().position(|plugin| {
+            plugin
+                .depends_on()
+                .iter()
+                .all(|dep| done.contains(&dep.plugin_name()) || !is_registered(dep.plugin_name()))
+        });
+        let plugin = match next {
+            Some(index) => pending.remove(index),
+            None => {
+                let names: Vec<_> = pending.iter().map(|plugin| plugin.name()).collect();
+                return Err(format!(
+                    "runtime plugins have a dependency cycle: {}",
+                    names.join(", ")
+                )
+                .into());
+            }
+        };
+        done.push(plugin.name());
+        ordered.push(plugin);
+    }
+    Ok(ordered)
+}
+
+/// Tears down the [`RuntimePlugins`] it holds when dropped.
+///
+/// Created by [`RuntimePlugins::teardown_on_drop`]. Errors raised while tearing down plugins on
+/// drop are discarded; call [`TeardownOnDrop::close`] instead to observe them.
+pub struct TeardownOnDrop {
+    plugins: RuntimePlugins,
+    cfg: ConfigBag,
+}
+
+impl TeardownOnDrop {
+    /// The plugins that will be torn down.
+    pub fn plugins(&self) -> &RuntimePlugins {
+        &self.plugins
+    }
+
+    /// The config that will be passed to each plugin's `teardown`.
+    pub fn cfg_mut(&mut self) -> &mut ConfigBag {
+        &mut self.cfg
+    }
+
+    /// Tear down the plugins now, returning the first error encountered.
+    pub fn close(mut self) -> Result<(), BoxError> {
+        let plugins = std::mem::take(&mut self.plugins);
+        plugins.teardown(&mut self.cfg)
+    }
+}
+
+impl Drop for TeardownOnDrop {
+    fn drop(&mut self) {
+        // Drop can't report errors, so they're discarded
+        let _ = self.plugins.teardown(&mut self.cfg);
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{BoxError, RuntimePlugin, RuntimePlugins};
+    use super::{BoxError, PluginDependency, RuntimePlugin, RuntimePlugins};
     use crate::config_bag::ConfigBag;
+    use std::sync::{Arc, Mutex};
 
     struct SomeStruct;
 
@@ -82,4 +297,175 @@ mod tests {
         let mut rps = RuntimePlugins::new();
         rps.with_client_plugin(SomeStruct);
     }
+
+    struct TeardownRecorder {
+        name: &'static str,
+        torn_down: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl RuntimePlugin for TeardownRecorder {
+        fn configure(&self, _cfg: &mut ConfigBag) -> Result<(), BoxError> {
+            Ok(())
+        }
+
+        fn teardown(&self, _cfg: &mut ConfigBag) -> Result<(), BoxError> {
+            self.torn_down.lock().unwrap().push(self.name);
+            Err(self.name.into())
+        }
+    }
+
+    fn plugins(torn_down: &Arc<Mutex<Vec<&'static str>>>) -> RuntimePlugins {
+        let plugin = |name| TeardownRecorder {
+            name,
+            torn_down: torn_down.clone(),
+        };
+        let mut rps = RuntimePlugins::new();
+        rps.with_client_plugin(plugin("client 1"))
+            .with_client_plugin(plugin("client 2"))
+            .with_operation_plugin(plugin("operation"));
+        rps
+    }
+
+    #[test]
+    fn teardown_runs_in_reverse_registration_order() {
+        let torn_down = Arc::default();
+        let err = plugins(&torn_down)
+            .teardown(&mut ConfigBag::base())
+            .unwrap_err();
+
+        assert_eq!(err.to_string(), "operation");
+        assert_eq!(
+            *torn_down.lock().unwrap(),
+            vec!["operation", "client 2", "client 1"]
+        );
+    }
+
+    #[test]
+    fn teardown_on_drop() {
+        let torn_down = Arc::default();
+        let guard = plugins(&torn_down).teardown_on_drop(ConfigBag::base());
+        assert!(torn_down.lock().unwrap().is_empty());
+        drop(guard);
+        assert_eq!(torn_down.lock().unwrap().len(), 3);
+
+        let torn_down = Arc::default();
+        let guard = plugins(&torn_down).teardown_on_drop(ConfigBag::base());
+        assert!(guard.close().is_err());
+        // Closing tears the plugins down exactly once
+        assert_eq!(torn_down.lock().unwrap().len(), 3);
+    }
+
+    struct RequiresRegion;
+
+    impl RuntimePlugin for RequiresRegion {
+        fn configure(&self, _cfg: &mut ConfigBag) -> Result<(), BoxError> {
+            Ok(())
+        }
+
+        fn name(&self) -> &'static str {
+            "requires-region"
+        }
+
+        fn validate(&self, cfg: &ConfigBag) -> Result<(), BoxError> {
+            cfg.get::<&'static str>().ok_or("no region is configured")?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn validate_runs_after_configuration() {
+        let mut rps = RuntimePlugins::new();
+        rps.with_client_plugin(RequiresRegion);
+        let err = rps.validate(&ConfigBag::base()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "runtime plugin `requires-region` rejected the configuration: no region is configured"
+        );
+
+        let mut cfg = ConfigBag::base();
+        cfg.put("us-east-1");
+        rps.validate(&cfg).unwrap();
+    }
+
+    struct DependentPlugin {
+        name: &'static str,
+        depends_on: Vec<PluginDependency>,
+        applied: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl RuntimePlugin for DependentPlugin {
+        fn configure(&self, _cfg: &mut ConfigBag) -> Result<(), BoxError> {
+            self.applied.lock().unwrap().push(self.name);
+            Ok(())
+        }
+
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn depends_on(&self) -> Vec<PluginDependency> {
+            self.depends_on.clone()
+        }
+    }
+
+    fn dependent_plugins(
+        plugins: &[(&'static str, Vec<PluginDependency>)],
+    ) -> (RuntimePlugins, Arc<Mutex<Vec<&'static str>>>) {
+        let applied = Arc::<Mutex<Vec<_>>>::default();
+        let mut rps = RuntimePlugins::new();
+        for (name, depends_on) in plugins {
+            rps.with_client_plugin(DependentPlugin {
+                name,
+                depends_on: depends_on.clone(),
+                applied: applied.clone(),
+            });
+        }
+        (rps, applied)
+    }
+
+    #[test]
+    fn plugins_are_applied_after_their_dependencies() {
+        let (rps, applied) = dependent_plugins(&[
+            ("signing", vec![PluginDependency::required("credentials")]),
+            ("logging", vec![PluginDependency::optional("metrics")]),
+            ("credentials", vec![]),
+        ]);
+        rps.apply_client_configuration(&mut ConfigBag::base())
+            .unwrap();
+        assert_eq!(
+            *applied.lock().unwrap(),
+            vec!["logging", "credentials", "signing"]
+        );
+    }
+
+    #[test]
+    fn missing_required_dependencies_are_an_error() {
+        let (rps, applied) =
+            dependent_plugins(&[("signing", vec![PluginDependency::required("credentials")])]);
+        let err = rps
+            .apply_client_configuration(&mut ConfigBag::base())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "runtime plugin `signing` depends on `credentials`, which isn't registered"
+        );
+        assert!(applied.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn dependency_cycles_are_an_error() {
+        let (rps, applied) = dependent_plugins(&[
+            ("a", vec![PluginDependency::required("b")]),
+            ("b", vec![PluginDependency::required("a")]),
+            ("c", vec![]),
+        ]);
+        let err = rps
+            .apply_client_configuration(&mut ConfigBag::base())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "runtime plugins have a dependency cycle: a, b"
+        );
+        assert!(applied.lock().unwrap().is_empty());
+    }
 }
@@ -0,0 +1,33 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+pub mod body_hash;
+pub mod checksum;
+pub mod circuit_breaker;
+pub mod correlation_id;
+pub mod execution_tracing;
+pub mod metrics;
+pub mod rate_limit;
+pub mod request_caching;
+pub mod request_id;
+pub mod signing;
+pub mod timeout;
+pub mod trace_propagation;
+
+pub use body_hash::{RequestBodyHash, RequestBodyHashInterceptor};
+pub use checksum::ChecksumInterceptor;
+pub use circuit_breaker::CircuitBreakerInterceptor;
+pub use correlation_id::CorrelationIdInterceptor;
+pub use execution_tracing::{ExecutionSpan, ExecutionTracingInterceptor};
+pub use metrics::MetricsInterceptor;
+pub use rate_limit::{
+    AsyncRateLimitInterceptor, AttemptRateLimit, RateLimitExceeded, RateLimitInterceptor,
+    TokenBucket,
+};
+pub use request_caching::RequestCachingInterceptor;
+pub use request_id::RequestIdInterceptor;
+pub use signing::{HeaderSigningInterceptor, SignatureAlgorithm};
+pub use timeout::TimeoutInterceptor;
+pub use trace_propagation::{TraceHeaderFormat, TracingPropagationInterceptor};
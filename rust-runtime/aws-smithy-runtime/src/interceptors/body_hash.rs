@@ -0,0 +1,126 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An interceptor that computes the request body's SHA-256 hash once per execution.
+
+use aws_smithy_http::body::SdkBody;
+use aws_smithy_runtime_api::config_bag::ConfigBag;
+use aws_smithy_runtime_api::interceptors::{
+    Interceptor, InterceptorContext, InterceptorError, InterceptorHookSet,
+};
+use sha2::{Digest, Sha256};
+
+/// The hex-encoded SHA-256 hash of the request body, stored in the config bag by a
+/// [`RequestBodyHashInterceptor`].
+///
+/// Signers should use this hash when it's present instead of hashing the body again.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequestBodyHash(String);
+
+impl RequestBodyHash {
+    /// Hash `body`.
+    pub fn compute(body: &[u8]) -> Self {
+        Self(hex::encode(Sha256::digest(body)))
+    }
+
+    /// The hex-encoded hash.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// An interceptor that hashes the request body in `read_before_signing` and stores the result in
+/// the config bag as a [`RequestBodyHash`].
+///
+/// The hash is only computed for the first attempt; retries reuse it, which avoids hashing large
+/// buffered bodies again. Streaming bodies can't be hashed up front, so no hash is stored for
+/// them.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct RequestBodyHashInterceptor;
+
+impl RequestBodyHashInterceptor {
+    /// Create a new `RequestBodyHashInterceptor`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<ModReq, TxRes, ModRes> Interceptor<ModReq, http::Request<SdkBody>, TxRes, ModRes>
+    for RequestBodyHashInterceptor
+{
+    fn hooks(&self) -> InterceptorHookSet {
+        InterceptorHookSet::READ_BEFORE_EXECUTION | InterceptorHookSet::READ_BEFORE_SIGNING
+    }
+
+    fn read_before_execution(
+        &mut self,
+        _context: &InterceptorContext<ModReq, http::Request<SdkBody>, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        // Don't reuse a hash left in the bag by a previous execution
+        cfg.unset::<RequestBodyHash>();
+        Ok(())
+    }
+
+    fn read_before_signing(
+        &mut self,
+        context: &InterceptorContext<ModReq, http::Request<SdkBody>, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        if cfg.contains::<RequestBodyHash>() {
+            return Ok(());
+        }
+        if let Some(body) = context.tx_request()?.body().bytes() {
+            cfg.put(RequestBodyHash::compute(body));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RequestBodyHash, RequestBodyHashInterceptor};
+    use aws_smithy_http::body::SdkBody;
+    use aws_smithy_runtime_api::config_bag::ConfigBag;
+    use aws_smithy_runtime_api::interceptors::{InterceptorContext, Interceptors};
+
+    #[test]
+    fn body_is_hashed_once_per_execution() {
+        let mut interceptors = Interceptors::<(), http::Request<SdkBody>, (), ()>::new();
+        interceptors.with_client_interceptor(RequestBodyHashInterceptor::new());
+        let mut cfg = ConfigBag::base();
+
+        let mut ctx = InterceptorContext::new(());
+        ctx.set_tx_request(http::Request::new(SdkBody::from("hello")));
+        interceptors
+            .client_read_before_execution(&ctx, &mut cfg)
+            .unwrap();
+        interceptors.read_before_signing(&ctx, &mut cfg).unwrap();
+        assert_eq!(
+            cfg.get::<RequestBodyHash>().unwrap().as_str(),
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+
+        // A retry reuses the hash even if the body has been replaced
+        let mut retry = InterceptorContext::new(());
+        retry.set_tx_request(http::Request::new(SdkBody::from("changed")));
+        interceptors.read_before_signing(&retry, &mut cfg).unwrap();
+        assert_eq!(
+            cfg.get::<RequestBodyHash>(),
+            Some(&RequestBodyHash::compute(b"hello"))
+        );
+
+        // The next execution computes a new hash
+        interceptors
+            .client_read_before_execution(&retry, &mut cfg)
+            .unwrap();
+        interceptors.read_before_signing(&retry, &mut cfg).unwrap();
+        assert_eq!(
+            cfg.get::<RequestBodyHash>(),
+            Some(&RequestBodyHash::compute(b"changed"))
+        );
+    }
+}
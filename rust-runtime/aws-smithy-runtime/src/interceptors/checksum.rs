@@ -0,0 +1,163 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An interceptor that computes SHA-256 checksums of request and response bodies.
+
+use aws_smithy_http::body::SdkBody;
+use aws_smithy_runtime_api::config_bag::ConfigBag;
+use aws_smithy_runtime_api::interceptors::{
+    Interceptor, InterceptorContext, InterceptorError, InterceptorHookSet,
+};
+use aws_smithy_types::base64;
+use http::header::{HeaderName, HeaderValue};
+use sha2::{Digest, Sha256};
+
+const CHECKSUM_HEADER: &str = "x-amz-checksum-sha256";
+
+/// An interceptor that computes SHA-256 checksums of the request and response bodies and records
+/// them on the [`InterceptorContext`].
+///
+/// The request checksum is computed once the request has been serialized, in
+/// `modify_before_retry_loop`, and sent base64-encoded as the `x-amz-checksum-sha256` header. The
+/// response checksum is computed for each attempt in `modify_before_attempt_completion`, along
+/// with the checksum the service sent in the same header, so that the runtime can reject
+/// responses that don't match. Streaming bodies can't be checksummed up front and are skipped.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct ChecksumInterceptor;
+
+impl ChecksumInterceptor {
+    /// Create a new `ChecksumInterceptor`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<ModReq, ModRes> Interceptor<ModReq, http::Request<SdkBody>, http::Response<SdkBody>, ModRes>
+    for ChecksumInterceptor
+{
+    fn hooks(&self) -> InterceptorHookSet {
+        InterceptorHookSet::MODIFY_BEFORE_RETRY_LOOP
+            | InterceptorHookSet::MODIFY_BEFORE_ATTEMPT_COMPLETION
+    }
+
+    fn modify_before_retry_loop(
+        &mut self,
+        context: &mut InterceptorContext<
+            ModReq,
+            http::Request<SdkBody>,
+            http::Response<SdkBody>,
+            ModRes,
+        >,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        let checksum = match context.tx_request()?.body().bytes() {
+            Some(body) => Sha256::digest(body).to_vec(),
+            None => return Ok(()),
+        };
+        let value = HeaderValue::try_from(base64::encode(&checksum))
+            .map_err(InterceptorError::modify_before_retry_loop)?;
+        context
+            .tx_request_mut()?
+            .headers_mut()
+            .insert(HeaderName::from_static(CHECKSUM_HEADER), value);
+        context.set_request_checksum(checksum);
+        Ok(())
+    }
+
+    fn modify_before_attempt_completion(
+        &mut self,
+        context: &mut InterceptorContext<
+            ModReq,
+            http::Request<SdkBody>,
+            http::Response<SdkBody>,
+            ModRes,
+        >,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        let response = match context.tx_response() {
+            Ok(response) => response,
+            // The attempt failed before a response was received
+            Err(_) => return Ok(()),
+        };
+        let checksum = match response.body().bytes() {
+            Some(body) => Sha256::digest(body).to_vec(),
+            None => return Ok(()),
+        };
+        let expected = match response.headers().get(CHECKSUM_HEADER) {
+            Some(value) => {
+                let value = value
+                    .to_str()
+                    .map_err(InterceptorError::modify_before_attempt_completion)?;
+                Some(
+                    base64::decode(value)
+                        .map_err(InterceptorError::modify_before_attempt_completion)?,
+                )
+            }
+            None => None,
+        };
+        context.set_response_checksum(checksum, expected);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChecksumInterceptor;
+    use aws_smithy_http::body::SdkBody;
+    use aws_smithy_runtime_api::config_bag::ConfigBag;
+    use aws_smithy_runtime_api::interceptors::{InterceptorContext, Interceptors};
+    use aws_smithy_types::base64;
+
+    // The SHA-256 checksum of "hello", base64-encoded
+    const HELLO_CHECKSUM: &str = "LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ=";
+
+    type Ctx = InterceptorContext<(), http::Request<SdkBody>, http::Response<SdkBody>, ()>;
+
+    fn complete_attempt(checksum_header: Option<&str>) -> Ctx {
+        let mut interceptors = Interceptors::new();
+        interceptors.with_client_interceptor(ChecksumInterceptor::new());
+        let mut cfg = ConfigBag::base();
+        let mut ctx = Ctx::new(());
+        ctx.set_tx_request(http::Request::new(SdkBody::from("hello")));
+        interceptors
+            .modify_before_retry_loop(&mut ctx, &mut cfg)
+            .unwrap();
+
+        let mut response = http::Response::builder();
+        if let Some(checksum) = checksum_header {
+            response = response.header("x-amz-checksum-sha256", checksum);
+        }
+        ctx.set_tx_response(response.body(SdkBody::from("hello")).unwrap());
+        interceptors
+            .modify_before_attempt_completion(&mut ctx, &mut cfg)
+            .unwrap();
+        ctx
+    }
+
+    #[test]
+    fn request_checksum_is_sent() {
+        let ctx = complete_attempt(None);
+        let request = ctx.tx_request().unwrap();
+        assert_eq!(request.headers()["x-amz-checksum-sha256"], HELLO_CHECKSUM);
+        assert_eq!(
+            ctx.request_checksum().unwrap(),
+            base64::decode(HELLO_CHECKSUM).unwrap()
+        );
+    }
+
+    #[test]
+    fn response_checksum_is_verified() {
+        let ctx = complete_attempt(None);
+        assert!(ctx.response_checksum().is_some());
+        assert!(ctx.verify_response_checksum().is_ok());
+
+        let ctx = complete_attempt(Some(HELLO_CHECKSUM));
+        assert!(ctx.verify_response_checksum().is_ok());
+
+        let ctx = complete_attempt(Some(&base64::encode("not the checksum")));
+        assert!(ctx.verify_response_checksum().is_err());
+    }
+}
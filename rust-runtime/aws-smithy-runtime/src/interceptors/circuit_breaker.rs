@@ -0,0 +1,336 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An interceptor that stops sending requests after repeated failures.
+
+use aws_smithy_runtime_api::config_bag::ConfigBag;
+use aws_smithy_runtime_api::interceptors::{
+    Interceptor, InterceptorContext, InterceptorError, InterceptorHookSet,
+};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The error returned for attempts rejected by an open [`CircuitBreakerInterceptor`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CircuitOpenError;
+
+impl fmt::Display for CircuitOpenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the circuit breaker is open; the request was not sent")
+    }
+}
+
+impl std::error::Error for CircuitOpenError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Circuit {
+    /// Requests are sent normally
+    Closed,
+    /// Requests are rejected until the reset timeout has passed
+    Open { since: Instant },
+    /// A single probe request has been let through to test whether the service has recovered
+    HalfOpen { since: Instant },
+}
+
+/// The state of a circuit breaker, shared by all clones of a [`CircuitBreakerInterceptor`].
+#[derive(Debug)]
+pub struct CircuitBreakerState {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    circuit: Circuit,
+    consecutive_failures: u32,
+}
+
+impl Default for CircuitBreakerState {
+    fn default() -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                circuit: Circuit::Closed,
+                consecutive_failures: 0,
+            }),
+        }
+    }
+}
+
+/// An interceptor that implements the circuit breaker pattern.
+///
+/// The circuit starts closed and all requests are sent. Once `threshold` consecutive attempts have
+/// failed, the circuit opens and attempts are rejected with a [`CircuitOpenError`] in
+/// `read_before_attempt`, before anything is sent. After `reset_timeout` has passed, the circuit is
+/// half-open: a single probe attempt is let through, and its outcome decides whether the circuit
+/// closes again or stays open for another `reset_timeout`. A probe whose outcome hasn't been
+/// recorded within `reset_timeout`, e.g. because it was failed by the signer or another
+/// interceptor, is treated as failed and another probe is let through.
+///
+/// Attempts are judged by their modeled response in `modify_before_attempt_completion`, and
+/// attempts that fail to get a response are counted as failures in `read_on_transport_error`, so
+/// a probe that never gets a response reopens the circuit. Clones share the same state, so a
+/// single breaker can protect a service across clients.
+#[derive(Clone, Debug)]
+pub struct CircuitBreakerInterceptor {
+    threshold: u32,
+    reset_timeout: Duration,
+    state: Arc<CircuitBreakerState>,
+}
+
+impl CircuitBreakerInterceptor {
+    /// Create a new `CircuitBreakerInterceptor` that opens after `threshold` consecutive failures
+    /// and lets a probe through once it has been open for `reset_timeout`.
+    pub fn new(threshold: u32, reset_timeout: Duration) -> Self {
+        Self {
+            threshold,
+            reset_timeout,
+            state: Default::default(),
+        }
+    }
+
+    fn inner(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.state.inner.lock().unwrap()
+    }
+
+    /// Decide whether an attempt may be made, moving from open to half-open if the reset timeout
+    /// has passed.
+    fn try_attempt(&self) -> bool {
+        let mut inner = self.inner();
+        match inner.circuit {
+            Circuit::Closed => true,
+            Circuit::Open { since } | Circuit::HalfOpen { since }
+                if since.elapsed() >= self.reset_timeout =>
+            {
+                inner.circuit = Circuit::HalfOpen {
+                    since: Instant::now(),
+                };
+                true
+            }
+            // Only the probe is let through while half-open
+            Circuit::Open { .. } | Circuit::HalfOpen { .. } => false,
+        }
+    }
+
+    fn record_outcome(&self, succeeded: bool) {
+        let mut inner = self.inner();
+        if succeeded {
+            inner.consecutive_failures = 0;
+            inner.circuit = Circuit::Closed;
+            return;
+        }
+
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+        let probe_failed = matches!(inner.circuit, Circuit::HalfOpen { .. });
+        if probe_failed || inner.consecutive_failures >= self.threshold {
+            inner.circuit = Circuit::Open {
+                since: Instant::now(),
+            };
+        }
+    }
+}
+
+impl<ModReq, TxReq, TxRes, T, E> Interceptor<ModReq, TxReq, TxRes, Result<T, E>>
+    for CircuitBreakerInterceptor
+{
+    fn hooks(&self) -> InterceptorHookSet {
+        InterceptorHookSet::READ_BEFORE_ATTEMPT
+            | InterceptorHookSet::READ_ON_TRANSPORT_ERROR
+            | InterceptorHookSet::MODIFY_BEFORE_ATTEMPT_COMPLETION
+    }
+
+    fn read_before_attempt(
+        &mut self,
+        _context: &InterceptorContext<ModReq, TxReq, TxRes, Result<T, E>>,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        if self.try_attempt() {
+            Ok(())
+        } else {
+            Err(InterceptorError::read_before_attempt(CircuitOpenError))
+        }
+    }
+
+    fn read_on_transport_error(
+        &mut self,
+        _context: &InterceptorContext<ModReq, TxReq, TxRes, Result<T, E>>,
+        _cfg: &mut ConfigBag,
+        _error: &(dyn std::error::Error + Send + Sync + 'static),
+    ) -> Result<(), InterceptorError> {
+        self.record_outcome(false);
+        Ok(())
+    }
+
+    fn modify_before_attempt_completion(
+        &mut self,
+        context: &mut InterceptorContext<ModReq, TxReq, TxRes, Result<T, E>>,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        if let Ok(response) = context.modeled_response() {
+            self.record_outcome(response.is_ok());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Circuit, CircuitBreakerInterceptor, CircuitOpenError};
+    use aws_smithy_runtime_api::config_bag::ConfigBag;
+    use aws_smithy_runtime_api::interceptors::{
+        Interceptor, InterceptorContext, InterceptorError, Interceptors,
+    };
+    use std::time::Duration;
+
+    type TestInterceptors = Interceptors<(), (), (), Result<(), ()>>;
+
+    /// Long enough that an attempt made right after another sees the same circuit state
+    const RESET_TIMEOUT: Duration = Duration::from_millis(100);
+
+    fn interceptors(breaker: &CircuitBreakerInterceptor) -> TestInterceptors {
+        let mut interceptors = Interceptors::new();
+        interceptors.with_client_interceptor(breaker.clone());
+        interceptors
+    }
+
+    /// Make an attempt with the given outcome, returning `false` if it was rejected
+    fn attempt(interceptors: &mut TestInterceptors, outcome: Result<(), ()>) -> bool {
+        let mut cfg = ConfigBag::base();
+        let mut ctx = InterceptorContext::new(());
+        if interceptors.read_before_attempt(&ctx, &mut cfg).is_err() {
+            return false;
+        }
+        ctx.set_modeled_response(outcome);
+        interceptors
+            .modify_before_attempt_completion(&mut ctx, &mut cfg)
+            .unwrap();
+        true
+    }
+
+    fn circuit(breaker: &CircuitBreakerInterceptor) -> Circuit {
+        breaker.inner().circuit
+    }
+
+    #[test]
+    fn opens_after_consecutive_failures() {
+        let breaker = CircuitBreakerInterceptor::new(2, Duration::from_secs(3600));
+        let mut interceptors = interceptors(&breaker);
+
+        assert!(attempt(&mut interceptors, Err(())));
+        // A success resets the count
+        assert!(attempt(&mut interceptors, Ok(())));
+        assert!(attempt(&mut interceptors, Err(())));
+        assert_eq!(circuit(&breaker), Circuit::Closed);
+        assert!(attempt(&mut interceptors, Err(())));
+        assert!(matches!(circuit(&breaker), Circuit::Open { .. }));
+    }
+
+    #[test]
+    fn open_circuit_rejects_attempts_from_every_clone() {
+        let breaker = CircuitBreakerInterceptor::new(1, Duration::from_secs(3600));
+        let mut first = interceptors(&breaker);
+        let mut second = interceptors(&breaker);
+
+        assert!(attempt(&mut first, Err(())));
+        assert!(!attempt(&mut first, Ok(())));
+        assert!(!attempt(&mut second, Ok(())));
+    }
+
+    #[test]
+    fn half_open_probe_success_closes_circuit() {
+        let breaker = CircuitBreakerInterceptor::new(1, RESET_TIMEOUT);
+        let mut interceptors = interceptors(&breaker);
+        assert!(attempt(&mut interceptors, Err(())));
+
+        // The reset timeout has passed, so the next attempt is let through as a probe
+        std::thread::sleep(RESET_TIMEOUT);
+        let mut cfg = ConfigBag::base();
+        let mut ctx = InterceptorContext::new(());
+        interceptors.read_before_attempt(&ctx, &mut cfg).unwrap();
+        assert!(matches!(circuit(&breaker), Circuit::HalfOpen { .. }));
+        // Other attempts are rejected while the probe is in flight
+        assert!(!attempt(&mut interceptors, Ok(())));
+
+        ctx.set_modeled_response(Ok(()));
+        interceptors
+            .modify_before_attempt_completion(&mut ctx, &mut cfg)
+            .unwrap();
+        assert_eq!(circuit(&breaker), Circuit::Closed);
+    }
+
+    #[test]
+    fn half_open_probe_failure_reopens_circuit() {
+        let breaker = CircuitBreakerInterceptor::new(3, Duration::ZERO);
+        let mut interceptors = interceptors(&breaker);
+        for _ in 0..3 {
+            assert!(attempt(&mut interceptors, Err(())));
+        }
+
+        // A single failed probe is enough to reopen the circuit
+        assert!(attempt(&mut interceptors, Err(())));
+        assert!(matches!(circuit(&breaker), Circuit::Open { .. }));
+    }
+
+    #[test]
+    fn half_open_probe_transport_error_reopens_circuit() {
+        let breaker = CircuitBreakerInterceptor::new(1, Duration::ZERO);
+        let mut interceptors = interceptors(&breaker);
+        assert!(attempt(&mut interceptors, Err(())));
+
+        let mut cfg = ConfigBag::base();
+        let ctx = InterceptorContext::new(());
+        interceptors.read_before_attempt(&ctx, &mut cfg).unwrap();
+        assert!(matches!(circuit(&breaker), Circuit::HalfOpen { .. }));
+
+        // The probe never gets a response, so `modify_before_attempt_completion` isn't run
+        let error = CircuitOpenError;
+        interceptors
+            .read_on_transport_error(&ctx, &mut cfg, &error)
+            .unwrap();
+        assert!(matches!(circuit(&breaker), Circuit::Open { .. }));
+        // The circuit can be probed again once the reset timeout has passed
+        assert!(attempt(&mut interceptors, Ok(())));
+        assert_eq!(circuit(&breaker), Circuit::Closed);
+    }
+
+    /// Fails every attempt in `read_before_attempt`, like a signer that can't find credentials
+    struct RejectAttempts;
+
+    impl Interceptor<(), (), (), Result<(), ()>> for RejectAttempts {
+        fn read_before_attempt(
+            &mut self,
+            _context: &InterceptorContext<(), (), (), Result<(), ()>>,
+            _cfg: &mut ConfigBag,
+        ) -> Result<(), InterceptorError> {
+            Err(InterceptorError::read_before_attempt("no credentials"))
+        }
+    }
+
+    #[test]
+    fn unresolved_half_open_probe_is_treated_as_failed() {
+        let breaker = CircuitBreakerInterceptor::new(1, RESET_TIMEOUT);
+        let mut interceptors = interceptors(&breaker);
+        assert!(attempt(&mut interceptors, Err(())));
+        std::thread::sleep(RESET_TIMEOUT);
+
+        // The probe is let through by the breaker but failed by a later interceptor, so none of
+        // the hooks that record its outcome are run
+        let mut failing = TestInterceptors::new();
+        failing
+            .with_client_interceptor(breaker.clone())
+            .with_client_interceptor(RejectAttempts);
+        let ctx = InterceptorContext::new(());
+        assert!(failing
+            .read_before_attempt(&ctx, &mut ConfigBag::base())
+            .is_err());
+        assert!(matches!(circuit(&breaker), Circuit::HalfOpen { .. }));
+        assert!(!attempt(&mut interceptors, Ok(())));
+
+        // Once the reset timeout has passed, another probe is let through
+        std::thread::sleep(RESET_TIMEOUT);
+        assert!(attempt(&mut interceptors, Ok(())));
+        assert_eq!(circuit(&breaker), Circuit::Closed);
+    }
+}
@@ -0,0 +1,98 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An interceptor that sends a correlation ID to the service.
+
+use aws_smithy_runtime_api::config_bag::ConfigBag;
+use aws_smithy_runtime_api::interceptors::{
+    Interceptor, InterceptorContext, InterceptorError, InterceptorHookSet,
+};
+use http::header::{HeaderName, HeaderValue};
+
+const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+
+/// An interceptor that adds [`InterceptorContext::correlation_id`] to the transport request as the
+/// `x-correlation-id` header in `modify_before_transmit`.
+///
+/// If no correlation ID has been set, a random UUID (v4) is generated and set on the context, so
+/// that every attempt of an execution sends the same ID.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct CorrelationIdInterceptor;
+
+impl CorrelationIdInterceptor {
+    /// Create a new `CorrelationIdInterceptor`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<ModReq, B, TxRes, ModRes> Interceptor<ModReq, http::Request<B>, TxRes, ModRes>
+    for CorrelationIdInterceptor
+{
+    fn hooks(&self) -> InterceptorHookSet {
+        InterceptorHookSet::MODIFY_BEFORE_TRANSMIT
+    }
+
+    fn modify_before_transmit(
+        &mut self,
+        context: &mut InterceptorContext<ModReq, http::Request<B>, TxRes, ModRes>,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        let correlation_id = match context.correlation_id() {
+            Some(correlation_id) => correlation_id.to_owned(),
+            None => {
+                let correlation_id = uuid::Uuid::new_v4().to_string();
+                context.set_correlation_id(correlation_id.clone());
+                correlation_id
+            }
+        };
+        let value = HeaderValue::try_from(correlation_id)
+            .map_err(InterceptorError::modify_before_transmit)?;
+        context
+            .tx_request_mut()?
+            .headers_mut()
+            .insert(HeaderName::from_static(CORRELATION_ID_HEADER), value);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CorrelationIdInterceptor;
+    use aws_smithy_runtime_api::config_bag::ConfigBag;
+    use aws_smithy_runtime_api::interceptors::{InterceptorContext, Interceptors};
+
+    type Ctx = InterceptorContext<(), http::Request<()>, (), ()>;
+
+    fn transmit(ctx: &mut Ctx) -> String {
+        let mut interceptors = Interceptors::new();
+        interceptors.with_client_interceptor(CorrelationIdInterceptor::new());
+        interceptors
+            .modify_before_transmit(ctx, &mut ConfigBag::base())
+            .unwrap();
+        let headers = ctx.tx_request().unwrap().headers();
+        headers["x-correlation-id"].to_str().unwrap().to_owned()
+    }
+
+    #[test]
+    fn existing_correlation_id_is_sent() {
+        let mut ctx = Ctx::new(());
+        ctx.set_correlation_id("user-request-1");
+        ctx.set_tx_request(http::Request::new(()));
+        assert_eq!(transmit(&mut ctx), "user-request-1");
+    }
+
+    #[test]
+    fn missing_correlation_id_is_generated_once() {
+        let mut ctx = Ctx::new(());
+        ctx.set_tx_request(http::Request::new(()));
+        let correlation_id = transmit(&mut ctx);
+        assert_eq!(ctx.correlation_id(), Some(correlation_id.as_str()));
+        assert!(uuid::Uuid::parse_str(&correlation_id).is_ok());
+        // A retry sends the same ID
+        assert_eq!(transmit(&mut ctx), correlation_id);
+    }
+}
@@ -0,0 +1,251 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An interceptor that creates a root `tracing` span for each execution.
+
+use aws_smithy_runtime_api::config_bag::ConfigBag;
+use aws_smithy_runtime_api::interceptors::{
+    Interceptor, InterceptorContext, InterceptorError, InterceptorHookSet,
+};
+use std::collections::BTreeMap;
+use tracing::field::Empty;
+use tracing::Span;
+
+/// The root span of an execution, stored in the config bag by an [`ExecutionTracingInterceptor`].
+#[derive(Debug)]
+pub struct ExecutionSpan(Span);
+
+impl ExecutionSpan {
+    /// Retrieve the span.
+    pub fn span(&self) -> &Span {
+        &self.0
+    }
+}
+
+/// Emit an event for `hook` within the execution span, if there is one
+fn hook_event(cfg: &ConfigBag, hook: &'static str) {
+    if let Some(execution) = cfg.get::<ExecutionSpan>() {
+        execution
+            .0
+            .in_scope(|| tracing::debug!(hook, "running interceptor hook"));
+    }
+}
+
+macro_rules! traced_read_hooks {
+    ($($hook:ident;)+) => {
+        $(
+            fn $hook(
+                &mut self,
+                _context: &InterceptorContext<ModReq, TxReq, TxRes, Result<T, E>>,
+                cfg: &mut ConfigBag,
+            ) -> Result<(), InterceptorError> {
+                hook_event(cfg, stringify!($hook));
+                Ok(())
+            }
+        )+
+    };
+}
+
+/// An interceptor that gives each execution a root `tracing` span.
+///
+/// The `execution` span is created in `read_before_execution` and stored in the config bag as an
+/// [`ExecutionSpan`]. Every read hook emits an event within it, and the span is closed in
+/// `read_after_execution`. The span has an `operation_name` field, and records `attempt_count`
+/// and `success` when the execution completes. Any
+/// [custom attributes](InterceptorContext::custom_attributes) set on the context are recorded in
+/// the `attributes` field, sorted by key.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct ExecutionTracingInterceptor;
+
+impl ExecutionTracingInterceptor {
+    /// Create a new `ExecutionTracingInterceptor`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<ModReq, TxReq, TxRes, T, E> Interceptor<ModReq, TxReq, TxRes, Result<T, E>>
+    for ExecutionTracingInterceptor
+{
+    fn hooks(&self) -> InterceptorHookSet {
+        InterceptorHookSet::READ_BEFORE_EXECUTION
+            | InterceptorHookSet::READ_BEFORE_SERIALIZATION
+            | InterceptorHookSet::READ_AFTER_SERIALIZATION
+            | InterceptorHookSet::READ_BEFORE_ATTEMPT
+            | InterceptorHookSet::READ_BEFORE_SIGNING
+            | InterceptorHookSet::READ_AFTER_SIGNING
+            | InterceptorHookSet::READ_BEFORE_TRANSMIT
+            | InterceptorHookSet::READ_AFTER_TRANSMIT
+            | InterceptorHookSet::READ_BEFORE_DESERIALIZATION
+            | InterceptorHookSet::READ_AFTER_DESERIALIZATION
+            | InterceptorHookSet::READ_AFTER_ATTEMPT
+            | InterceptorHookSet::READ_AFTER_EXECUTION
+    }
+
+    fn read_before_execution(
+        &mut self,
+        context: &InterceptorContext<ModReq, TxReq, TxRes, Result<T, E>>,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        // This hook runs for both client and operation interceptors, but only one span is needed
+        if !cfg.contains::<ExecutionSpan>() {
+            let span = tracing::debug_span!(
+                "execution",
+                operation_name = context.operation_name(),
+                attempt_count = Empty,
+                success = Empty,
+                attributes = Empty,
+            );
+            cfg.put(ExecutionSpan(span));
+        }
+        hook_event(cfg, "read_before_execution");
+        Ok(())
+    }
+
+    traced_read_hooks! {
+        read_before_serialization;
+        read_after_serialization;
+        read_before_attempt;
+        read_before_signing;
+        read_after_signing;
+        read_before_transmit;
+        read_after_transmit;
+        read_before_deserialization;
+        read_after_deserialization;
+        read_after_attempt;
+    }
+
+    fn read_after_execution(
+        &mut self,
+        context: &InterceptorContext<ModReq, TxReq, TxRes, Result<T, E>>,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        hook_event(cfg, "read_after_execution");
+        if let Some(execution) = cfg.get::<ExecutionSpan>() {
+            let success = matches!(context.modeled_response(), Ok(Ok(_)));
+            execution
+                .0
+                .record("attempt_count", context.attempt_number())
+                .record("success", success);
+            let attributes = context.custom_attributes();
+            if !attributes.is_empty() {
+                let attributes: BTreeMap<_, _> = attributes.iter().collect();
+                execution
+                    .0
+                    .record("attributes", tracing::field::debug(&attributes));
+            }
+        }
+        // Dropping the last handle to the span closes it
+        cfg.unset::<ExecutionSpan>();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExecutionTracingInterceptor;
+    use aws_smithy_runtime_api::config_bag::ConfigBag;
+    use aws_smithy_runtime_api::interceptors::{InterceptorContext, Interceptors};
+    use std::fmt::Debug;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Subscriber};
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::registry::LookupSpan;
+    use tracing_subscriber::Layer;
+
+    #[derive(Default)]
+    struct Fields(Vec<String>);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0.push(format!("{}={:?}", field.name(), value));
+        }
+    }
+
+    /// Records every event as `<parent span> <fields>`, and the fields recorded on spans
+    #[derive(Clone, Default)]
+    struct Recorder {
+        events: Arc<Mutex<Vec<String>>>,
+        fields: Arc<Mutex<Vec<String>>>,
+        closed: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Recorder {
+        fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+            let mut fields = Fields::default();
+            attrs.record(&mut fields);
+            self.fields.lock().unwrap().extend(fields.0);
+        }
+
+        fn on_record(&self, _id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+            let mut fields = Fields::default();
+            values.record(&mut fields);
+            self.fields.lock().unwrap().extend(fields.0);
+        }
+
+        fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+            let parent = ctx.event_span(event).map(|span| span.name()).unwrap_or("");
+            let mut fields = Fields::default();
+            event.record(&mut fields);
+            let hook = fields.0.iter().find(|f| f.starts_with("hook=")).unwrap();
+            self.events.lock().unwrap().push(format!("{parent} {hook}"));
+        }
+
+        fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+            let name = ctx.span(&id).unwrap().name();
+            self.closed.lock().unwrap().push(name);
+        }
+    }
+
+    #[test]
+    fn hook_events_are_children_of_the_execution_span() {
+        let recorder = Recorder::default();
+        let subscriber = tracing_subscriber::registry().with(recorder.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            let mut interceptors = Interceptors::<(), (), (), Result<(), ()>>::new();
+            interceptors.with_client_interceptor(ExecutionTracingInterceptor::new());
+            let mut ctx = InterceptorContext::new(()).with_operation_name("GetObject");
+            let mut cfg = ConfigBag::base();
+
+            interceptors
+                .client_read_before_execution(&ctx, &mut cfg)
+                .unwrap();
+            for _ in 0..2 {
+                ctx.start_attempt();
+                interceptors.read_before_attempt(&ctx, &mut cfg).unwrap();
+                interceptors.read_after_attempt(&ctx, &mut cfg).unwrap();
+            }
+            ctx.set_modeled_response(Ok(()));
+            ctx.set_attribute("tenant", "a");
+            ctx.set_attribute("region", "us-east-1");
+            interceptors.read_after_execution(&ctx, &mut cfg).unwrap();
+        });
+
+        assert_eq!(
+            *recorder.events.lock().unwrap(),
+            vec![
+                "execution hook=\"read_before_execution\"",
+                "execution hook=\"read_before_attempt\"",
+                "execution hook=\"read_after_attempt\"",
+                "execution hook=\"read_before_attempt\"",
+                "execution hook=\"read_after_attempt\"",
+                "execution hook=\"read_after_execution\"",
+            ]
+        );
+        assert_eq!(
+            *recorder.fields.lock().unwrap(),
+            vec![
+                "operation_name=\"GetObject\"",
+                "attempt_count=2",
+                "success=true",
+                "attributes={\"region\": \"us-east-1\", \"tenant\": \"a\"}"
+            ]
+        );
+        assert_eq!(*recorder.closed.lock().unwrap(), vec!["execution"]);
+    }
+}
@@ -0,0 +1,319 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An interceptor that records latency and retry metrics for each execution.
+
+use aws_smithy_runtime_api::config_bag::ConfigBag;
+use aws_smithy_runtime_api::interceptors::{
+    Interceptor, InterceptorContext, InterceptorError, InterceptorHookSet,
+};
+use std::time::{Duration, Instant};
+
+/// Metric name for the duration of an entire execution, including all attempts.
+pub const EXECUTION_DURATION: &str = "execution_duration";
+/// Metric name for the duration of a single attempt.
+pub const ATTEMPT_DURATION: &str = "attempt_duration";
+/// Metric name for the time spent serializing the request.
+pub const SERIALIZATION_DURATION: &str = "serialization_duration";
+/// Metric name for the time spent signing the request.
+pub const SIGNING_DURATION: &str = "signing_duration";
+/// Metric name for the time spent communicating with the service.
+pub const TRANSMIT_DURATION: &str = "transmit_duration";
+/// Metric name for the time spent deserializing the response.
+pub const DESERIALIZATION_DURATION: &str = "deserialization_duration";
+/// Metric name for the number of attempts made during an execution.
+pub const ATTEMPT_COUNT: &str = "attempt_count";
+/// Metric name for the number of retries made during an execution.
+pub const RETRY_COUNT: &str = "retry_count";
+
+/// A sink for metrics emitted by a [`MetricsInterceptor`].
+pub trait MetricsRecorder: Send + Sync {
+    /// Record that the operation called `name` took `duration`.
+    fn record_duration(&self, name: &str, duration: Duration);
+
+    /// Record that the event called `name` happened `count` times.
+    fn record_count(&self, name: &str, count: u64);
+}
+
+/// A [`MetricsRecorder`] that discards all metrics.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct NoOpMetricsRecorder;
+
+impl NoOpMetricsRecorder {
+    /// Create a new `NoOpMetricsRecorder`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl MetricsRecorder for NoOpMetricsRecorder {
+    fn record_duration(&self, _name: &str, _duration: Duration) {}
+
+    fn record_count(&self, _name: &str, _count: u64) {}
+}
+
+/// A [`MetricsRecorder`] that emits each metric as a `tracing` event.
+///
+/// Events are emitted at the `DEBUG` level with `metric` and `value` fields. Durations are
+/// emitted in milliseconds.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct TracingMetricsRecorder;
+
+impl TracingMetricsRecorder {
+    /// Create a new `TracingMetricsRecorder`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl MetricsRecorder for TracingMetricsRecorder {
+    fn record_duration(&self, name: &str, duration: Duration) {
+        tracing::debug!(
+            metric = name,
+            value = duration.as_millis() as u64,
+            "duration (ms)"
+        );
+    }
+
+    fn record_count(&self, name: &str, count: u64) {
+        tracing::debug!(metric = name, value = count, "count");
+    }
+}
+
+/// An interceptor that measures how long each phase of an execution takes and how many attempts
+/// were made, and hands the results to a [`MetricsRecorder`].
+///
+/// Durations are measured between the matching `read_before_*` and `read_after_*` hooks and
+/// recorded under the names defined in this module, e.g. [`SIGNING_DURATION`]. The
+/// [`TRANSMIT_DURATION`] is taken from [`InterceptorContext::transmit_duration`] so that it only
+/// includes network time. Attempt and retry counts are recorded once per execution, in
+/// `read_after_execution`.
+#[derive(Debug, Default)]
+pub struct MetricsInterceptor<R> {
+    recorder: R,
+    attempts: u64,
+    execution_start: Option<Instant>,
+    attempt_start: Option<Instant>,
+    serialization_start: Option<Instant>,
+    signing_start: Option<Instant>,
+    deserialization_start: Option<Instant>,
+}
+
+impl<R: MetricsRecorder> MetricsInterceptor<R> {
+    /// Create a new `MetricsInterceptor` that sends metrics to `recorder`.
+    pub fn new(recorder: R) -> Self {
+        Self {
+            recorder,
+            attempts: 0,
+            execution_start: None,
+            attempt_start: None,
+            serialization_start: None,
+            signing_start: None,
+            deserialization_start: None,
+        }
+    }
+
+    fn record_elapsed(&self, name: &str, start: Option<Instant>) {
+        if let Some(start) = start {
+            self.recorder.record_duration(name, start.elapsed());
+        }
+    }
+}
+
+impl<R, ModReq, TxReq, TxRes, ModRes> Interceptor<ModReq, TxReq, TxRes, ModRes>
+    for MetricsInterceptor<R>
+where
+    R: MetricsRecorder,
+{
+    fn hooks(&self) -> InterceptorHookSet {
+        InterceptorHookSet::READ_BEFORE_EXECUTION
+            | InterceptorHookSet::READ_BEFORE_SERIALIZATION
+            | InterceptorHookSet::READ_AFTER_SERIALIZATION
+            | InterceptorHookSet::READ_BEFORE_ATTEMPT
+            | InterceptorHookSet::READ_BEFORE_SIGNING
+            | InterceptorHookSet::READ_AFTER_SIGNING
+            | InterceptorHookSet::READ_AFTER_TRANSMIT
+            | InterceptorHookSet::READ_BEFORE_DESERIALIZATION
+            | InterceptorHookSet::READ_AFTER_DESERIALIZATION
+            | InterceptorHookSet::READ_AFTER_ATTEMPT
+            | InterceptorHookSet::READ_AFTER_EXECUTION
+    }
+
+    fn read_before_execution(
+        &mut self,
+        _context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        self.attempts = 0;
+        self.execution_start = Some(Instant::now());
+        Ok(())
+    }
+
+    fn read_before_serialization(
+        &mut self,
+        _context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        self.serialization_start = Some(Instant::now());
+        Ok(())
+    }
+
+    fn read_after_serialization(
+        &mut self,
+        _context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        let start = self.serialization_start.take();
+        self.record_elapsed(SERIALIZATION_DURATION, start);
+        Ok(())
+    }
+
+    fn read_before_attempt(
+        &mut self,
+        _context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        self.attempts += 1;
+        self.attempt_start = Some(Instant::now());
+        Ok(())
+    }
+
+    fn read_before_signing(
+        &mut self,
+        _context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        self.signing_start = Some(Instant::now());
+        Ok(())
+    }
+
+    fn read_after_signing(
+        &mut self,
+        _context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        let start = self.signing_start.take();
+        self.record_elapsed(SIGNING_DURATION, start);
+        Ok(())
+    }
+
+    fn read_after_transmit(
+        &mut self,
+        context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        if let Some(duration) = context.transmit_duration() {
+            self.recorder.record_duration(TRANSMIT_DURATION, duration);
+        }
+        Ok(())
+    }
+
+    fn read_before_deserialization(
+        &mut self,
+        _context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        self.deserialization_start = Some(Instant::now());
+        Ok(())
+    }
+
+    fn read_after_deserialization(
+        &mut self,
+        _context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        let start = self.deserialization_start.take();
+        self.record_elapsed(DESERIALIZATION_DURATION, start);
+        Ok(())
+    }
+
+    fn read_after_attempt(
+        &mut self,
+        _context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        let start = self.attempt_start.take();
+        self.record_elapsed(ATTEMPT_DURATION, start);
+        Ok(())
+    }
+
+    fn read_after_execution(
+        &mut self,
+        _context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        let start = self.execution_start.take();
+        self.record_elapsed(EXECUTION_DURATION, start);
+        self.recorder.record_count(ATTEMPT_COUNT, self.attempts);
+        self.recorder
+            .record_count(RETRY_COUNT, self.attempts.saturating_sub(1));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_runtime_api::interceptors::Interceptors;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct TestRecorder {
+        durations: Arc<Mutex<Vec<String>>>,
+        counts: Arc<Mutex<Vec<(String, u64)>>>,
+    }
+
+    impl MetricsRecorder for TestRecorder {
+        fn record_duration(&self, name: &str, _duration: Duration) {
+            self.durations.lock().unwrap().push(name.to_string());
+        }
+
+        fn record_count(&self, name: &str, count: u64) {
+            self.counts.lock().unwrap().push((name.to_string(), count));
+        }
+    }
+
+    #[test]
+    fn records_durations_and_retry_counts() {
+        let recorder = TestRecorder::default();
+        let mut interceptors = Interceptors::<(), (), (), ()>::new();
+        interceptors.with_client_interceptor(MetricsInterceptor::new(recorder.clone()));
+
+        let mut ctx = InterceptorContext::new(());
+        let mut cfg = ConfigBag::base();
+        interceptors
+            .client_read_before_execution(&ctx, &mut cfg)
+            .unwrap();
+        interceptors
+            .read_before_serialization(&ctx, &mut cfg)
+            .unwrap();
+        interceptors
+            .read_after_serialization(&ctx, &mut cfg)
+            .unwrap();
+        for _ in 0..3 {
+            interceptors.read_before_attempt(&ctx, &mut cfg).unwrap();
+            interceptors.read_before_transmit(&ctx, &mut cfg).unwrap();
+            ctx.mark_transmit_start();
+            ctx.mark_transmit_end();
+            interceptors.read_after_transmit(&ctx, &mut cfg).unwrap();
+            interceptors.read_after_attempt(&ctx, &mut cfg).unwrap();
+        }
+        interceptors.read_after_execution(&ctx, &mut cfg).unwrap();
+
+        let durations = recorder.durations.lock().unwrap();
+        assert_eq!(durations.first().unwrap(), SERIALIZATION_DURATION);
+        assert_eq!(
+            durations.iter().filter(|d| *d == TRANSMIT_DURATION).count(),
+            3
+        );
+        assert_eq!(durations.last().unwrap(), EXECUTION_DURATION);
+        assert_eq!(
+            *recorder.counts.lock().unwrap(),
+            vec![(ATTEMPT_COUNT.to_string(), 3), (RETRY_COUNT.to_string(), 2)]
+        );
+    }
+}
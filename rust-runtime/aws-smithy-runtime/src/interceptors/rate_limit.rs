@@ -0,0 +1,333 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An interceptor that limits how often requests are sent.
+
+use aws_smithy_async::rt::sleep::AsyncSleep;
+use aws_smithy_runtime_api::config_bag::ConfigBag;
+use aws_smithy_runtime_api::interceptors::{
+    Interceptor, InterceptorContext, InterceptorError, InterceptorHookSet,
+};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// The error returned when a [`TokenBucket`] has no tokens left.
+#[derive(Debug)]
+pub struct RateLimitExceeded {
+    wait: Duration,
+}
+
+impl RateLimitExceeded {
+    /// How long until the next token will be available.
+    pub fn wait(&self) -> Duration {
+        self.wait
+    }
+}
+
+impl fmt::Display for RateLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the rate limit was exceeded; the next token is available in {:?}",
+            self.wait
+        )
+    }
+}
+
+impl std::error::Error for RateLimitExceeded {}
+
+/// A thread-safe token bucket that refills at `rate` tokens per second and holds at most `burst`
+/// tokens.
+///
+/// Rather than counting tokens, the bucket stores the time at which it will next be full (the
+/// generic cell rate algorithm), so that taking a token is a single atomic compare-and-swap. The
+/// bucket starts full.
+#[derive(Debug)]
+pub struct TokenBucket {
+    start: Instant,
+    /// The time it takes to refill a single token, in nanoseconds
+    interval: u64,
+    /// How far the next token's arrival may be in the future while tokens are still available,
+    /// in nanoseconds
+    tolerance: u64,
+    /// The arrival time of the next token, in nanoseconds since `start`
+    next_token: AtomicU64,
+}
+
+impl TokenBucket {
+    /// Create a new, full `TokenBucket`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` isn't positive or `burst` is zero.
+    pub fn new(rate: f64, burst: u64) -> Self {
+        assert!(rate > 0.0, "the rate must be positive");
+        assert!(burst > 0, "the burst must be at least one token");
+        let interval = (1_000_000_000.0 / rate) as u64;
+        Self {
+            start: Instant::now(),
+            interval,
+            tolerance: interval.saturating_mul(burst - 1),
+            next_token: AtomicU64::new(0),
+        }
+    }
+
+    /// Take a token if one is available, without waiting.
+    pub fn try_acquire(&self) -> Result<(), RateLimitExceeded> {
+        self.try_acquire_at(Instant::now())
+    }
+
+    /// Take a token, waiting with `sleep` until one is available.
+    pub async fn acquire(&self, sleep: &dyn AsyncSleep) {
+        while let Err(err) = self.try_acquire() {
+            sleep.sleep(err.wait()).await;
+        }
+    }
+
+    fn try_acquire_at(&self, now: Instant) -> Result<(), RateLimitExceeded> {
+        let now = now.saturating_duration_since(self.start).as_nanos() as u64;
+        let mut next_token = self.next_token.load(Ordering::Acquire);
+        loop {
+            // Tokens don't accumulate beyond a full bucket
+            let arrival = next_token.max(now);
+            if arrival - now > self.tolerance {
+                return Err(RateLimitExceeded {
+                    wait: Duration::from_nanos(arrival - now - self.tolerance),
+                });
+            }
+            match self.next_token.compare_exchange_weak(
+                next_token,
+                arrival + self.interval,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => return Ok(()),
+                Err(actual) => next_token = actual,
+            }
+        }
+    }
+}
+
+/// An interceptor that limits attempts with a [`TokenBucket`].
+///
+/// Each attempt takes a token in `read_before_attempt`. Hooks can't wait, so when the bucket is
+/// empty the attempt fails immediately with a [`RateLimitExceeded`] error. Use an
+/// [`AsyncRateLimitInterceptor`] to wait for a token instead. Clones share the same bucket.
+#[derive(Clone, Debug)]
+pub struct RateLimitInterceptor {
+    bucket: Arc<TokenBucket>,
+}
+
+impl RateLimitInterceptor {
+    /// Create a new `RateLimitInterceptor` allowing `rate` attempts per second, with bursts of up
+    /// to `burst` attempts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` isn't positive or `burst` is zero.
+    pub fn new(rate: f64, burst: u64) -> Self {
+        Self::with_token_bucket(Arc::new(TokenBucket::new(rate, burst)))
+    }
+
+    /// Create a new `RateLimitInterceptor` that takes tokens from `bucket`, e.g. to share a limit
+    /// between clients.
+    pub fn with_token_bucket(bucket: Arc<TokenBucket>) -> Self {
+        Self { bucket }
+    }
+
+    /// Retrieve the bucket that tokens are taken from.
+    pub fn token_bucket(&self) -> &Arc<TokenBucket> {
+        &self.bucket
+    }
+}
+
+impl<ModReq, TxReq, TxRes, ModRes> Interceptor<ModReq, TxReq, TxRes, ModRes>
+    for RateLimitInterceptor
+{
+    fn hooks(&self) -> InterceptorHookSet {
+        InterceptorHookSet::READ_BEFORE_ATTEMPT
+    }
+
+    fn read_before_attempt(
+        &mut self,
+        _context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        self.bucket
+            .try_acquire()
+            .map_err(InterceptorError::read_before_attempt)
+    }
+}
+
+/// A [`TokenBucket`] that the runtime acquires a token from before each attempt, waiting with
+/// the [`AsyncSleep`] implementation from the config bag until one is available.
+///
+/// This is put in the config bag by an [`AsyncRateLimitInterceptor`].
+#[derive(Clone, Debug)]
+pub struct AttemptRateLimit {
+    bucket: Arc<TokenBucket>,
+}
+
+impl AttemptRateLimit {
+    /// Retrieve the bucket that tokens are taken from.
+    pub fn token_bucket(&self) -> &Arc<TokenBucket> {
+        &self.bucket
+    }
+}
+
+/// An interceptor that limits attempts with a [`TokenBucket`], waiting for a token when the
+/// bucket is empty.
+///
+/// Hooks can't wait, so in `modify_before_retry_loop` this puts an [`AttemptRateLimit`] in the
+/// config bag, and the runtime asynchronously acquires a token from it before the
+/// `read_before_attempt` hook of every attempt. Clones share the same bucket.
+#[derive(Clone, Debug)]
+pub struct AsyncRateLimitInterceptor {
+    bucket: Arc<TokenBucket>,
+}
+
+impl AsyncRateLimitInterceptor {
+    /// Create a new `AsyncRateLimitInterceptor` allowing `rate` attempts per second, with bursts
+    /// of up to `burst` attempts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` isn't positive or `burst` is zero.
+    pub fn new(rate: f64, burst: u64) -> Self {
+        Self::with_token_bucket(Arc::new(TokenBucket::new(rate, burst)))
+    }
+
+    /// Create a new `AsyncRateLimitInterceptor` that takes tokens from `bucket`, e.g. to share a
+    /// limit between clients.
+    pub fn with_token_bucket(bucket: Arc<TokenBucket>) -> Self {
+        Self { bucket }
+    }
+
+    /// Retrieve the bucket that tokens are taken from.
+    pub fn token_bucket(&self) -> &Arc<TokenBucket> {
+        &self.bucket
+    }
+}
+
+impl<ModReq, TxReq, TxRes, ModRes> Interceptor<ModReq, TxReq, TxRes, ModRes>
+    for AsyncRateLimitInterceptor
+{
+    fn hooks(&self) -> InterceptorHookSet {
+        InterceptorHookSet::MODIFY_BEFORE_RETRY_LOOP
+    }
+
+    fn modify_before_retry_loop(
+        &mut self,
+        _context: &mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        cfg.put(AttemptRateLimit {
+            bucket: self.bucket.clone(),
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AsyncRateLimitInterceptor, AttemptRateLimit, RateLimitExceeded, RateLimitInterceptor,
+        TokenBucket,
+    };
+    use aws_smithy_async::rt::sleep::{AsyncSleep, Sleep};
+    use aws_smithy_runtime_api::config_bag::ConfigBag;
+    use aws_smithy_runtime_api::interceptors::{InterceptorContext, Interceptors};
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn bucket_allows_bursts_and_refills_at_the_rate() {
+        let bucket = TokenBucket::new(10.0, 3);
+        let start = bucket.start;
+        for _ in 0..3 {
+            bucket.try_acquire_at(start).unwrap();
+        }
+        let err = bucket.try_acquire_at(start).unwrap_err();
+        assert_eq!(err.wait(), Duration::from_millis(100));
+
+        // One token is refilled every 100ms
+        let later = start + Duration::from_millis(150);
+        bucket.try_acquire_at(later).unwrap();
+        assert_eq!(
+            bucket.try_acquire_at(later).unwrap_err().wait(),
+            Duration::from_millis(50)
+        );
+
+        // Tokens don't accumulate beyond the burst
+        let much_later = start + Duration::from_secs(60);
+        for _ in 0..3 {
+            bucket.try_acquire_at(much_later).unwrap();
+        }
+        assert!(bucket.try_acquire_at(much_later).is_err());
+    }
+
+    #[test]
+    fn interceptor_rejects_attempts_once_the_bucket_is_empty() {
+        let bucket = Arc::new(TokenBucket::new(0.001, 2));
+        let mut interceptors = Interceptors::<(), (), (), ()>::new();
+        interceptors
+            .with_client_interceptor(RateLimitInterceptor::with_token_bucket(bucket.clone()));
+        let ctx = InterceptorContext::new(());
+        let mut cfg = ConfigBag::base();
+
+        interceptors.read_before_attempt(&ctx, &mut cfg).unwrap();
+        // Tokens taken elsewhere count against the same limit
+        bucket.try_acquire().unwrap();
+        let err = interceptors
+            .read_before_attempt(&ctx, &mut cfg)
+            .unwrap_err();
+        let source = std::error::Error::source(&err).unwrap();
+        assert!(source.is::<RateLimitExceeded>());
+    }
+
+    #[test]
+    fn async_interceptor_hands_the_bucket_to_the_runtime() {
+        let bucket = Arc::new(TokenBucket::new(1.0, 1));
+        let mut interceptors = Interceptors::<(), (), (), ()>::new();
+        interceptors
+            .with_client_interceptor(AsyncRateLimitInterceptor::with_token_bucket(bucket.clone()));
+        let mut ctx = InterceptorContext::new(());
+        let mut cfg = ConfigBag::base();
+
+        interceptors
+            .modify_before_retry_loop(&mut ctx, &mut cfg)
+            .unwrap();
+        let rate_limit = cfg.get::<AttemptRateLimit>().unwrap();
+        assert!(Arc::ptr_eq(rate_limit.token_bucket(), &bucket));
+        // No token is taken by the hook itself
+        bucket.try_acquire().unwrap();
+    }
+
+    #[derive(Debug, Default)]
+    struct RecordingSleep(Mutex<Vec<Duration>>);
+
+    impl AsyncSleep for RecordingSleep {
+        fn sleep(&self, duration: Duration) -> Sleep {
+            self.0.lock().unwrap().push(duration);
+            std::thread::sleep(duration);
+            Sleep::new(async {})
+        }
+    }
+
+    #[tokio::test]
+    async fn acquire_waits_for_a_token() {
+        let bucket = TokenBucket::new(100.0, 1);
+        let sleep = RecordingSleep::default();
+        let start = Instant::now();
+        bucket.acquire(&sleep).await;
+        assert!(sleep.0.lock().unwrap().is_empty());
+
+        bucket.acquire(&sleep).await;
+        assert!(!sleep.0.lock().unwrap().is_empty());
+        assert!(start.elapsed() >= Duration::from_millis(5));
+    }
+}
@@ -0,0 +1,186 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An interceptor that answers repeated requests from an in-memory cache.
+
+use aws_smithy_runtime_api::config_bag::ConfigBag;
+use aws_smithy_runtime_api::interceptors::{
+    Interceptor, InterceptorContext, InterceptorError, InterceptorHookSet,
+};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// An interceptor that caches successful responses in memory and serves repeated requests from
+/// the cache without sending them.
+///
+/// `key_fn` extracts the cache key from the modeled request, or returns `None` for requests that
+/// shouldn't be cached. `value_fn` extracts the value to cache from a successful modeled response,
+/// or returns `None` for responses that shouldn't be cached. Entries expire `ttl` after they were
+/// inserted.
+///
+/// The cache is checked in `modify_before_retry_loop`, the last hook that can modify the context
+/// before the first attempt; on a hit, the execution is completed with the cached value through
+/// [`InterceptorContext::cancel_with_response`] and nothing is sent. Successful responses are
+/// inserted in `read_after_execution`. Clones share the same cache.
+#[derive(Clone)]
+pub struct RequestCachingInterceptor<K, V, KeyFn, ValueFn> {
+    cache: Arc<Mutex<HashMap<K, (Instant, V)>>>,
+    ttl: Duration,
+    key_fn: KeyFn,
+    value_fn: ValueFn,
+}
+
+impl<K, V, KeyFn, ValueFn> RequestCachingInterceptor<K, V, KeyFn, ValueFn> {
+    /// Create a new `RequestCachingInterceptor` with an empty cache.
+    pub fn new(ttl: Duration, key_fn: KeyFn, value_fn: ValueFn) -> Self {
+        Self {
+            cache: Arc::new(Mutex::new(HashMap::new())),
+            ttl,
+            key_fn,
+            value_fn,
+        }
+    }
+
+    /// Remove all entries from the cache.
+    pub fn clear(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+}
+
+impl<K, V, KeyFn, ValueFn> fmt::Debug for RequestCachingInterceptor<K, V, KeyFn, ValueFn> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestCachingInterceptor")
+            .field("ttl", &self.ttl)
+            .field("entries", &self.cache.lock().unwrap().len())
+            .finish()
+    }
+}
+
+impl<ModReq, TxReq, TxRes, T, E, K, V, KeyFn, ValueFn>
+    Interceptor<ModReq, TxReq, TxRes, Result<T, E>>
+    for RequestCachingInterceptor<K, V, KeyFn, ValueFn>
+where
+    K: Eq + Hash,
+    V: Clone + Into<T>,
+    KeyFn: Fn(&ModReq) -> Option<K>,
+    ValueFn: Fn(&T) -> Option<V>,
+{
+    fn hooks(&self) -> InterceptorHookSet {
+        InterceptorHookSet::MODIFY_BEFORE_RETRY_LOOP | InterceptorHookSet::READ_AFTER_EXECUTION
+    }
+
+    fn modify_before_retry_loop(
+        &mut self,
+        context: &mut InterceptorContext<ModReq, TxReq, TxRes, Result<T, E>>,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        let key = match (self.key_fn)(context.modeled_request()) {
+            Some(key) => key,
+            None => return Ok(()),
+        };
+        let mut cache = self.cache.lock().unwrap();
+        let cached = match cache.get(&key) {
+            Some((inserted, _)) if inserted.elapsed() >= self.ttl => {
+                cache.remove(&key);
+                None
+            }
+            Some((_, value)) => Some(value.clone()),
+            None => None,
+        };
+        if let Some(value) = cached {
+            context.cancel_with_response(Ok(value.into()));
+        }
+        Ok(())
+    }
+
+    fn read_after_execution(
+        &mut self,
+        context: &InterceptorContext<ModReq, TxReq, TxRes, Result<T, E>>,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        // Don't refresh the entry that answered this execution
+        if context.has_injected_response() {
+            return Ok(());
+        }
+        let output = match context.modeled_response() {
+            Ok(Ok(output)) => output,
+            _ => return Ok(()),
+        };
+        if let (Some(key), Some(value)) = (
+            (self.key_fn)(context.modeled_request()),
+            (self.value_fn)(output),
+        ) {
+            self.cache
+                .lock()
+                .unwrap()
+                .insert(key, (Instant::now(), value));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RequestCachingInterceptor;
+    use aws_smithy_runtime_api::config_bag::ConfigBag;
+    use aws_smithy_runtime_api::interceptors::{InterceptorContext, Interceptors};
+    use std::time::Duration;
+
+    type Ctx = InterceptorContext<&'static str, (), (), Result<String, ()>>;
+
+    fn interceptors(ttl: Duration) -> Interceptors<&'static str, (), (), Result<String, ()>> {
+        let caching = RequestCachingInterceptor::new(
+            ttl,
+            |key: &&'static str| (!key.is_empty()).then(|| key.to_string()),
+            |output: &String| Some(output.clone()),
+        );
+        let mut interceptors = Interceptors::new();
+        interceptors.with_client_interceptor(caching);
+        interceptors
+    }
+
+    /// Run an execution for `key`, returning `true` if it was answered from the cache
+    fn execute(
+        interceptors: &mut Interceptors<&'static str, (), (), Result<String, ()>>,
+        key: &'static str,
+    ) -> bool {
+        let mut cfg = ConfigBag::base();
+        let mut ctx = Ctx::new(key);
+        interceptors
+            .modify_before_retry_loop(&mut ctx, &mut cfg)
+            .unwrap();
+        let hit = ctx.is_cancelled();
+        if !hit {
+            ctx.set_modeled_response(Ok(format!("response for {key}")));
+        }
+        interceptors.read_after_execution(&ctx, &mut cfg).unwrap();
+        assert_eq!(
+            ctx.modeled_response().unwrap(),
+            &Ok(format!("response for {key}"))
+        );
+        hit
+    }
+
+    #[test]
+    fn repeated_requests_are_served_from_the_cache() {
+        let mut interceptors = interceptors(Duration::from_secs(60));
+        assert!(!execute(&mut interceptors, "a"));
+        assert!(execute(&mut interceptors, "a"));
+        assert!(!execute(&mut interceptors, "b"));
+        // Requests without a key are never cached
+        assert!(!execute(&mut interceptors, ""));
+        assert!(!execute(&mut interceptors, ""));
+    }
+
+    #[test]
+    fn expired_entries_are_not_served() {
+        let mut interceptors = interceptors(Duration::ZERO);
+        assert!(!execute(&mut interceptors, "a"));
+        assert!(!execute(&mut interceptors, "a"));
+    }
+}
@@ -0,0 +1,91 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An interceptor that records the request ID returned by the service.
+
+use aws_smithy_runtime_api::config_bag::ConfigBag;
+use aws_smithy_runtime_api::interceptors::{
+    Interceptor, InterceptorContext, InterceptorError, InterceptorHookSet,
+};
+
+/// Response headers that may contain the request ID, in order of preference.
+const REQUEST_ID_HEADERS: &[&str] = &["x-amzn-requestid", "x-amz-request-id"];
+
+/// An interceptor that sets [`InterceptorContext::request_id`] from the response headers.
+///
+/// The ID is read from the `x-amzn-requestid` header, falling back to `x-amz-request-id`. Read
+/// hooks can't modify the context, so the ID is set in `modify_before_deserialization`, which runs
+/// immediately after `read_after_transmit`. It's available to all later hooks.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct RequestIdInterceptor;
+
+impl RequestIdInterceptor {
+    /// Create a new `RequestIdInterceptor`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<ModReq, TxReq, B, ModRes> Interceptor<ModReq, TxReq, http::Response<B>, ModRes>
+    for RequestIdInterceptor
+{
+    fn hooks(&self) -> InterceptorHookSet {
+        InterceptorHookSet::MODIFY_BEFORE_DESERIALIZATION
+    }
+
+    fn modify_before_deserialization(
+        &mut self,
+        context: &mut InterceptorContext<ModReq, TxReq, http::Response<B>, ModRes>,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        let headers = context.tx_response()?.headers();
+        let request_id = REQUEST_ID_HEADERS
+            .iter()
+            .find_map(|name| headers.get(*name))
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned);
+        if let Some(request_id) = request_id {
+            context.set_request_id(request_id);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RequestIdInterceptor;
+    use aws_smithy_runtime_api::config_bag::ConfigBag;
+    use aws_smithy_runtime_api::interceptors::{InterceptorContext, Interceptors};
+
+    fn request_id(response: http::Response<()>) -> Option<String> {
+        let mut interceptors = Interceptors::<(), (), http::Response<()>, ()>::new();
+        interceptors.with_client_interceptor(RequestIdInterceptor::new());
+        let mut ctx = InterceptorContext::new(());
+        ctx.set_tx_response(response);
+        interceptors
+            .modify_before_deserialization(&mut ctx, &mut ConfigBag::base())
+            .unwrap();
+        ctx.request_id().map(str::to_owned)
+    }
+
+    #[test]
+    fn request_id_from_headers() {
+        let response = http::Response::builder()
+            .header("x-amz-request-id", "fallback")
+            .header("x-amzn-requestid", "preferred")
+            .body(())
+            .unwrap();
+        assert_eq!(request_id(response).as_deref(), Some("preferred"));
+
+        let response = http::Response::builder()
+            .header("x-amz-request-id", "fallback")
+            .body(())
+            .unwrap();
+        assert_eq!(request_id(response).as_deref(), Some("fallback"));
+
+        assert_eq!(request_id(http::Response::new(())), None);
+    }
+}
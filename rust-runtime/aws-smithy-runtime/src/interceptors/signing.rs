@@ -0,0 +1,146 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An interceptor that signs the transport request.
+
+use crate::BoxError;
+use aws_smithy_runtime_api::config_bag::ConfigBag;
+use aws_smithy_runtime_api::interceptors::{
+    Interceptor, InterceptorContext, InterceptorError, InterceptorHookSet,
+};
+use std::fmt::Debug;
+
+/// A signing scheme used by the [`HeaderSigningInterceptor`], e.g. SigV4.
+///
+/// Implementations add whatever headers their scheme requires, such as `Authorization`, to the
+/// transport request.
+pub trait SignatureAlgorithm<TxReq>: Send + Sync + Debug {
+    /// Sign `request` by adding signature headers to it.
+    fn sign_headers(&self, request: &mut TxReq, cfg: &ConfigBag) -> Result<(), BoxError>;
+
+    /// Sign `request` like [`Self::sign_headers`], also returning the canonical request string
+    /// that was signed, if the scheme has one.
+    ///
+    /// The [`HeaderSigningInterceptor`] stores the canonical request in the
+    /// [`InterceptorContext`](InterceptorContext::canonical_request_string) so that audit
+    /// interceptors can log it. By default, no canonical request is returned.
+    fn sign_headers_with_canonical_request(
+        &self,
+        request: &mut TxReq,
+        cfg: &ConfigBag,
+    ) -> Result<Option<String>, BoxError> {
+        self.sign_headers(request, cfg).map(|_| None)
+    }
+}
+
+/// An interceptor that signs the transport request's headers in `modify_before_signing`.
+///
+/// The signing scheme is provided by a [`SignatureAlgorithm`], so that non-AWS schemes can be
+/// plugged in. Failures are returned as [signing](InterceptorError::signing) errors. If the
+/// algorithm provides the canonical request string it signed, it's available from
+/// `read_after_signing` through [`InterceptorContext::canonical_request_string`].
+#[derive(Debug)]
+pub struct HeaderSigningInterceptor<TxReq> {
+    algorithm: Box<dyn SignatureAlgorithm<TxReq>>,
+}
+
+impl<TxReq> HeaderSigningInterceptor<TxReq> {
+    /// Create a new `HeaderSigningInterceptor` that signs requests with `algorithm`.
+    pub fn new(algorithm: impl SignatureAlgorithm<TxReq> + 'static) -> Self {
+        Self {
+            algorithm: Box::new(algorithm),
+        }
+    }
+}
+
+impl<ModReq, TxReq, TxRes, ModRes> Interceptor<ModReq, TxReq, TxRes, ModRes>
+    for HeaderSigningInterceptor<TxReq>
+{
+    fn hooks(&self) -> InterceptorHookSet {
+        InterceptorHookSet::MODIFY_BEFORE_SIGNING
+    }
+
+    fn modify_before_signing(
+        &mut self,
+        context: &mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        let request = context.tx_request_mut()?;
+        let canonical_request = self
+            .algorithm
+            .sign_headers_with_canonical_request(request, cfg)
+            .map_err(InterceptorError::signing)?;
+        if let Some(canonical_request) = canonical_request {
+            context.set_canonical_request_string(canonical_request);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HeaderSigningInterceptor, SignatureAlgorithm};
+    use crate::BoxError;
+    use aws_smithy_runtime_api::config_bag::ConfigBag;
+    use aws_smithy_runtime_api::interceptors::{ErrorKind, InterceptorContext, Interceptors};
+
+    #[derive(Debug)]
+    struct TestSignature(Option<&'static str>);
+
+    impl SignatureAlgorithm<http::Request<()>> for TestSignature {
+        fn sign_headers(
+            &self,
+            request: &mut http::Request<()>,
+            _cfg: &ConfigBag,
+        ) -> Result<(), BoxError> {
+            let signature = self.0.ok_or("no signature available")?;
+            request
+                .headers_mut()
+                .insert(http::header::AUTHORIZATION, signature.parse()?);
+            Ok(())
+        }
+
+        fn sign_headers_with_canonical_request(
+            &self,
+            request: &mut http::Request<()>,
+            cfg: &ConfigBag,
+        ) -> Result<Option<String>, BoxError> {
+            self.sign_headers(request, cfg)?;
+            Ok(Some(format!("{}\n{}", request.method(), request.uri())))
+        }
+    }
+
+    fn interceptors(
+        signature: Option<&'static str>,
+    ) -> Interceptors<(), http::Request<()>, (), ()> {
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_client_interceptor(HeaderSigningInterceptor::new(TestSignature(signature)));
+        interceptors
+    }
+
+    #[test]
+    fn signs_request_headers() {
+        let mut ctx = InterceptorContext::new(());
+        ctx.set_tx_request(http::Request::new(()));
+        interceptors(Some("signature"))
+            .modify_before_signing(&mut ctx, &mut ConfigBag::base())
+            .unwrap();
+        let request = ctx.tx_request().unwrap();
+        assert_eq!(request.headers()["authorization"], "signature");
+        assert_eq!(ctx.canonical_request_string(), Some("GET\n/"));
+    }
+
+    #[test]
+    fn signing_failures_are_signing_errors() {
+        let mut ctx = InterceptorContext::new(());
+        ctx.set_tx_request(http::Request::new(()));
+        let err = interceptors(None)
+            .modify_before_signing(&mut ctx, &mut ConfigBag::base())
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Signing);
+        assert_eq!(ctx.canonical_request_string(), None);
+    }
+}
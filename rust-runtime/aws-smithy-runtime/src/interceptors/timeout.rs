@@ -0,0 +1,210 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An interceptor that fails executions and attempts that have run past their deadline.
+
+use aws_smithy_runtime_api::config_bag::ConfigBag;
+use aws_smithy_runtime_api::interceptors::{
+    Interceptor, InterceptorContext, InterceptorError, InterceptorHookSet,
+};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+struct ExecutionStart(Instant);
+
+#[derive(Debug)]
+struct AttemptStart(Instant);
+
+/// An error returned when an execution or attempt exceeds its timeout.
+#[derive(Debug)]
+pub struct TimeoutError {
+    kind: TimeoutKind,
+    timeout: Duration,
+}
+
+impl TimeoutError {
+    /// An error for a request the service didn't respond to within `timeout`
+    pub(crate) fn operation(timeout: Duration) -> Self {
+        Self {
+            kind: TimeoutKind::Operation,
+            timeout,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum TimeoutKind {
+    Attempt,
+    Execution,
+    Operation,
+}
+
+impl fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            TimeoutKind::Attempt => write!(f, "attempt timeout of {:?} exceeded", self.timeout),
+            TimeoutKind::Execution => {
+                write!(f, "execution timeout of {:?} exceeded", self.timeout)
+            }
+            TimeoutKind::Operation => {
+                write!(f, "operation timeout of {:?} exceeded", self.timeout)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TimeoutError {}
+
+/// An interceptor that enforces per-attempt and per-execution deadlines.
+///
+/// The start of the execution and of each attempt are recorded in the [`ConfigBag`]. Before the
+/// request is transmitted, the deadlines are checked and the attempt fails if either has passed.
+/// Attempts that time out may be retried, but executions that time out will not be.
+///
+/// Deadlines are only checked before transmitting; this interceptor can't interrupt a request that
+/// is already in flight.
+#[derive(Debug, Default)]
+pub struct TimeoutInterceptor {
+    attempt_timeout: Option<Duration>,
+    execution_timeout: Option<Duration>,
+}
+
+impl TimeoutInterceptor {
+    /// Create a new `TimeoutInterceptor` with no timeouts set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum duration of a single attempt.
+    pub fn with_attempt_timeout(mut self, attempt_timeout: Duration) -> Self {
+        self.attempt_timeout = Some(attempt_timeout);
+        self
+    }
+
+    /// Set the maximum duration of an entire execution, including all attempts.
+    pub fn with_execution_timeout(mut self, execution_timeout: Duration) -> Self {
+        self.execution_timeout = Some(execution_timeout);
+        self
+    }
+
+    /// The time left before the current attempt times out.
+    ///
+    /// Returns `None` if there is no attempt timeout or no attempt has started.
+    pub fn attempt_remaining(&self, cfg: &ConfigBag) -> Option<Duration> {
+        let start = cfg.get::<AttemptStart>()?;
+        Some(self.attempt_timeout?.saturating_sub(start.0.elapsed()))
+    }
+
+    /// The time left before the current execution times out.
+    ///
+    /// Returns `None` if there is no execution timeout or the execution hasn't started.
+    pub fn execution_remaining(&self, cfg: &ConfigBag) -> Option<Duration> {
+        let start = cfg.get::<ExecutionStart>()?;
+        Some(self.execution_timeout?.saturating_sub(start.0.elapsed()))
+    }
+}
+
+impl<ModReq, TxReq, TxRes, ModRes> Interceptor<ModReq, TxReq, TxRes, ModRes>
+    for TimeoutInterceptor
+{
+    fn hooks(&self) -> InterceptorHookSet {
+        InterceptorHookSet::READ_BEFORE_EXECUTION
+            | InterceptorHookSet::READ_BEFORE_ATTEMPT
+            | InterceptorHookSet::MODIFY_BEFORE_TRANSMIT
+    }
+
+    fn read_before_execution(
+        &mut self,
+        _context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        cfg.put(ExecutionStart(Instant::now()));
+        Ok(())
+    }
+
+    fn read_before_attempt(
+        &mut self,
+        _context: &InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        cfg.put(AttemptStart(Instant::now()));
+        Ok(())
+    }
+
+    fn modify_before_transmit(
+        &mut self,
+        _context: &mut InterceptorContext<ModReq, TxReq, TxRes, ModRes>,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        if let (Some(remaining), Some(timeout)) =
+            (self.execution_remaining(cfg), self.execution_timeout)
+        {
+            if remaining.is_zero() {
+                return Err(InterceptorError::modify_before_transmit(TimeoutError {
+                    kind: TimeoutKind::Execution,
+                    timeout,
+                }));
+            }
+        }
+        if let (Some(remaining), Some(timeout)) =
+            (self.attempt_remaining(cfg), self.attempt_timeout)
+        {
+            if remaining.is_zero() {
+                return Err(InterceptorError::retryable(TimeoutError {
+                    kind: TimeoutKind::Attempt,
+                    timeout,
+                }));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TimeoutInterceptor;
+    use aws_smithy_runtime_api::config_bag::ConfigBag;
+    use aws_smithy_runtime_api::interceptors::{InterceptorContext, Interceptors};
+    use std::time::Duration;
+
+    fn run_until_transmit(timeout: TimeoutInterceptor) -> Result<(), String> {
+        let mut interceptors = Interceptors::<(), (), (), ()>::new();
+        interceptors.with_client_interceptor(timeout);
+        let mut ctx = InterceptorContext::new(());
+        let mut cfg = ConfigBag::base();
+        interceptors
+            .client_read_before_execution(&ctx, &mut cfg)
+            .unwrap();
+        interceptors.read_before_attempt(&ctx, &mut cfg).unwrap();
+        interceptors
+            .modify_before_transmit(&mut ctx, &mut cfg)
+            .map_err(|err| std::error::Error::source(&err).unwrap().to_string())
+    }
+
+    #[test]
+    fn unexpired_deadlines_allow_transmit() {
+        let timeout = TimeoutInterceptor::new()
+            .with_attempt_timeout(Duration::from_secs(3600))
+            .with_execution_timeout(Duration::from_secs(3600));
+        assert!(run_until_transmit(timeout).is_ok());
+        assert!(run_until_transmit(TimeoutInterceptor::new()).is_ok());
+    }
+
+    #[test]
+    fn expired_deadlines_fail_transmit() {
+        let attempt = TimeoutInterceptor::new().with_attempt_timeout(Duration::ZERO);
+        assert_eq!(
+            run_until_transmit(attempt).unwrap_err(),
+            "attempt timeout of 0ns exceeded"
+        );
+
+        let execution = TimeoutInterceptor::new().with_execution_timeout(Duration::ZERO);
+        assert_eq!(
+            run_until_transmit(execution).unwrap_err(),
+            "execution timeout of 0ns exceeded"
+        );
+    }
+}
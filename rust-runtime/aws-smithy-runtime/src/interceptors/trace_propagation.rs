@@ -0,0 +1,141 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An interceptor that propagates the distributed tracing context to the service.
+
+use aws_smithy_runtime_api::config_bag::ConfigBag;
+use aws_smithy_runtime_api::interceptors::{
+    Interceptor, InterceptorContext, InterceptorError, InterceptorHookSet, TraceContext,
+};
+use http::header::{HeaderName, HeaderValue};
+
+/// The header format used to propagate a [`TraceContext`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TraceHeaderFormat {
+    /// The W3C Trace Context `traceparent` header, along with a W3C `baggage` header
+    #[default]
+    W3c,
+    /// The AWS X-Ray `X-Amzn-Trace-Id` header
+    XRay,
+}
+
+/// An interceptor that adds the execution's [`TraceContext`] to the transport request as headers
+/// in `modify_before_transmit`.
+///
+/// Nothing is added if the trace context is empty.
+#[derive(Debug, Default)]
+pub struct TracingPropagationInterceptor {
+    format: TraceHeaderFormat,
+}
+
+impl TracingPropagationInterceptor {
+    /// Create a new `TracingPropagationInterceptor` that uses the given header format.
+    pub fn new(format: TraceHeaderFormat) -> Self {
+        Self { format }
+    }
+
+    fn headers(&self, trace_context: &TraceContext) -> Vec<(HeaderName, String)> {
+        match self.format {
+            TraceHeaderFormat::W3c => {
+                let mut headers = vec![(
+                    HeaderName::from_static("traceparent"),
+                    format!(
+                        "00-{}-{}-01",
+                        trace_context.trace_id(),
+                        trace_context.span_id()
+                    ),
+                )];
+                if !trace_context.baggage().is_empty() {
+                    let mut baggage: Vec<_> = trace_context
+                        .baggage()
+                        .iter()
+                        .map(|(key, value)| format!("{key}={value}"))
+                        .collect();
+                    baggage.sort();
+                    headers.push((HeaderName::from_static("baggage"), baggage.join(",")));
+                }
+                headers
+            }
+            TraceHeaderFormat::XRay => vec![(
+                HeaderName::from_static("x-amzn-trace-id"),
+                format!(
+                    "Root={};Parent={};Sampled=1",
+                    trace_context.trace_id(),
+                    trace_context.span_id()
+                ),
+            )],
+        }
+    }
+}
+
+impl<ModReq, B, TxRes, ModRes> Interceptor<ModReq, http::Request<B>, TxRes, ModRes>
+    for TracingPropagationInterceptor
+{
+    fn hooks(&self) -> InterceptorHookSet {
+        InterceptorHookSet::MODIFY_BEFORE_TRANSMIT
+    }
+
+    fn modify_before_transmit(
+        &mut self,
+        context: &mut InterceptorContext<ModReq, http::Request<B>, TxRes, ModRes>,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), InterceptorError> {
+        if context.trace_context().is_empty() {
+            return Ok(());
+        }
+        let headers = self.headers(context.trace_context());
+        let request = context.tx_request_mut()?;
+        for (name, value) in headers {
+            let value =
+                HeaderValue::try_from(value).map_err(InterceptorError::modify_before_transmit)?;
+            request.headers_mut().insert(name, value);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TraceHeaderFormat, TracingPropagationInterceptor};
+    use aws_smithy_runtime_api::config_bag::ConfigBag;
+    use aws_smithy_runtime_api::interceptors::{InterceptorContext, Interceptors, TraceContext};
+
+    fn propagate(format: TraceHeaderFormat, trace_context: TraceContext) -> http::HeaderMap {
+        let mut interceptors = Interceptors::<(), http::Request<()>, (), ()>::new();
+        interceptors.with_client_interceptor(TracingPropagationInterceptor::new(format));
+        let mut ctx = InterceptorContext::new(()).with_trace_context(trace_context);
+        ctx.set_tx_request(http::Request::new(()));
+        interceptors
+            .modify_before_transmit(&mut ctx, &mut ConfigBag::base())
+            .unwrap();
+        ctx.tx_request().unwrap().headers().clone()
+    }
+
+    #[test]
+    fn w3c_headers() {
+        let trace_context = TraceContext::new("trace", "span")
+            .with_baggage("tenant", "a")
+            .with_baggage("region", "b");
+        let headers = propagate(TraceHeaderFormat::W3c, trace_context);
+        assert_eq!(headers["traceparent"], "00-trace-span-01");
+        assert_eq!(headers["baggage"], "region=b,tenant=a");
+    }
+
+    #[test]
+    fn xray_header() {
+        let headers = propagate(TraceHeaderFormat::XRay, TraceContext::new("trace", "span"));
+        assert_eq!(
+            headers["x-amzn-trace-id"],
+            "Root=trace;Parent=span;Sampled=1"
+        );
+    }
+
+    #[test]
+    fn empty_trace_context_is_not_propagated() {
+        let headers = propagate(TraceHeaderFormat::W3c, TraceContext::default());
+        assert!(headers.is_empty());
+    }
+}
@@ -10,34 +10,206 @@
     rust_2018_idioms
 )]
 
+/// Built-in interceptors.
+pub mod interceptors;
+
+use crate::interceptors::rate_limit::AttemptRateLimit;
+use crate::interceptors::timeout::TimeoutError;
+use aws_smithy_async::future::timeout::Timeout;
+use aws_smithy_async::rt::sleep::AsyncSleep;
 use aws_smithy_runtime_api::config_bag::ConfigBag;
-use aws_smithy_runtime_api::interceptors::{InterceptorContext, Interceptors};
+use aws_smithy_runtime_api::interceptors::{
+    AttemptOutcome, AttemptResult, CancelledError, ErrorDetails, InterceptorContext,
+    InterceptorError, InterceptorHookTimeout, Interceptors, OperationTimeout, RetryDecision,
+    RetryReason, ServiceName, TraceContext,
+};
+use aws_smithy_runtime_api::protocol::ProtocolId;
 use aws_smithy_runtime_api::runtime_plugin::RuntimePlugins;
+use aws_smithy_types::retry::{RetryConfig, RetryKind};
 use std::fmt::Debug;
 use std::future::Future;
 use std::pin::Pin;
+use std::time::{Duration, Instant};
 
 pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
 pub type BoxFallibleFut<T> = Pin<Box<dyn Future<Output = Result<T, BoxError>>>>;
 
+/// The longest a server's retry-after hint may delay a retry.
+pub const MAX_RETRY_AFTER: Duration = Duration::from_secs(20);
+
 pub trait TraceProbe: Send + Sync + Debug {
     fn dispatch_events(&self, cfg: &ConfigBag) -> BoxFallibleFut<()>;
 }
 
 pub trait RequestSerializer<In, TxReq>: Send + Sync + Debug {
     fn serialize_request(&self, req: &mut In, cfg: &ConfigBag) -> Result<TxReq, BoxError>;
+
+    /// The expected size of the serialized request's body in bytes, if it's known, e.g. from
+    /// the `Content-Length` header or the body's size hint.
+    ///
+    /// This is used to populate [`InterceptorContext::body_size_hint`] once the request has been
+    /// serialized. Defaults to `None`.
+    fn body_size_hint(&self, req: &TxReq) -> Option<u64> {
+        let _req = req;
+        None
+    }
+
+    /// The exact size of the serialized request's body in bytes, if it's known, e.g. because the
+    /// body is held in memory.
+    ///
+    /// This is used to populate [`InterceptorContext::request_payload_size`] once the request has
+    /// been serialized. Defaults to `None`.
+    fn request_payload_size(&self, req: &TxReq) -> Option<u64> {
+        let _req = req;
+        None
+    }
 }
 
 pub trait ResponseDeserializer<TxRes, Out>: Send + Sync + Debug {
     fn deserialize_response(&self, res: &mut TxRes, cfg: &ConfigBag) -> Result<Out, BoxError>;
+
+    /// Returns `true` if `res` has a streaming body that won't be fully consumed by
+    /// [`deserialize_response`](ResponseDeserializer::deserialize_response).
+    ///
+    /// This is used to populate [`InterceptorContext::is_streaming_response`] before
+    /// deserialization. Defaults to `false`.
+    fn is_streaming_response(&self, res: &TxRes) -> bool {
+        let _res = res;
+        false
+    }
+
+    /// Parse the structured details, such as the error code, out of an error response.
+    ///
+    /// This is only called when [`deserialize_response`](ResponseDeserializer::deserialize_response)
+    /// returned a service error, and is used to populate [`InterceptorContext::error_details`].
+    /// Defaults to `None`.
+    fn error_details(&self, res: &TxRes) -> Option<ErrorDetails> {
+        let _res = res;
+        None
+    }
+
+    /// The size of the response's body in bytes, if it's known, e.g. from the `Content-Length`
+    /// header or by measuring the body consumed by
+    /// [`deserialize_response`](ResponseDeserializer::deserialize_response).
+    ///
+    /// This is called after deserialization, and is used to populate
+    /// [`InterceptorContext::response_payload_size`]. Defaults to `None`.
+    fn response_payload_size(&self, res: &TxRes) -> Option<u64> {
+        let _res = res;
+        None
+    }
 }
 
 pub trait Connection<TxReq, TxRes>: Send + Sync + Debug {
     fn call(&self, req: &mut TxReq, cfg: &ConfigBag) -> BoxFallibleFut<TxRes>;
 }
 
+/// How a [`RetryStrategy`] classified the result of an attempt, independent of whether the
+/// strategy's retry budget allows another attempt.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RetryClassification {
+    kind: RetryKind,
+    retry_after: Option<Duration>,
+}
+
+impl RetryClassification {
+    /// Create a new `RetryClassification` of the given kind, without a retry delay.
+    pub fn new(kind: RetryKind) -> Self {
+        Self {
+            kind,
+            retry_after: None,
+        }
+    }
+
+    /// Set the minimum time to wait before retrying, e.g. from a `Retry-After` header.
+    pub fn with_retry_after(mut self, retry_after: Duration) -> Self {
+        self.retry_after = Some(retry_after);
+        self
+    }
+
+    /// Whether the result was a success, a retryable error, or a fatal error.
+    pub fn kind(&self) -> &RetryKind {
+        &self.kind
+    }
+
+    /// The minimum time to wait before retrying, if the result dictates one.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry_after
+    }
+
+    /// Returns `true` if the result may be retried.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self.kind, RetryKind::Error(_) | RetryKind::Explicit(_))
+    }
+}
+
 pub trait RetryStrategy<Out>: Send + Sync + Debug {
-    fn should_retry(&self, res: &Out, cfg: &ConfigBag) -> Result<bool, BoxError>;
+    /// Reinitialize any per-execution retry state stored in `cfg`.
+    ///
+    /// This is called at the start of every execution, once all runtime plugins have been
+    /// applied. Defaults to doing nothing.
+    fn reset(&self, cfg: &mut ConfigBag) -> Result<(), BoxError> {
+        let _cfg = cfg;
+        Ok(())
+    }
+
+    /// Decide whether the first attempt of an execution may be made.
+    ///
+    /// Returning an error fails the execution before any request is sent.
+    fn should_attempt_initial_request(&self, cfg: &ConfigBag) -> Result<(), BoxError>;
+
+    /// Classify the result of an attempt as a success, a transient, throttling, or server error,
+    /// or a fatal error.
+    ///
+    /// Classification doesn't depend on any retry state, so it can be tested on its own.
+    /// Implementations of [`should_attempt_retry`](RetryStrategy::should_attempt_retry) should
+    /// call this and then apply their retry budget, e.g. a token bucket or a maximum number of
+    /// attempts.
+    fn classify_response(&self, res: &Out) -> RetryClassification;
+
+    /// Decide whether another attempt should be made after an attempt resulted in `res`.
+    ///
+    /// This is also called when an attempt fails with an [`InterceptorError`] for which
+    /// [`InterceptorError::is_retryable`] or [`InterceptorError::is_throttling`] is `true`, so
+    /// that a throttling response rejected by an interceptor can be classified by its
+    /// [status code](InterceptorError::status_code). Other interceptor errors fail the execution
+    /// immediately, without consulting the retry strategy.
+    fn should_attempt_retry(&self, res: &Out, cfg: &ConfigBag) -> Result<bool, BoxError>;
+
+    /// The minimum time to wait before retrying after an attempt resulted in `res`.
+    ///
+    /// This allows a server to extend the backoff, e.g. through a `Retry-After` header. The hint
+    /// is only used when [`should_attempt_retry`](RetryStrategy::should_attempt_retry) decides to
+    /// retry, and an [`InterceptorError::retry_after`] delay takes precedence over it. Defaults to
+    /// the [`retry_after`](RetryClassification::retry_after) delay of the
+    /// [classification](RetryStrategy::classify_response). The hint is capped at
+    /// [`MAX_RETRY_AFTER`], and the runtime waits for the longer of the hint and the
+    /// [`backoff_duration`](RetryStrategy::backoff_duration).
+    fn retry_after_hint(&self, res: &Out) -> Option<Duration> {
+        self.classify_response(res).retry_after()
+    }
+
+    /// How long to wait before retrying after attempt number `attempt` failed, unless an
+    /// [`InterceptorError::retry_after`] delay or a
+    /// [`retry_after_hint`](RetryStrategy::retry_after_hint) asks for a longer wait.
+    ///
+    /// This is where strategies implement their backoff, e.g. exponential backoff with jitter.
+    /// Since it doesn't depend on the attempt's result, it can be tested on its own and called to
+    /// log the planned delay ahead of time. Defaults to no delay.
+    fn backoff_duration(&self, attempt: u32, cfg: &ConfigBag) -> Duration {
+        let _attempt = attempt;
+        let _cfg = cfg;
+        Duration::ZERO
+    }
+
+    /// Called when attempt number `attempt_number` succeeds and the execution won't be retried.
+    ///
+    /// Strategies backed by a retry token bucket should return a token to the bucket here.
+    /// Defaults to doing nothing.
+    fn on_attempt_success(&self, attempt_number: u32, cfg: &ConfigBag) {
+        let _attempt_number = attempt_number;
+        let _cfg = cfg;
+    }
 }
 
 pub trait AuthOrchestrator<Req>: Send + Sync + Debug {
@@ -46,6 +218,15 @@ pub trait AuthOrchestrator<Req>: Send + Sync + Debug {
 
 pub trait EndpointOrchestrator<Req>: Send + Sync + Debug {
     fn resolve_and_apply_endpoint(&self, req: &mut Req, cfg: &ConfigBag) -> Result<(), BoxError>;
+
+    /// The endpoint that `req` will be sent to, used to populate
+    /// [`InterceptorContext::service_endpoint`].
+    ///
+    /// Defaults to `None` for request types that don't expose a URI.
+    fn service_endpoint(&self, req: &Req) -> Option<http::Uri> {
+        let _req = req;
+        None
+    }
     // TODO(jdisanti) The EP Orc and Auth Orc need to share info on auth schemes but I'm not sure how that should happen
     fn resolve_auth_schemes(&self) -> Result<Vec<String>, BoxError>;
 }
@@ -57,6 +238,7 @@ pub trait EndpointOrchestrator<Req>: Send + Sync + Debug {
 ///     - The 'success' output message e.g. `ListObjectsResponse`
 ///     - The 'failure' output message e.g. `NoSuchBucketException`
 pub async fn invoke<In, Req, Res, T>(
+    operation_name: &'static str,
     input: In,
     interceptors: &mut Interceptors<In, Req, Res, Result<T, BoxError>>,
     runtime_plugins: &RuntimePlugins,
@@ -69,48 +251,197 @@ where
     Res: 'static,
     T: 'static,
 {
-    let mut ctx: InterceptorContext<In, Req, Res, Result<T, BoxError>> =
-        InterceptorContext::new(input);
-
     runtime_plugins.apply_client_configuration(cfg)?;
-    interceptors.client_read_before_execution(&ctx, cfg)?;
+    interceptors.resolve_deferred_interceptors().await;
+    interceptors.instantiate_interceptor_factories(cfg);
+    let hook_timeout = configured_hook_timeout(cfg);
+    let mut ctx: InterceptorContext<In, Req, Res, Result<T, BoxError>> =
+        InterceptorContext::new(input)
+            .with_service_name(cfg.get::<ServiceName>().map_or("", ServiceName::as_str))
+            .with_operation_name(operation_name)
+            .with_trace_context(cfg.get::<TraceContext>().cloned().unwrap_or_default());
+    run_hook(interceptors, hook_timeout, |interceptors| {
+        interceptors.client_read_before_execution(&ctx, cfg)
+    })?;
 
     runtime_plugins.apply_operation_configuration(cfg)?;
-    interceptors.operation_read_before_execution(&ctx, cfg)?;
+    runtime_plugins.validate(cfg)?;
+    // Operation configuration may override the client's hook timeout
+    let hook_timeout = configured_hook_timeout(cfg);
+    if let Some(protocol) = cfg.get::<ProtocolId>() {
+        ctx.set_protocol(*protocol);
+    }
+    if let Some(retry_config) = cfg.get::<RetryConfig>() {
+        ctx.set_max_attempts(retry_config.max_attempts());
+    }
+    // The strategy is shared so that it can modify the bag it's stored in
+    let retry_strategy = cfg
+        .get_shared::<Box<dyn RetryStrategy<Result<T, BoxError>>>>()
+        .ok_or("missing retry strategy")?;
+    retry_strategy.reset(cfg)?;
+    run_hook(interceptors, hook_timeout, |interceptors| {
+        interceptors.operation_read_before_execution(&ctx, cfg)
+    })?;
 
-    interceptors.read_before_serialization(&ctx, cfg)?;
-    interceptors.modify_before_serialization(&mut ctx, cfg)?;
+    run_hook(interceptors, hook_timeout, |interceptors| {
+        interceptors.read_before_serialization(&ctx, cfg)
+    })?;
+    run_hook(interceptors, hook_timeout, |interceptors| {
+        interceptors.modify_before_serialization(&mut ctx, cfg)
+    })?;
+    if ctx.is_cancelled() {
+        return complete_cancelled_execution(ctx, cfg, interceptors);
+    }
 
     let request_serializer = cfg
         .get::<Box<dyn RequestSerializer<In, Req>>>()
         .ok_or("missing serializer")?;
     let req = request_serializer.serialize_request(ctx.modeled_request_mut(), cfg)?;
+    if let Some(body_size_hint) = request_serializer.body_size_hint(&req) {
+        ctx.set_body_size_hint(body_size_hint);
+    }
+    if let Some(request_payload_size) = request_serializer.request_payload_size(&req) {
+        ctx.set_request_payload_size(request_payload_size);
+    }
     ctx.set_tx_request(req);
 
-    interceptors.read_after_serialization(&ctx, cfg)?;
-    interceptors.modify_before_retry_loop(&mut ctx, cfg)?;
+    run_hook(interceptors, hook_timeout, |interceptors| {
+        interceptors.read_after_serialization(&ctx, cfg)
+    })?;
+    run_hook(interceptors, hook_timeout, |interceptors| {
+        interceptors.modify_before_retry_loop(&mut ctx, cfg)
+    })?;
+    if ctx.is_cancelled() {
+        return complete_cancelled_execution(ctx, cfg, interceptors);
+    }
+
+    let retry_strategy = cfg
+        .get::<Box<dyn RetryStrategy<Result<T, BoxError>>>>()
+        .ok_or("missing retry strategy")?;
+    retry_strategy.should_attempt_initial_request(cfg)?;
 
     loop {
-        make_an_attempt(&mut ctx, cfg, interceptors).await?;
-        interceptors.read_after_attempt(&ctx, cfg)?;
-        interceptors.modify_before_attempt_completion(&mut ctx, cfg)?;
-
-        let retry_strategy = cfg
-            .get::<Box<dyn RetryStrategy<Result<T, BoxError>>>>()
-            .ok_or("missing retry strategy")?;
-        let mod_res = ctx
-            .modeled_response()
-            .expect("it's set during 'make_an_attempt'");
-        if retry_strategy.should_retry(mod_res, cfg)? {
+        if let Err(err) = make_an_attempt(&mut ctx, cfg, interceptors).await {
+            let may_retry = err
+                .downcast_ref::<InterceptorError>()
+                .map(|err| err.is_retryable() || err.is_throttling())
+                .unwrap_or(false);
+            // Don't bother asking the retry strategy about errors that can never succeed on retry
+            if !may_retry {
+                return Err(err);
+            }
+
+            let retry_strategy = cfg
+                .get::<Box<dyn RetryStrategy<Result<T, BoxError>>>>()
+                .ok_or("missing retry strategy")?;
+            let server_hint = err
+                .downcast_ref::<InterceptorError>()
+                .and_then(InterceptorError::retry_after);
+            let attempt_result = Err(err);
+            if retry_strategy.should_attempt_retry(&attempt_result, cfg)? {
+                let hint = server_hint.or_else(|| retry_strategy.retry_after_hint(&attempt_result));
+                let backoff = retry_strategy.backoff_duration(ctx.attempt_number(), cfg);
+                let mut delay = retry_delay(hint, backoff);
+                if let Err(err) = attempt_result {
+                    let err = err
+                        .downcast::<InterceptorError>()
+                        .expect("only interceptor errors are retried");
+                    let outcome = AttemptOutcome::Failed(err.kind());
+                    ctx.record_attempt_error(*err);
+                    record_attempt_result(&mut ctx, outcome, RetryDecision::retry(delay));
+                }
+                ctx.set_retry_decision(RetryDecision::retry(delay));
+                run_hook(interceptors, hook_timeout, |interceptors| {
+                    interceptors.modify_before_retry(&mut ctx, cfg, &mut delay)
+                })?;
+                let actual_sleep = sleep_before_retry(delay, cfg).await?;
+                run_hook(interceptors, hook_timeout, |interceptors| {
+                    interceptors.read_between_attempts(&ctx, cfg, actual_sleep)
+                })?;
+                continue;
+            }
+            return attempt_result;
+        }
+        if ctx.is_cancelled() {
+            return complete_cancelled_execution(ctx, cfg, interceptors);
+        }
+        run_hook(interceptors, hook_timeout, |interceptors| {
+            interceptors.modify_before_attempt_completion(&mut ctx, cfg)
+        })?;
+        if ctx.is_cancelled() {
+            return complete_cancelled_execution(ctx, cfg, interceptors);
+        }
+
+        // A response whose body was corrupted in transit is treated like any other failed attempt
+        if let Err(err) = ctx.verify_response_checksum() {
+            *ctx.modeled_response_mut()? = Err(err.into());
+        }
+
+        // Decide whether to retry before `read_after_attempt` so that interceptors can observe it
+        let (outcome, retry_decision) = {
+            let retry_strategy = cfg
+                .get::<Box<dyn RetryStrategy<Result<T, BoxError>>>>()
+                .ok_or("missing retry strategy")?;
+            let mod_res = ctx
+                .modeled_response()
+                .expect("it's set during 'make_an_attempt'");
+            let outcome = match mod_res {
+                Ok(_) => AttemptOutcome::Success,
+                Err(err) => match err.downcast_ref::<InterceptorError>() {
+                    Some(err) => AttemptOutcome::Failed(err.kind()),
+                    None => AttemptOutcome::ServiceError,
+                },
+            };
+            let retry_decision = if retry_strategy.should_attempt_retry(mod_res, cfg)? {
+                let hint = mod_res
+                    .as_ref()
+                    .err()
+                    .and_then(|err| err.downcast_ref::<InterceptorError>())
+                    .and_then(InterceptorError::retry_after)
+                    .or_else(|| retry_strategy.retry_after_hint(mod_res));
+                let backoff = retry_strategy.backoff_duration(ctx.attempt_number(), cfg);
+                RetryDecision::retry(retry_delay(hint, backoff))
+            } else if mod_res.is_ok() {
+                RetryDecision::no_retry(RetryReason::Success)
+            } else {
+                RetryDecision::no_retry(RetryReason::RetryStrategyDeclined)
+            };
+            (outcome, retry_decision)
+        };
+        ctx.set_retry_decision(retry_decision);
+        record_attempt_result(&mut ctx, outcome, retry_decision);
+        run_hook(interceptors, hook_timeout, |interceptors| {
+            interceptors.read_after_attempt(&ctx, cfg)
+        })?;
+
+        if retry_decision.should_retry() {
+            let mut delay = retry_decision.delay().unwrap_or_default();
+            run_hook(interceptors, hook_timeout, |interceptors| {
+                interceptors.modify_before_retry(&mut ctx, cfg, &mut delay)
+            })?;
+            let actual_sleep = sleep_before_retry(delay, cfg).await?;
+            run_hook(interceptors, hook_timeout, |interceptors| {
+                interceptors.read_between_attempts(&ctx, cfg, actual_sleep)
+            })?;
             continue;
         }
+        if retry_decision.reason() == RetryReason::Success {
+            let retry_strategy = cfg
+                .get::<Box<dyn RetryStrategy<Result<T, BoxError>>>>()
+                .ok_or("missing retry strategy")?;
+            retry_strategy.on_attempt_success(ctx.attempt_number(), cfg);
+        }
 
-        interceptors.modify_before_completion(&mut ctx, cfg)?;
+        run_hook(interceptors, hook_timeout, |interceptors| {
+            interceptors.modify_before_completion(&mut ctx, cfg)
+        })?;
         let trace_probe = cfg
             .get::<Box<dyn TraceProbe>>()
             .ok_or("missing trace probes")?;
         trace_probe.dispatch_events(cfg);
-        interceptors.read_after_execution(&ctx, cfg)?;
+        run_hook(interceptors, hook_timeout, |interceptors| {
+            interceptors.read_after_execution(&ctx, cfg)
+        })?;
 
         break;
     }
@@ -119,6 +450,93 @@ where
     modeled_response
 }
 
+// A retry-after hint is a minimum: the strategy's backoff still applies when it's longer. Hints are
+// capped so that a misbehaving server can't stall the client indefinitely.
+fn retry_delay(hint: Option<Duration>, backoff: Duration) -> Duration {
+    hint.map_or(backoff, |hint| hint.min(MAX_RETRY_AFTER).max(backoff))
+}
+
+// Wait before the next attempt using the sleep implementation from the config bag, returning how
+// long was actually spent waiting. No sleep implementation is needed if there's nothing to wait for.
+async fn sleep_before_retry(delay: Duration, cfg: &ConfigBag) -> Result<Duration, BoxError> {
+    let start = Instant::now();
+    if !delay.is_zero() {
+        let sleep = cfg
+            .get::<Box<dyn AsyncSleep>>()
+            .ok_or("missing sleep implementation")?
+            .sleep(delay);
+        sleep.await;
+    }
+    Ok(start.elapsed())
+}
+
+// The configured limit on how long each interceptor hook may run, if any
+fn configured_hook_timeout(cfg: &ConfigBag) -> Option<Duration> {
+    cfg.get::<InterceptorHookTimeout>()
+        .map(InterceptorHookTimeout::as_duration)
+}
+
+// Run an interceptor hook, failing it if it runs for longer than `hook_timeout`
+fn run_hook<In, Req, Res, Out>(
+    interceptors: &mut Interceptors<In, Req, Res, Out>,
+    hook_timeout: Option<Duration>,
+    hook: impl FnOnce(&mut Interceptors<In, Req, Res, Out>) -> Result<(), InterceptorError>,
+) -> Result<(), InterceptorError> {
+    match hook_timeout {
+        Some(timeout) => interceptors.run_hook_with_timeout(hook, timeout),
+        None => hook(interceptors),
+    }
+}
+
+// Record the result of the current attempt, measuring its latency from when it started
+fn record_attempt_result<In, Req, Res, T>(
+    ctx: &mut InterceptorContext<In, Req, Res, Result<T, BoxError>>,
+    outcome: AttemptOutcome,
+    retry_decision: RetryDecision,
+) {
+    let latency = ctx
+        .attempt_start()
+        .map_or(Duration::ZERO, |start| start.elapsed());
+    ctx.record_attempt_result(AttemptResult::new(
+        ctx.attempt_number(),
+        latency,
+        outcome,
+        retry_decision,
+    ));
+}
+
+// When an interceptor cancels the execution, all remaining hooks are skipped except for the
+// ones that complete the execution, which see a `CancelledError` as the modeled response.
+fn complete_cancelled_execution<In, Req, Res, T>(
+    mut ctx: InterceptorContext<In, Req, Res, Result<T, BoxError>>,
+    cfg: &mut ConfigBag,
+    interceptors: &mut Interceptors<In, Req, Res, Result<T, BoxError>>,
+) -> Result<T, BoxError> {
+    let hook_timeout = configured_hook_timeout(cfg);
+    // An injected response is returned as if it came from the service
+    if !ctx.has_injected_response() {
+        let reason = ctx
+            .cancellation_reason()
+            .expect("only called for cancelled executions")
+            .clone();
+        let cancelled = Err(CancelledError::new(reason).into());
+        if ctx.modeled_response().is_ok() {
+            *ctx.modeled_response_mut()? = cancelled;
+        } else {
+            ctx.set_modeled_response(cancelled);
+        }
+    }
+
+    run_hook(interceptors, hook_timeout, |interceptors| {
+        interceptors.modify_before_completion(&mut ctx, cfg)
+    })?;
+    run_hook(interceptors, hook_timeout, |interceptors| {
+        interceptors.read_after_execution(&ctx, cfg)
+    })?;
+
+    ctx.into_modeled_response()?
+}
+
 // Making an HTTP request can fail for several reasons, but we still need to
 // call lifecycle events when that happens. Therefore, we define this
 // `make_an_attempt` function to make error handling simpler.
@@ -133,7 +551,18 @@ where
     Res: 'static,
     T: 'static,
 {
-    interceptors.read_before_attempt(ctx, cfg)?;
+    let hook_timeout = configured_hook_timeout(cfg);
+    // Hooks can't wait, so a rate limit that waits for a token is applied here instead
+    if let Some(rate_limit) = cfg.get::<AttemptRateLimit>() {
+        let sleep = cfg
+            .get::<Box<dyn AsyncSleep>>()
+            .ok_or("missing sleep implementation")?;
+        rate_limit.token_bucket().acquire(sleep.as_ref()).await;
+    }
+    ctx.start_attempt();
+    run_hook(interceptors, hook_timeout, |interceptors| {
+        interceptors.read_before_attempt(ctx, cfg)
+    })?;
 
     let tx_req_mut = ctx.tx_request_mut().expect("tx_request has been set");
     let endpoint_orchestrator = cfg
@@ -141,19 +570,56 @@ where
         .ok_or("missing endpoint orchestrator")?;
     endpoint_orchestrator.resolve_and_apply_endpoint(tx_req_mut, cfg)?;
 
-    interceptors.modify_before_signing(ctx, cfg)?;
-    interceptors.read_before_signing(ctx, cfg)?;
+    run_hook(interceptors, hook_timeout, |interceptors| {
+        interceptors.modify_before_signing(ctx, cfg)
+    })?;
+    if ctx.is_cancelled() {
+        return Ok(());
+    }
+    run_hook(interceptors, hook_timeout, |interceptors| {
+        interceptors.read_before_signing(ctx, cfg)
+    })?;
 
+    // An auth scheme selected by an interceptor takes precedence over the configured one
+    let auth_scheme = ctx.auth_scheme().cloned();
     let tx_req_mut = ctx.tx_request_mut().expect("tx_request has been set");
-    let auth_orchestrator = cfg
-        .get::<Box<dyn AuthOrchestrator<Req>>>()
-        .ok_or("missing auth orchestrator")?;
-    auth_orchestrator.auth_request(tx_req_mut, cfg)?;
+    match auth_scheme {
+        Some(auth_scheme) => auth_scheme.signer().sign_request(tx_req_mut, cfg)?,
+        None => {
+            let auth_orchestrator = cfg
+                .get::<Box<dyn AuthOrchestrator<Req>>>()
+                .ok_or("missing auth orchestrator")?;
+            auth_orchestrator.auth_request(tx_req_mut, cfg)?;
+        }
+    }
 
-    interceptors.read_after_signing(ctx, cfg)?;
-    interceptors.modify_before_transmit(ctx, cfg)?;
-    interceptors.read_before_transmit(ctx, cfg)?;
+    run_hook(interceptors, hook_timeout, |interceptors| {
+        interceptors.read_after_signing(ctx, cfg)
+    })?;
+    run_hook(interceptors, hook_timeout, |interceptors| {
+        interceptors.modify_before_transmit(ctx, cfg)
+    })?;
+    if ctx.is_cancelled() {
+        return Ok(());
+    }
 
+    // Interceptors may have changed the endpoint, so look it up as late as possible
+    let endpoint_orchestrator = cfg
+        .get::<Box<dyn EndpointOrchestrator<Req>>>()
+        .ok_or("missing endpoint orchestrator")?;
+    if let Some(endpoint) = endpoint_orchestrator.service_endpoint(ctx.tx_request()?) {
+        ctx.set_service_endpoint(endpoint);
+    }
+    run_hook(interceptors, hook_timeout, |interceptors| {
+        interceptors.read_before_transmit(ctx, cfg)
+    })?;
+
+    // A timeout set by an interceptor takes precedence over the client's
+    let operation_timeout = ctx.operation_timeout().or_else(|| {
+        cfg.get::<OperationTimeout>()
+            .map(OperationTimeout::as_duration)
+    });
+    ctx.mark_transmit_start();
     // The connection consumes the request but we need to keep a copy of it
     // within the interceptor context, so we clone it here.
     let res = {
@@ -161,21 +627,286 @@ where
         let connection = cfg
             .get::<Box<dyn Connection<Req, Res>>>()
             .ok_or("missing connector")?;
-        connection.call(tx_req, cfg).await?
+        let call = connection.call(tx_req, cfg);
+        match operation_timeout {
+            Some(timeout) => {
+                let sleep = cfg
+                    .get::<Box<dyn AsyncSleep>>()
+                    .ok_or("missing sleep implementation")?
+                    .sleep(timeout);
+                Timeout::new(call, sleep)
+                    .await
+                    .unwrap_or_else(|_| Err(TimeoutError::operation(timeout).into()))
+            }
+            None => call.await,
+        }
+    };
+    ctx.mark_transmit_end();
+    let res = match res {
+        Ok(res) => res,
+        Err(err) => {
+            run_hook(interceptors, hook_timeout, |interceptors| {
+                interceptors.read_on_transport_error(ctx, cfg, err.as_ref())
+            })?;
+            return Err(err);
+        }
     };
     ctx.set_tx_response(res);
 
-    interceptors.read_after_transmit(ctx, cfg)?;
-    interceptors.modify_before_deserialization(ctx, cfg)?;
-    interceptors.read_before_deserialization(ctx, cfg)?;
-    let tx_res = ctx.tx_response_mut().expect("tx_response has been set");
+    run_hook(interceptors, hook_timeout, |interceptors| {
+        interceptors.read_after_transmit(ctx, cfg)
+    })?;
+    run_hook(interceptors, hook_timeout, |interceptors| {
+        interceptors.modify_before_deserialization(ctx, cfg)
+    })?;
+    if ctx.is_cancelled() {
+        return Ok(());
+    }
+    // The deserializer is shared so that the read hooks can borrow the bag mutably
     let response_deserializer = cfg
-        .get::<Box<dyn ResponseDeserializer<Res, Result<T, BoxError>>>>()
+        .get_shared::<Box<dyn ResponseDeserializer<Res, Result<T, BoxError>>>>()
         .ok_or("missing response deserializer")?;
+    let is_streaming = response_deserializer
+        .is_streaming_response(ctx.tx_response().expect("tx_response has been set"));
+    ctx.set_streaming_response(is_streaming);
+    run_hook(interceptors, hook_timeout, |interceptors| {
+        interceptors.read_before_deserialization(ctx, cfg)
+    })?;
+    let tx_res = ctx.tx_response_mut().expect("tx_response has been set");
     let res = response_deserializer.deserialize_response(tx_res, cfg)?;
+    let error_details = res
+        .as_ref()
+        .err()
+        .and_then(|_| response_deserializer.error_details(tx_res));
+    let response_payload_size = response_deserializer.response_payload_size(tx_res);
+    if let Some(error_details) = error_details {
+        ctx.set_error_details(error_details);
+    }
+    if let Some(response_payload_size) = response_payload_size {
+        ctx.set_response_payload_size(response_payload_size);
+    }
     ctx.set_modeled_response(res);
 
-    interceptors.read_after_deserialization(ctx, cfg)?;
+    run_hook(interceptors, hook_timeout, |interceptors| {
+        interceptors.read_after_deserialization(ctx, cfg)
+    })?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        invoke, retry_delay, AuthOrchestrator, BoxError, BoxFallibleFut, Connection,
+        EndpointOrchestrator, RequestSerializer, ResponseDeserializer, RetryClassification,
+        RetryStrategy, TraceProbe, MAX_RETRY_AFTER,
+    };
+    use crate::interceptors::AsyncRateLimitInterceptor;
+    use aws_smithy_async::rt::sleep::{AsyncSleep, Sleep};
+    use aws_smithy_runtime_api::config_bag::ConfigBag;
+    use aws_smithy_runtime_api::interceptors::{
+        Interceptor, InterceptorContext, InterceptorError, Interceptors,
+    };
+    use aws_smithy_runtime_api::runtime_plugin::RuntimePlugins;
+    use aws_smithy_types::retry::{ErrorKind, RetryKind};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    type Output = Result<String, BoxError>;
+
+    #[derive(Debug)]
+    struct Serializer;
+
+    impl RequestSerializer<(), ()> for Serializer {
+        fn serialize_request(&self, _req: &mut (), _cfg: &ConfigBag) -> Result<(), BoxError> {
+            Ok(())
+        }
+    }
+
+    #[derive(Debug)]
+    struct Deserializer;
+
+    impl ResponseDeserializer<u16, Output> for Deserializer {
+        fn deserialize_response(
+            &self,
+            res: &mut u16,
+            _cfg: &ConfigBag,
+        ) -> Result<Output, BoxError> {
+            Ok(match *res {
+                200 => Ok("success".to_string()),
+                status => Err(format!("service error ({status})").into()),
+            })
+        }
+    }
+
+    /// Responds with each of the given status codes in turn.
+    #[derive(Debug)]
+    struct CannedConnection(Mutex<Vec<u16>>);
+
+    impl Connection<(), u16> for CannedConnection {
+        fn call(&self, _req: &mut (), _cfg: &ConfigBag) -> BoxFallibleFut<u16> {
+            let status = self.0.lock().unwrap().remove(0);
+            Box::pin(async move { Ok(status) })
+        }
+    }
+
+    #[derive(Debug)]
+    struct Orchestrator;
+
+    impl EndpointOrchestrator<()> for Orchestrator {
+        fn resolve_and_apply_endpoint(
+            &self,
+            _req: &mut (),
+            _cfg: &ConfigBag,
+        ) -> Result<(), BoxError> {
+            Ok(())
+        }
+
+        fn resolve_auth_schemes(&self) -> Result<Vec<String>, BoxError> {
+            Ok(Vec::new())
+        }
+    }
+
+    impl AuthOrchestrator<()> for Orchestrator {
+        fn auth_request(&self, _req: &mut (), _cfg: &ConfigBag) -> Result<(), BoxError> {
+            Ok(())
+        }
+    }
+
+    impl TraceProbe for Orchestrator {
+        fn dispatch_events(&self, _cfg: &ConfigBag) -> BoxFallibleFut<()> {
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    /// Retries every service error, without any backoff.
+    #[derive(Debug)]
+    struct RetryErrors;
+
+    impl RetryStrategy<Output> for RetryErrors {
+        fn should_attempt_initial_request(&self, _cfg: &ConfigBag) -> Result<(), BoxError> {
+            Ok(())
+        }
+
+        fn classify_response(&self, res: &Output) -> RetryClassification {
+            match res {
+                Ok(_) => RetryClassification::new(RetryKind::Unnecessary),
+                Err(_) => RetryClassification::new(RetryKind::Error(ErrorKind::ServerError)),
+            }
+        }
+
+        fn should_attempt_retry(&self, res: &Output, _cfg: &ConfigBag) -> Result<bool, BoxError> {
+            Ok(self.classify_response(res).is_retryable())
+        }
+    }
+
+    /// Records the attempt number and status code of every attempt.
+    struct AttemptRecorder(Arc<Mutex<Vec<(u32, u16)>>>);
+
+    impl Interceptor<(), (), u16, Output> for AttemptRecorder {
+        fn read_after_attempt(
+            &mut self,
+            context: &InterceptorContext<(), (), u16, Output>,
+            _cfg: &mut ConfigBag,
+        ) -> Result<(), InterceptorError> {
+            let status = *context.tx_response()?;
+            self.0
+                .lock()
+                .unwrap()
+                .push((context.attempt_number(), status));
+            Ok(())
+        }
+    }
+
+    fn test_config(statuses: Vec<u16>) -> ConfigBag {
+        let mut cfg = ConfigBag::base();
+        cfg.put(Box::new(Serializer) as Box<dyn RequestSerializer<(), ()>>)
+            .put(Box::new(Deserializer) as Box<dyn ResponseDeserializer<u16, Output>>)
+            .put(Box::new(CannedConnection(Mutex::new(statuses))) as Box<dyn Connection<(), u16>>)
+            .put(Box::new(Orchestrator) as Box<dyn EndpointOrchestrator<()>>)
+            .put(Box::new(Orchestrator) as Box<dyn AuthOrchestrator<()>>)
+            .put(Box::new(Orchestrator) as Box<dyn TraceProbe>)
+            .put(Box::new(RetryErrors) as Box<dyn RetryStrategy<Output>>);
+        cfg
+    }
+
+    /// Records the requested delays, sleeping for real so that the rate limit's bucket refills
+    #[derive(Clone, Debug, Default)]
+    struct RecordingSleep(Arc<Mutex<Vec<Duration>>>);
+
+    impl AsyncSleep for RecordingSleep {
+        fn sleep(&self, duration: Duration) -> Sleep {
+            self.0.lock().unwrap().push(duration);
+            std::thread::sleep(duration);
+            Sleep::new(async {})
+        }
+    }
+
+    #[tokio::test]
+    async fn attempts_wait_for_the_async_rate_limit() {
+        let attempts = Arc::new(Mutex::new(Vec::new()));
+        let mut interceptors = Interceptors::new();
+        interceptors
+            .with_client_interceptor(AsyncRateLimitInterceptor::new(1000.0, 1))
+            .with_client_interceptor(AttemptRecorder(attempts.clone()));
+        let sleep = RecordingSleep::default();
+        let mut cfg = test_config(vec![503, 200]);
+        cfg.put(Box::new(sleep.clone()) as Box<dyn AsyncSleep>);
+
+        let output = invoke(
+            "GetObject",
+            (),
+            &mut interceptors,
+            &RuntimePlugins::new(),
+            &mut cfg,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(output, "success");
+        assert_eq!(*attempts.lock().unwrap(), vec![(1, 503), (2, 200)]);
+        // The first attempt took the only token, so the retry had to wait for the next one
+        assert!(!sleep.0.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn retry_after_hint_is_a_capped_minimum() {
+        let backoff = Duration::from_secs(2);
+        assert_eq!(retry_delay(None, backoff), backoff);
+        assert_eq!(retry_delay(Some(Duration::from_secs(1)), backoff), backoff);
+        assert_eq!(
+            retry_delay(Some(Duration::from_secs(5)), backoff),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            retry_delay(Some(Duration::from_secs(3600)), backoff),
+            MAX_RETRY_AFTER
+        );
+        // The strategy's own backoff isn't capped
+        assert_eq!(
+            retry_delay(Some(Duration::from_secs(3600)), Duration::from_secs(60)),
+            Duration::from_secs(60)
+        );
+    }
+
+    #[tokio::test]
+    async fn retried_attempt_starts_without_previous_response() {
+        let attempts = Arc::new(Mutex::new(Vec::new()));
+        let mut interceptors = Interceptors::new();
+        interceptors.with_client_interceptor(AttemptRecorder(attempts.clone()));
+        let mut cfg = test_config(vec![503, 200]);
+
+        let output = invoke(
+            "GetObject",
+            (),
+            &mut interceptors,
+            &RuntimePlugins::new(),
+            &mut cfg,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(output, "success");
+        assert_eq!(*attempts.lock().unwrap(), vec![(1, 503), (2, 200)]);
+    }
+}